@@ -0,0 +1,43 @@
+//! Times laying out a document of 1000 paragraphs.
+//!
+//! Run with `cargo bench --bench lay`.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use pdf_doc::*;
+use skia_safe::{textlayout::FontCollection, FontMgr};
+
+const PARS: usize = 1000;
+const ITERS: u32 = 5;
+const TXT: &str = "Quantum Chromodynamics (QCD) is the theory describing the strong force, one of nature's fundamental interactions. Quarks and gluons are the particles governed by QCD.";
+
+fn main() {
+    let mut doc = new_ansi_letter();
+    for idx in 0..PARS {
+        // Every tenth paragraph has a run with its own style.
+        match idx % 10 {
+            0 => doc.add_par(par(TXT).add_run(" Bold run.", Some(Style::Bold))),
+            _ => doc.add_par(par(TXT)),
+        }
+    }
+
+    // Load fonts before timing so only layout is measured.
+    let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+    let font_mgr = FontMgr::new();
+    doc.load_fnts(&mut fnts, &font_mgr)
+        .expect("Failed to load fonts");
+    doc.lay_pags(&mut fnts, &font_mgr)
+        .expect("Failed to lay out pages");
+
+    let beg = Instant::now();
+    for _ in 0..ITERS {
+        doc.lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+    }
+    println!(
+        "lay_pags: {} paragraphs in {:?} per iteration",
+        PARS,
+        beg.elapsed() / ITERS
+    );
+}
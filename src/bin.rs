@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::Path;
+
+use crate::doc::*;
+use crate::err::*;
+
+impl Doc {
+    /// Serialize the document to compact _MessagePack_ bytes.
+    ///
+    /// Fields are encoded by name, as skipped optional fields
+    /// leave positional formats such as _bincode_ unable to decode.
+    pub fn to_bin(&self) -> Result<Vec<u8>, DocError> {
+        rmp_serde::to_vec_named(self).map_err(DocError::from)
+    }
+
+    /// Deserialize a document from _MessagePack_ bytes.
+    pub fn from_bin(bin: &[u8]) -> Result<Doc, DocError> {
+        rmp_serde::from_slice(bin).map_err(DocError::from)
+    }
+
+    /// Save the document as a binary file.
+    ///
+    /// `.bin` file suffix is automatically appended.
+    pub fn save_bin<P>(&self, pth: P) -> Result<(), DocError>
+    where
+        P: AsRef<Path>,
+    {
+        let bin = self.to_bin()?;
+        let file_path = pth.as_ref().with_extension("bin");
+        fs::write(file_path, bin).map_err(DocError::FileError)
+    }
+
+    /// Read a binary file from disk.
+    ///
+    /// `.bin` file suffix is automatically appended.
+    pub fn read_bin<P>(pth: P) -> Result<Doc, DocError>
+    where
+        P: AsRef<Path>,
+    {
+        let file_path = pth.as_ref().with_extension("bin");
+        let bin = fs::read(file_path).map_err(DocError::from)?;
+        Doc::from_bin(&bin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inch::*;
+
+    #[test]
+    fn test_bin_round_trip() {
+        let mut org = new_a4().set_title("Report");
+        org.add_par(par("Hello"));
+        let data: Vec<u8> = (0..=255).collect();
+        org.add_img(data.clone(), In(1.0), In(1.0));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_bin");
+        org.save_bin(&pth).expect("Failed to save bin");
+        let de = Doc::read_bin(&pth).expect("Failed to read bin");
+        assert_eq!(de.meta.title, "Report");
+        assert!(matches!(&de.elms[1], Elm::Img(img) if img.data == data));
+        assert_eq!(de.to_bin().unwrap(), org.to_bin().unwrap());
+
+        // Binary is smaller than the pretty JSON of the same document.
+        assert!(org.to_bin().unwrap().len() < org.to_json().unwrap().len());
+
+        assert!(matches!(Doc::from_bin(&[0xc1]), Err(DocError::BinError(_))));
+    }
+}
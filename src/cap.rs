@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::doc::*;
+use crate::err::*;
+use crate::fnt::*;
+use skia_safe::{
+    textlayout::{FontCollection, Paragraph},
+    Canvas, FontMgr, Point,
+};
+
+/// Height of a capital letter as a fraction of its font size.
+const CAP_RTO: f32 = 0.7;
+
+/// Gap between a drop cap and the text following it
+/// as a fraction of the paragraph font size.
+const CAP_GAP: f32 = 0.15;
+
+/// Length in bytes of a placeholder in a paragraph's text.
+pub(crate) const PLC_LEN: usize = '\u{FFFC}'.len_utf8();
+
+impl Par {
+    /// Returns the first character of the paragraph text.
+    fn fst_chr(&self) -> Option<char> {
+        if self.runs.is_empty() {
+            self.txt.chars().next()
+        } else {
+            self.runs.iter().find_map(|run| run.txt.chars().next())
+        }
+    }
+
+    /// Returns the paragraph without its first character
+    /// or first line indentation, following a drop cap.
+    pub fn cap_bdy(&self) -> Par {
+        let mut ret = self.clone().set_has_ind(Some(false));
+        let txt = if ret.runs.is_empty() {
+            Some(&mut ret.txt)
+        } else {
+            ret.runs
+                .iter_mut()
+                .map(|run| &mut run.txt)
+                .find(|txt| !txt.is_empty())
+        };
+        if let Some(txt) = txt {
+            if let Some(c) = txt.chars().next() {
+                txt.drain(..c.len_utf8());
+            }
+        }
+        ret
+    }
+}
+
+impl Doc {
+    /// Returns the paragraph of a paragraph's drop cap,
+    /// or `None` when the paragraph has no drop cap.
+    ///
+    /// The drop cap is sized so that its capital letter spans
+    /// from the top of the first line to the baseline of the last line.
+    pub fn cap_par(&self, par: &Par) -> Option<Par> {
+        let lnes = par.drop_cap.filter(|lnes| *lnes > 0)?;
+        if !par.tabs.is_empty() {
+            return None;
+        }
+        let cap = par.fst_chr().filter(|c| !c.is_whitespace())?;
+
+        // The drop cap takes the style of the text it begins.
+        let run = par.runs.iter().find(|run| !run.txt.is_empty());
+        let fnt_sze = par.fnt_sze.unwrap_or(self.fnt_sze);
+        let cap_sze = (lnes - 1) as f32 * self.base_lne_hgt(par) / CAP_RTO + fnt_sze;
+        Some(Par {
+            fnt: run
+                .and_then(|run| run.fnt.clone())
+                .or_else(|| par.fnt.clone()),
+            fnt_sze: Some(cap_sze),
+            fnt_sty: run.and_then(|run| run.fnt_sty).or(par.fnt_sty),
+            clr: run.and_then(|run| run.clr).or(par.clr),
+            aln: Some(Align::Left),
+            spc_lne: Some(LineSpace::Single),
+            has_ind: Some(false),
+            stroke: par.stroke,
+            txt: cap.to_string(),
+            ..Default::default()
+        })
+    }
+
+    /// Builds the text following a drop cap laid out to a width in points.
+    ///
+    /// A placeholder the width of the drop cap begins each line
+    /// spanned by the drop cap.
+    pub(crate) fn bld_cap_bdy(
+        &self,
+        par: &Par,
+        cap: &Par,
+        wid: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Paragraph, DocError> {
+        let fnt_sze = par.fnt_sze.unwrap_or(self.fnt_sze);
+        let cap_wid = self.bld_par(cap, fnts, font_mgr)?.longest_line() + CAP_GAP * fnt_sze;
+        let bdy = par.cap_bdy();
+        let lnes = par.drop_cap.unwrap_or_default() as usize;
+
+        // Begin each following line where the previous line ends.
+        let mut brks = vec![0];
+        loop {
+            let mut paragraph = self.bld_txt(&bdy, &brks, cap_wid, fnts, font_mgr)?;
+            paragraph.layout(wid);
+            let lne = brks.len();
+            if lne >= lnes || lne >= paragraph.line_number() {
+                return Ok(paragraph);
+            }
+            let end = paragraph.get_actual_text_range(lne - 1, true).end - PLC_LEN * lne;
+            if end <= brks[lne - 1] {
+                return Ok(paragraph);
+            }
+            brks.push(end);
+        }
+    }
+
+    /// Returns the _UTF-16_ ranges and URLs of linked runs
+    /// of a paragraph built following a drop cap.
+    pub(crate) fn cap_lnk_rngs<'a>(
+        &self,
+        paragraph: &Paragraph,
+        par: &'a Par,
+    ) -> Vec<(Range<usize>, &'a str)> {
+        let cap_len = par.fst_chr().map_or(0, char::len_utf16);
+        let bdy = par.cap_bdy().all_txt();
+
+        // Placeholders begin the lines spanned by the drop cap.
+        let lnes = (par.drop_cap.unwrap_or_default() as usize).min(paragraph.line_number());
+        let brks: Vec<usize> = (0..lnes)
+            .map(|lne| {
+                let beg = paragraph.get_actual_text_range(lne, true).start - PLC_LEN * lne;
+                bdy.get(..beg).map_or(0, |pre| pre.encode_utf16().count())
+            })
+            .collect();
+        let pos = |idx: usize, inc: bool| {
+            let idx = idx.saturating_sub(cap_len);
+            idx + brks
+                .iter()
+                .filter(|brk| **brk < idx || (inc && **brk == idx))
+                .count()
+        };
+
+        par.lnk_rngs(0)
+            .into_iter()
+            .map(|(rng, url)| (pos(rng.start, true)..pos(rng.end, false), url))
+            .collect()
+    }
+
+    /// Draw the drop cap of a paragraph painted at `x` and `y` points.
+    ///
+    /// The drop cap's baseline aligns with the last line it spans.
+    #[allow(clippy::too_many_arguments)]
+    pub fn drw_cap(
+        &self,
+        canvas: &Canvas,
+        paragraph: &Paragraph,
+        par: &Par,
+        x: f32,
+        y: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(), DocError> {
+        let Some(cap) = self.cap_par(&par.tf_par()) else {
+            return Ok(());
+        };
+        let lne = (par.drop_cap.unwrap_or_default() as usize).min(paragraph.line_number());
+        let Some(lm) = paragraph.get_line_metrics_at(lne.saturating_sub(1)) else {
+            return Ok(());
+        };
+        let lft = paragraph
+            .get_rects_for_placeholders()
+            .first()
+            .map_or(0.0, |tb| tb.rect.left);
+
+        let cap = self.bld_par(&cap, fnts, font_mgr)?;
+        let pnt = Point {
+            x: x + lft,
+            y: y + lm.baseline as f32 - cap.alphabetic_baseline(),
+        };
+        cap.paint(canvas, pnt);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inch::*;
+
+    #[test]
+    fn test_cap_bdy() {
+        let par = par("Once upon a time").set_drop_cap(3);
+        assert_eq!(par.cap_bdy().txt, "nce upon a time");
+        assert_eq!(par.cap_bdy().has_ind, Some(false));
+
+        let par = par
+            .set_txt("".into())
+            .add_run("", None)
+            .add_run("Émile", None);
+        assert_eq!(par.fst_chr(), Some('É'));
+        assert_eq!(par.cap_bdy().all_txt(), "mile");
+    }
+
+    #[test]
+    fn test_cap_par() {
+        let doc = new_ansi_letter();
+        assert!(doc.cap_par(&par("Once")).is_none());
+        assert!(doc.cap_par(&par("").set_drop_cap(3)).is_none());
+        assert!(doc.cap_par(&par("Once").set_drop_cap(0)).is_none());
+
+        let cap = doc.cap_par(&par("Once").set_drop_cap(1)).unwrap();
+        assert_eq!(cap.txt, "O");
+        assert_eq!(cap.fnt_sze, Some(doc.fnt_sze));
+        let cap = doc.cap_par(&par("Once").set_drop_cap(3)).unwrap();
+        assert!(cap.fnt_sze.unwrap() > 3.0 * doc.fnt_sze);
+    }
+
+    #[test]
+    fn test_drop_cap_save_pdf() {
+        let mut doc = new_ansi_letter();
+        let txt = "Once upon a time, a paragraph opened with a large drop cap spanning its first three lines while the rest of its text flowed around it and then returned to the full width of the text area.";
+        let cap = par(txt)
+            .set_drop_cap(3)
+            .add_link(" Read more.", "https://example.com");
+        doc.add_par(cap.clone());
+        doc.add_par(par("").set_drop_cap(3));
+
+        // The first lines begin to the right of the drop cap.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let paragraph = doc
+            .bld_par_at(&cap, In(6.5).pt(), &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph");
+        let plcs = paragraph.get_rects_for_placeholders();
+        assert_eq!(plcs.len(), 3);
+        assert!(plcs[0].rect.width() > doc.fnt_sze);
+        for plc in plcs.iter() {
+            assert!(plc.rect.left.abs() < 1.0);
+        }
+        assert!(plcs[2].rect.top > plcs[1].rect.top);
+        let lnks = doc.cap_lnk_rngs(&paragraph, &cap);
+        assert_eq!(lnks.len(), 1);
+        assert_eq!(lnks[0].1, "https://example.com");
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_drop_cap");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+}
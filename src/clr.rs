@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// Opaque black.
+pub const BLACK: Color = Color::rgb(0, 0, 0);
+
+/// Opaque white.
+pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+/// A color with _red_, _green_, _blue_, and _alpha_ channels.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Color {
+    /// The _red_ channel.
+    pub r: u8,
+    /// The _green_ channel.
+    pub g: u8,
+    /// The _blue_ channel.
+    pub b: u8,
+    /// The _alpha_ channel.
+    pub a: u8,
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        BLACK
+    }
+}
+
+impl Color {
+    /// Returns a new [`Color`].
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Returns a new opaque [`Color`].
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+}
+
+impl From<Color> for skia_safe::Color {
+    fn from(clr: Color) -> Self {
+        skia_safe::Color::from_argb(clr.a, clr.r, clr.g, clr.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_round_trip_serialize_deserialize() {
+        let original = Color::new(10, 20, 30, 40);
+
+        // Serialize the `Color` instance to a JSON string
+        let serialized = serde_json::to_string(&original).expect("Failed to serialize");
+        assert_eq!(serialized, r#"{"r":10,"g":20,"b":30,"a":40}"#);
+
+        // Deserialize the JSON string back to a `Color` instance
+        let deserialized: Color = serde_json::from_str(&serialized).expect("Failed to deserialize");
+
+        // Check that the original and deserialized instances are the same
+        assert_eq!(original, deserialized);
+    }
+}
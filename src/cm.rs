@@ -0,0 +1,92 @@
+use crate::inch::*;
+use crate::unit::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter, Result},
+    ops::Deref,
+};
+
+/// A length in centimeters.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Cm(pub f32);
+
+impl Display for Cm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}cm", self.0)
+    }
+}
+
+impl Deref for Cm {
+    type Target = f32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Unit for Cm {
+    fn pt(&self) -> f32 {
+        self.0 * 10.0 * PT_PER_IN / MM_PER_IN
+    }
+}
+
+impl From<Cm> for In {
+    fn from(len: Cm) -> In {
+        In(len.0 * 10.0 / MM_PER_IN)
+    }
+}
+
+impl_unit_ops!(Cm);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Cm(2.54)), "2.54cm");
+    }
+
+    #[test]
+    fn test_to_pt() {
+        assert_eq!(Cm(2.54).pt().round(), 72.0);
+    }
+
+    #[test]
+    fn test_into_in() {
+        let len: In = Cm(5.08).into();
+        assert!((len.0 - 2.0).abs() < 1e-6);
+        assert!((len.pt() - Cm(5.08).pt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_round_trip_serialize_deserialize() {
+        let original = Cm(12.34);
+
+        // Serialize the `Cm` instance to a JSON string
+        let serialized = serde_json::to_string(&original).expect("Failed to serialize");
+
+        // Deserialize the JSON string back to a `Cm` instance
+        let deserialized: Cm = serde_json::from_str(&serialized).expect("Failed to deserialize");
+
+        // Check that the original and deserialized instances are the same
+        assert_eq!(original, deserialized);
+    }
+
+    #[test]
+    fn test_arithmetic_cm() {
+        let a = Cm(10.0);
+        let b = Cm(4.0);
+        assert_eq!((a + b).0, 14.0);
+        assert_eq!((a - b).0, 6.0);
+        assert_eq!((a * 2).0, 20.0);
+        assert_eq!((a / 4.0).0, 2.5);
+        assert_eq!(-a, Cm(-10.0));
+
+        let mut c = a;
+        c += b;
+        c -= 2;
+        c *= 0.5;
+        assert_eq!(c, Cm(6.0));
+    }
+}
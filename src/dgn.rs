@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// _Diagnostics_ reported while rendering a document.
+///
+/// Recoverable problems are reported as warnings rather than errors.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Diagnostics {
+    /// Warnings in the order they occurred.
+    pub wrns: Vec<String>,
+}
+
+impl Diagnostics {
+    /// Appends a warning.
+    pub fn wrn(&mut self, msg: String) {
+        self.wrns.push(msg);
+    }
+
+    /// Returns `true` if there are no warnings.
+    pub fn is_empty(&self) -> bool {
+        self.wrns.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrn() {
+        let mut dgn = Diagnostics::default();
+        assert!(dgn.is_empty());
+        dgn.wrn("Font fallback.".into());
+        assert!(!dgn.is_empty());
+        assert_eq!(dgn.wrns, vec!["Font fallback.".to_string()]);
+    }
+}
@@ -1,57 +1,184 @@
 use std::collections::HashMap;
+use std::fs;
 use std::fs::File;
-use std::io::BufReader;
-use std::io::Write;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
 use std::path::Path;
+use std::time::Duration;
 
+use crate::clr::*;
+use crate::dgn::*;
 use crate::err::*;
+use crate::fit::*;
+use crate::fld::*;
+use crate::fnt::*;
+use crate::frm::*;
+use crate::ftn::*;
+use crate::hng::*;
+use crate::hyp::*;
 use crate::inch::*;
+use crate::lay::*;
+use crate::meta::*;
 use crate::mrg::*;
+use crate::num::*;
+use crate::otl::*;
+use crate::pag::*;
+use crate::qr::*;
 use crate::sze::*;
+use crate::tab::*;
+use crate::tbl::*;
+use crate::txf::*;
 use crate::unit::*;
-use google_fonts::Font;
+use crate::vld::*;
+use crate::wtr::*;
 use serde::{Deserialize, Serialize};
 use skia_safe::{
-    pdf,
+    pdf, surfaces, svg,
     textlayout::{
-        FontCollection, ParagraphBuilder, ParagraphStyle, PlaceholderAlignment, PlaceholderStyle,
-        TextAlign, TextBaseline, TextStyle, TypefaceFontProvider,
+        self, FontCollection, Paragraph, ParagraphStyle, RectHeightStyle, RectWidthStyle,
+        TextAlign, TextStyle, TypefaceFontProvider,
     },
-    Document, FontMgr, FontStyle, Paint, Point,
+    Canvas, Data, Document, EncodedImageFormat, FontMgr, FontStyle, Image, Paint, PaintStyle,
+    Picture, PictureRecorder, Point, Rect,
 };
-use std::collections::hash_map::Entry::Vacant;
 
 /// Creates an _8.5in x 11in_ [`Doc`].
 pub fn new_ansi_letter() -> Doc {
+    new_preset(ANSI_LETTER, MRG_IN_1, In(0.5), LineSpace::Custom(1.35))
+}
+
+/// Creates an _8.5in x 14in_ [`Doc`].
+pub fn new_legal() -> Doc {
+    new_preset(LEGAL, MRG_IN_1, In(0.5), LineSpace::Custom(1.35))
+}
+
+/// Creates a _210mm x 297mm_ [`Doc`].
+pub fn new_a4() -> Doc {
+    new_preset(A4, MRG_MM_25, In(10.0 / MM_PER_IN), LineSpace::Custom(1.3))
+}
+
+/// Creates a _210mm x 297mm_ letter [`Doc`] with _25mm_ margins.
+///
+/// The metric counterpart of [`new_ansi_letter`], keeping its
+/// _0.5in_ indent and line spacing where [`new_a4`] is tighter.
+pub fn new_a4_letter() -> Doc {
+    new_preset(A4, MRG_MM_25, In(0.5), LineSpace::Custom(1.35))
+}
+
+/// Creates a [`Doc`] from a paper size preset.
+fn new_preset(sze: Sze, mrg: Mrg, ind: In, spc_lne: LineSpace) -> Doc {
     Doc::default()
-        .set_sze(ANSI_LETTER)
-        .set_mrg(MRG_IN_1)
-        .set_ind(In(0.5))
+        .set_sze(sze)
+        .set_mrg(mrg)
+        .set_ind(Indent::Fixed(ind))
+        .set_spc_lne(spc_lne)
 }
 
 /// A PDF document.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Doc {
+    /// Metadata of the document.
+    pub meta: DocMeta,
+    /// _Quality_ of raster images embedded in a PDF from `0.0` to `1.0`.
+    ///
+    /// Below `1.0`, opaque images are encoded as _JPEG_ at the quality,
+    /// trading fidelity for smaller files. `1.0` encodes images losslessly.
+    pub pdf_quality: f32,
+    /// Indicates whether PDF streams are _compressed_.
+    pub compress: bool,
     /// Size of the document.
     pub sze: Sze,
     /// Margin lengths of the document.
     pub mrg: Mrg,
-    /// Indentation length of a paragraph's first line.
-    pub ind: In,
+    /// Indentation of a paragraph's first line.
+    pub ind: Indent,
     /// Font for the paragraph.
-    pub fnt: Font,
+    pub fnt: FontSource,
+    /// Font used in place of a font which fails to load.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_fnt: Option<FontSource>,
+    /// Longest wait for each fetch of a Google font over the network.
+    ///
+    /// `None` waits until the fetch completes or fails.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fnt_timeout: Option<Duration>,
     /// The size of the font in points.
     pub fnt_sze: f32,
+    /// Smallest font size in points reached by [`Doc::fit_to_page`].
+    pub min_fnt_sze: f32,
     /// Font _style_ of the document.
     pub fnt_sty: Style,
+    /// Text _color_ of the document.
+    pub clr: Color,
     /// Text _alignment_ of the document.
     pub aln: Align,
+    /// Text _direction_ of the document.
+    pub direction: TextDirection,
+    /// _Vertical alignment_ of the content of each page between the margins.
+    pub vert_aln: VertAlign,
+    /// Indicates whether long words _hyphenate_ across lines.
+    pub hyphenate: bool,
+    /// _Language_ tag of the text, such as `en-US`, picking the hyphenation patterns.
+    pub lang: String,
+    /// Hyphenation patterns used in place of the built-in patterns for `lang`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hyp_pats: Option<String>,
+    /// Hyphenator parsed from `hyp_pats`, kept between paragraphs.
+    #[serde(skip)]
+    pub hyp_cache: HypCache,
     /// Line spacing of a document.
     pub spc_lne: LineSpace,
+    /// Spacing _before_ a paragraph.
+    pub spc_par_bfr: LineSpace,
     /// Spacing _after_ a paragraph.
+    ///
+    /// See [`SpacingModel::par_spc`] for the gap in points.
     pub spc_par_aft: LineSpace,
+    /// Indicates whether adjacent after and before spacing _collapse_
+    /// to the larger of the two rather than their sum.
+    pub collapse_spacing: bool,
+    /// Determines how line and paragraph spacing values are interpreted.
+    pub spc_mdl: SpacingModel,
     /// Indicates whether the first line of a paragraph is _indented_.
     pub has_ind: bool,
+    /// Minimum number of lines of a split paragraph at the top of a frame.
+    pub widow_lines: u8,
+    /// Minimum number of lines of a split paragraph at the bottom of a frame.
+    pub orphan_lines: u8,
+    /// _Background color_ filling every page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_clr: Option<Color>,
+    /// _Watermark_ drawn beneath the body of every page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watermark: Option<Watermark>,
+    /// Width of an unsafe _crop zone_ along each page edge.
+    ///
+    /// Elements laid out within the zone are reported as
+    /// warnings when rendering. `None` checks no zone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crop_zone: Option<In>,
+    /// _Header_ drawn at the top of every page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hdr: Option<Par>,
+    /// _Footer_ drawn at the bottom of every page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ftr: Option<Par>,
+    /// Number of _columns_ dividing the margins of a page.
+    pub columns: u8,
+    /// Gap between adjacent _columns_.
+    pub column_gap: In,
+    /// Frames through which `elms` flow, in order.
+    ///
+    /// Empty flows `elms` through the margins of each page.
+    pub frms: Vec<Frame>,
+    /// Values of custom `{{key}}` _fields_ filled in when rendering.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub fields: HashMap<String, String>,
+    /// Format of the `{{date}}` field, such as `%Y-%m-%d`.
+    ///
+    /// See [`fmt_dte`] for the supported specifiers.
+    pub date_fmt: String,
     /// Paragraphs of text.
     pub elms: Vec<Elm>,
 }
@@ -59,31 +186,73 @@ pub struct Doc {
 impl Default for Doc {
     fn default() -> Self {
         Doc {
+            meta: DocMeta::default(),
+            pdf_quality: 1.0,
+            compress: true,
             sze: Sze::default(),
             mrg: Mrg::default(),
-            ind: In::default(),
-            fnt: Font::DomineVariable,
+            ind: Indent::default(),
+            #[cfg(not(test))]
+            fnt: FontSource::default(),
+            // Tests lay out with a bundled font to run without a network.
+            #[cfg(test)]
+            fnt: crate::tst::tst_fnt(),
+            fallback_fnt: None,
+            fnt_timeout: None,
             fnt_sze: 12.0,
+            min_fnt_sze: MIN_FNT_SZE,
             fnt_sty: Style::Normal,
+            clr: BLACK,
             aln: Align::Justify,
+            direction: TextDirection::Ltr,
+            vert_aln: VertAlign::Top,
+            hyphenate: false,
+            lang: "en-US".into(),
+            hyp_pats: None,
+            hyp_cache: HypCache::default(),
             spc_lne: LineSpace::Custom(1.35),
+            spc_par_bfr: LineSpace::Custom(0.0),
             spc_par_aft: LineSpace::Custom(1.35),
+            collapse_spacing: false,
+            spc_mdl: SpacingModel::Multiplier,
             has_ind: true,
+            widow_lines: 2,
+            orphan_lines: 2,
+            bg_clr: None,
+            watermark: None,
+            crop_zone: None,
+            hdr: None,
+            ftr: None,
+            columns: 1,
+            column_gap: In(0.25),
+            frms: Vec::new(),
+            fields: HashMap::new(),
+            date_fmt: DTE_FMT.into(),
             elms: Vec::new(),
         }
     }
 }
 
 impl Doc {
+    /// Serialize the document to a _JSON_ string.
+    pub fn to_json(&self) -> Result<String, DocError> {
+        serde_json::to_string_pretty(self).map_err(DocError::from)
+    }
+
+    /// Deserialize a document from a _JSON_ string.
+    pub fn from_json(json_str: &str) -> Result<Doc, DocError> {
+        serde_json::from_str(json_str).map_err(DocError::from)
+    }
+
     /// Save the document as a _JSON_ file.
-    /// 
+    ///
     /// `.json` file suffix is automatically appended.
     pub fn save_json<P>(&self, pth: P) -> Result<(), DocError>
     where
         P: AsRef<Path>,
     {
         // Serialize doc.
-        let json_str = serde_json::to_string_pretty(self).map_err(DocError::from)?;
+        let json_str = self.to_json()?;
 
         // Append file suffix.
         let file_path = pth.as_ref().with_extension("json");
@@ -99,8 +268,9 @@ impl Doc {
     }
 
     /// Read a JSON file from disk.
-    /// 
+    ///
     /// `.json` file suffix is automatically appended.
+    /// The receiver is unused; see [`Doc::from_json`].
     pub fn read_json<P>(&self, pth: P) -> Result<Doc, DocError>
     where
         P: AsRef<Path>,
@@ -109,624 +279,4399 @@ impl Doc {
         let file_path = pth.as_ref().with_extension("json");
 
         // Load the file.
-        let fle = File::open(file_path).map_err(DocError::from)?;
-        let rdr = BufReader::new(fle);
+        let json_str = fs::read_to_string(file_path).map_err(DocError::from)?;
 
         // Deserialize the JSON into a struct.
-        let ret: Doc = serde_json::from_reader(rdr).map_err(DocError::from)?;
-
-        Ok(ret)
+        Doc::from_json(&json_str)
     }
 
     /// Save the document as a _PDF_ file.
-    /// 
+    ///
     /// `.pdf` file suffix is automatically appended.
-    pub fn save_pdf<P>(&self, pth: P) -> Result<(), DocError>
+    /// The PDF streams to the file as each page closes
+    /// rather than being buffered in memory.
+    ///
+    /// ### Returns
+    ///
+    /// Diagnostics with warnings for recovered problems, such as a font fallback.
+    pub fn save_pdf<P>(&self, pth: P) -> Result<Diagnostics, DocError>
     where
         P: AsRef<Path>,
     {
-        // Create a PDF document.
-        let mut memory = Vec::new();
-        let mut pdf = pdf::new_document(&mut memory, None);
-
-        // Prepare font variables.
-        let mut fnts: HashMap<Font, FontCollection> = HashMap::new();
-        let font_mgr = FontMgr::new();
-
-        // Segment document paragraphs into pages.
-        let pags = self.seg_pags();
-
-        // Write PDF pages.
-        for pars in pags {
-            pdf = self.wrt_pag(pars, pdf, &mut fnts, &font_mgr)?;
-        }
-
-        pdf.close();
-
         // Append file suffix.
         let file_path = pth.as_ref().with_extension("pdf");
 
         // Create file.
-        let mut file = File::create(file_path).map_err(DocError::from)?;
+        let mut file = BufWriter::new(File::create(file_path).map_err(DocError::from)?);
 
         // Write doc to disk.
-        file.write_all(&memory).map_err(DocError::FileError)?;
-
-        Ok(())
+        let dgn = self.write_pdf(&mut file)?;
+        file.flush().map_err(DocError::from)?;
+        Ok(dgn)
     }
 
-    /// Write a PDF page.
-    pub fn wrt_pag<'a>(
-        &'a self,
-        pars: Vec<Par>,
-        pdf: Document<'a>,
-        fnts: &mut HashMap<Font, FontCollection>,
-        font_mgr: &FontMgr,
-    ) -> Result<Document<'a>, DocError> {
-        let mut pdf_pag = pdf.begin_page(self.sze.pt(), None);
+    /// Save the document as a _PDF_ file with fonts from a shared cache.
+    ///
+    /// `.pdf` file suffix is automatically appended.
+    ///
+    /// ### Arguments
+    ///
+    /// * `pth` - The file path.
+    /// * `cache` - Fonts loaded by earlier renders, extended with fonts loaded here.
+    ///
+    /// ### Returns
+    ///
+    /// Diagnostics with warnings for recovered problems, such as a font fallback.
+    pub fn save_pdf_with_cache<P>(
+        &self,
+        pth: P,
+        cache: &mut FontCache,
+    ) -> Result<Diagnostics, DocError>
+    where
+        P: AsRef<Path>,
+    {
+        // Append file suffix.
+        let file_path = pth.as_ref().with_extension("pdf");
 
-        // Write paragraphs.
-        let par_wid = self.sze.width - self.mrg.width();
-        let mut y: f32 = self.mrg.top.pt();
-        for par in pars {
-            // Determine paragraph font collection.
-            let fnt = par.fnt.unwrap_or(self.fnt);
-            if let Vacant(e) = fnts.entry(fnt) {
-                e.insert(create_fnt_col(fnt, font_mgr)?);
-            }
-            let cur_fnt_col = fnts.get(&fnt).unwrap().clone();
-
-            // Determine paragraph text style.
-            let fnt_sze = par.fnt_sze.unwrap_or(self.fnt_sze);
-            let mut cur_ts = TextStyle::new();
-            cur_ts.set_font_families(&[par.fnt.unwrap_or(self.fnt).to_string()]);
-            cur_ts.set_font_size(fnt_sze);
-            cur_ts.set_height(par.spc_lne.unwrap_or(self.spc_lne).val());
-            cur_ts.set_height_override(true);
-            cur_ts.set_foreground_paint(&Paint::default());
-            par.fnt_sty.unwrap_or(self.fnt_sty).set(&mut cur_ts);
-
-            // Determine paragraph style.
-            let mut cur_par_sty = ParagraphStyle::new();
-            par.aln.unwrap_or(self.aln).set(&mut cur_par_sty);
-
-            // Build paragraph.
-            let mut par_bld = ParagraphBuilder::new(&cur_par_sty, &cur_fnt_col);
-            par_bld.push_style(&cur_ts);
-
-            // Determine paragraph first line indentation.
-            if par.has_ind.unwrap_or(self.has_ind) {
-                let ind = par.ind.as_ref().unwrap_or(&self.ind);
-                par_bld.add_placeholder(&PlaceholderStyle {
-                    width: ind.pt(),
-                    height: 0.0,
-                    alignment: PlaceholderAlignment::Baseline,
-                    baseline_offset: 0.0,
-                    baseline: TextBaseline::Alphabetic,
-                });
-            }
+        // Create file.
+        let mut file = BufWriter::new(File::create(file_path).map_err(DocError::from)?);
 
-            // Add paragraph text.
-            par_bld.add_text(&par.txt);
+        // Write doc to disk.
+        let dgn = self.write_pdf_with_cache(&mut file, cache)?;
+        file.flush().map_err(DocError::from)?;
+        Ok(dgn)
+    }
 
-            // Layout paragraph on canvas.
-            let mut paragraph = par_bld.build();
+    /// Returns the document as _PDF_ bytes.
+    pub fn to_pdf_bytes(&self) -> Result<Vec<u8>, DocError> {
+        let mut memory = Vec::new();
+        self.write_pdf(&mut memory)?;
+        Ok(memory)
+    }
 
-            paragraph.layout(par_wid.pt());
+    /// Write the document as a _PDF_ to a writer.
+    ///
+    /// Each page is written to `w` as it closes, and an outline of
+    /// headings is appended after the PDF without buffering it.
+    /// Embedded fonts are always subset to the glyphs the document draws,
+    /// so a large font adds little beyond the glyphs used. Skia's _PDF_
+    /// backend offers no switch to embed whole fonts.
+    ///
+    /// Pages are laid out and written on the calling thread. Each page
+    /// continues the flow of the page before it, and skia paragraphs
+    /// can't move between threads, so layout has no parallel mode.
+    ///
+    /// ### Arguments
+    ///
+    /// * `w` - The writer receiving the PDF.
+    ///
+    /// ### Returns
+    ///
+    /// Diagnostics with warnings for recovered problems, such as a font fallback.
+    pub fn write_pdf<W>(&self, w: &mut W) -> Result<Diagnostics, DocError>
+    where
+        W: Write,
+    {
+        self.write_pdf_with_cache(w, &mut FontCache::new())
+    }
 
-            // Paint paragraph to canvas.
-            paragraph.paint(
-                pdf_pag.canvas(),
-                Point {
-                    x: self.mrg.lft.pt(),
-                    y,
-                },
-            );
+    /// Write the document as a _PDF_ to a writer with fonts from a shared cache.
+    ///
+    /// ### Arguments
+    ///
+    /// * `w` - The writer receiving the PDF.
+    /// * `cache` - Fonts loaded by earlier renders, extended with fonts loaded here.
+    ///
+    /// ### Returns
+    ///
+    /// Diagnostics with warnings for recovered problems, such as a font fallback.
+    /// A font fallback is reported only by the render first loading the font.
+    pub fn write_pdf_with_cache<W>(
+        &self,
+        w: &mut W,
+        cache: &mut FontCache,
+    ) -> Result<Diagnostics, DocError>
+    where
+        W: Write,
+    {
+        self.fill_fields().wrt_pdf(w, cache)
+    }
 
-            // Determine space after paragraph.
-            let par_spc_aft = par.spc_aft.unwrap_or(self.spc_par_aft);
-            y += paragraph.get_line_metrics_at(0).unwrap().height as f32 * par_spc_aft.val();
+    /// Write the document with its fields filled in as a _PDF_ to a writer.
+    ///
+    /// An outline of headings is appended as the PDF streams to `w`.
+    fn wrt_pdf<W>(&self, w: &mut W, cache: &mut FontCache) -> Result<Diagnostics, DocError>
+    where
+        W: Write,
+    {
+        // Check settings before writing.
+        self.validate()?;
 
-            // Prepare for layout of next paragraph.
-            y += paragraph.height();
-        }
+        // Prepare font variables.
+        let fnts = &mut cache.fnts;
+        let font_mgr = &cache.font_mgr;
 
-        Ok(pdf_pag.end_page())
-    }
+        // Load fonts, falling back where a font fails to load.
+        // A font failing without a fallback fails again where it is
+        // first laid out, naming the page and element using it.
+        let mut dgn = match self.load_fnts(fnts, font_mgr) {
+            Ok(dgn) => dgn,
+            Err(err) => return Err(self.lay_secs(fnts, font_mgr).err().unwrap_or(err)),
+        };
 
-    /// Segments `elms` into pages of paragraphs.
-    pub fn seg_pags(&self) -> Vec<Vec<Par>> {
-        let mut pages: Vec<Vec<Par>> = vec![];
-        let mut current_page: Vec<Par> = vec![];
+        // Lay out document elements into pages.
+        let (pags, geos) = self.lay_secs(fnts, font_mgr)?;
 
-        for elm in &self.elms {
-            match elm {
-                Elm::Par(par) => current_page.push(par.clone()),
-                Elm::PagBrk => {
-                    // Start a new page
-                    if !current_page.is_empty() {
-                        pages.push(current_page);
-                        current_page = vec![];
-                    }
-                }
+        // Warn of elements within the crop zone.
+        if let Some(zne) = self.crop_zone {
+            for wrn in crop_wrns(&pags, &geos, zne) {
+                dgn.wrn(wrn.to_string());
             }
         }
 
-        // Add the last page if it has any paragraphs
-        if !current_page.is_empty() {
-            pages.push(current_page);
-        }
-
-        pages
-    }
-
-    /// Copies and appends paragraphs from another document.
-    pub fn copy_pars(&mut self, doc: Doc) {
-        self.elms.extend(doc.elms.iter().cloned())
-    }
-
-    /// Adds a _paragraph_ to the end of the document.
-    pub fn add_par(&mut self, par: Par) {
-        self.elms.push(Elm::Par(par));
-    }
+        // Write PDF pages, and the outline of headings.
+        let bmks = nest(self.bmks(&pags));
+        let mut w = PdfWtr::new(w);
+        self.wrt_pags(&mut w, &pags, &geos, fnts, font_mgr)?;
+        add_otl(&mut w, &bmks)?;
 
-    /// Adds a _page break_ to the end of the document.
-    pub fn add_pag_brk(&mut self) {
-        self.elms.push(Elm::PagBrk);
+        Ok(dgn)
     }
 
-    /// Replace text within a paragraph.
-    pub fn replace_par_at(&mut self, idx: usize, from: &str, to: &str) {
-        if let Some(Elm::Par(ref mut par)) = self.elms.get_mut(idx) {
-            par.txt = par.txt.replace(from, to);
+    /// Write laid out pages as a _PDF_ to a writer.
+    fn wrt_pags<W>(
+        &self,
+        w: &mut W,
+        pags: &[Vec<Lay>],
+        geos: &[(Sze, Mrg)],
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(), DocError>
+    where
+        W: Write,
+    {
+        let mut pdf = pdf::new_document(w, Some(&self.pdf_meta()));
+        for (pag_idx, (lays, &(sze, mrg))) in pags.iter().zip(geos).enumerate() {
+            let pag = Pag::new(pag_idx, PageNumber::new(pag_idx + 1, pags.len()), sze, mrg);
+            pdf = self.wrt_pag(pag, lays, pdf, fnts, font_mgr)?;
         }
-    }
+        pdf.close();
 
-    /// Clone the document and clear all elements.
-    pub fn clone_clear(&self) -> Self {
-        let mut ret = self.clone();
-        ret.elms.clear();
-        ret
+        Ok(())
     }
 
-    /// Sets the _size_ of the document.
-    ///
-    /// ### Arguments
-    ///
-    /// * `sze` - The new size of the document.
-    ///
-    /// ### Returns
+    /// Render a page as a _PNG_ image at a target _DPI_.
     ///
-    /// Self with updated size.
-    pub fn set_sze(mut self, sze: Sze) -> Self {
-        self.sze = sze;
-        self
+    /// Pixel dimensions are the page size in inches times `dpi`.
+    pub fn render_page_png_dpi(&self, pag_idx: usize, dpi: f32) -> Result<Vec<u8>, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+        let (fnts, font_mgr) = (&mut cache.fnts, &cache.font_mgr);
+        let (pags, geos) = doc.lay_secs(fnts, font_mgr)?;
+        let lays = pags.get(pag_idx).ok_or(DocError::PageIndexError(pag_idx))?;
+        let (sze, mrg) = geos[pag_idx];
+        let pag = Pag::new(pag_idx, PageNumber::new(pag_idx + 1, pags.len()), sze, mrg);
+        doc.rnd_png(pag, lays, dpi, fnts, font_mgr)
     }
 
-    /// Sets the _margin_ lengths of the document.
+    /// Saves each page as a _PNG_ image at a target _DPI_.
+    ///
+    /// Files are named `page-001.png`, `page-002.png`, etc.
     ///
     /// ### Arguments
     ///
-    /// * `mrg` - The new margin lengths.
+    /// * `dir` - The directory to write images to; created if missing.
+    /// * `dpi` - The resolution of the images.
     ///
     /// ### Returns
     ///
-    /// Self with updated margins.
-    pub fn set_mrg(mut self, mrg: Mrg) -> Self {
-        self.mrg = mrg;
-        self
+    /// The number of pages written.
+    pub fn save_png<P>(&self, dir: P, dpi: f32) -> Result<usize, DocError>
+    where
+        P: AsRef<Path>,
+    {
+        let (doc, mut cache) = self.prep_lay()?;
+        let (fnts, font_mgr) = (&mut cache.fnts, &cache.font_mgr);
+        let (pags, geos) = doc.lay_secs(fnts, font_mgr)?;
+
+        fs::create_dir_all(dir.as_ref()).map_err(DocError::FileError)?;
+        for (pag_idx, (lays, &(sze, mrg))) in pags.iter().zip(&geos).enumerate() {
+            let pag = Pag::new(pag_idx, PageNumber::new(pag_idx + 1, pags.len()), sze, mrg);
+            let data = doc.rnd_png(pag, lays, dpi, fnts, font_mgr)?;
+            let file_path = dir.as_ref().join(format!("page-{:03}.png", pag_idx + 1));
+            fs::write(file_path, data).map_err(DocError::FileError)?;
+        }
+
+        Ok(pags.len())
     }
 
-    /// Sets the _indentation_ length of a paragraph's first line.
-    ///
-    /// ### Arguments
+    /// Saves each page as a _PNG_ image at a target _DPI_,
+    /// rendering pages in parallel.
     ///
-    /// * `ind` - The new indentation length.
+    /// Files match those of [`Doc::save_png`].
     ///
-    /// ### Returns
+    /// Skia paragraphs and font collections can't move between threads,
+    /// and each page continues the flow of the page before it. So the
+    /// document is laid out once and each page's drawing recorded on the
+    /// calling thread, then pages are rasterized and encoded in parallel.
+    /// Rasterizing and encoding dominate for large documents.
     ///
-    /// Self with updated indentation length.
-    pub fn set_ind(mut self, ind: In) -> Self {
-        self.ind = ind;
-        self
-    }
-
-    /// Sets the _font_ for the paragraph.
+    /// _PDF_ output stays sequential, as pages are written in order
+    /// to a single skia document.
     ///
     /// ### Arguments
     ///
-    /// * `fnt` - The new font.
+    /// * `dir` - The directory to write images to; created if missing.
+    /// * `dpi` - The resolution of the images.
     ///
     /// ### Returns
     ///
-    /// Self with updated font.
-    pub fn set_fnt(mut self, fnt: Font) -> Self {
-        self.fnt = fnt;
-        self
+    /// The number of pages written.
+    #[cfg(feature = "rayon")]
+    pub fn save_png_par<P>(&self, dir: P, dpi: f32) -> Result<usize, DocError>
+    where
+        P: AsRef<Path>,
+    {
+        use rayon::prelude::*;
+
+        let (doc, mut cache) = self.prep_lay()?;
+        let (fnts, font_mgr) = (&mut cache.fnts, &cache.font_mgr);
+        let (pags, geos) = doc.lay_secs(fnts, font_mgr)?;
+
+        // Record each page once; recorded pictures move between threads.
+        let mut pics = Vec::with_capacity(pags.len());
+        for (pag_idx, (lays, &(sze, mrg))) in pags.iter().zip(&geos).enumerate() {
+            let pag = Pag::new(pag_idx, PageNumber::new(pag_idx + 1, pags.len()), sze, mrg);
+            pics.push((doc.rec_pag(pag, lays, fnts, font_mgr)?, sze));
+        }
+
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(DocError::FileError)?;
+        pics.par_iter()
+            .enumerate()
+            .try_for_each(|(pag_idx, (pic, sze))| {
+                let data = rst_png(pic, *sze, dpi)?;
+                let file_path = dir.join(format!("page-{:03}.png", pag_idx + 1));
+                fs::write(file_path, data).map_err(DocError::FileError)
+            })?;
+
+        Ok(pics.len())
     }
 
-    /// Sets the _font size_ in points.
+    /// Saves each page as a standalone _SVG_ image.
+    ///
+    /// Files are named `page-001.svg`, `page-002.svg`, etc.
     ///
     /// ### Arguments
     ///
-    /// * `fnt_sze` - The new font size.
+    /// * `dir` - The directory to write images to; created if missing.
     ///
     /// ### Returns
     ///
-    /// Self with updated font size.
-    pub fn set_fnt_sze(mut self, fnt_sze: f32) -> Self {
-        self.fnt_sze = fnt_sze;
-        self
+    /// The number of pages written.
+    pub fn save_svg<P>(&self, dir: P) -> Result<usize, DocError>
+    where
+        P: AsRef<Path>,
+    {
+        let (doc, mut cache) = self.prep_lay()?;
+        let (fnts, font_mgr) = (&mut cache.fnts, &cache.font_mgr);
+        let (pags, geos) = doc.lay_secs(fnts, font_mgr)?;
+
+        fs::create_dir_all(dir.as_ref()).map_err(DocError::FileError)?;
+        for (pag_idx, (lays, &(sze, mrg))) in pags.iter().zip(&geos).enumerate() {
+            // Draw the page onto an SVG canvas sized in points.
+            let (wid, hgt) = sze.pt();
+            let canvas = svg::Canvas::new(Rect::from_wh(wid, hgt), None);
+            let pag = Pag::new(pag_idx, PageNumber::new(pag_idx + 1, pags.len()), sze, mrg);
+            doc.drw_pag(pag, lays, &canvas, fnts, font_mgr)?;
+            let data = canvas.end();
+
+            let file_path = dir.as_ref().join(format!("page-{:03}.svg", pag_idx + 1));
+            fs::write(file_path, data.as_bytes()).map_err(DocError::FileError)?;
+        }
+
+        Ok(pags.len())
     }
 
-    /// Sets the _font style_ of the document.
-    ///
-    /// ### Arguments
-    ///
-    /// * `sty` - The new text style.
-    ///
-    /// ### Returns
-    ///
-    /// Self with updated text style.
-    pub fn set_fnt_sty(mut self, sty: Style) -> Self {
-        self.fnt_sty = sty;
-        self
+    /// Renders laid out elements of a page as a _PNG_ image.
+    fn rnd_png(
+        &self,
+        pag: Pag,
+        lays: &[Lay],
+        dpi: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Vec<u8>, DocError> {
+        let pic = self.rec_pag(pag, lays, fnts, font_mgr)?;
+        rst_png(&pic, pag.sze, dpi)
     }
 
-    /// Sets the _text alignment_ of the document.
-    ///
-    /// ### Arguments
-    ///
-    /// * `aln` - The new text alignment.
-    ///
-    /// ### Returns
-    ///
-    /// Self with updated text alignment.
-    pub fn set_aln(mut self, aln: Align) -> Self {
-        self.aln = aln;
-        self
+    /// Records the drawing of a page of laid out elements in units of _points_.
+    fn rec_pag(
+        &self,
+        pag: Pag,
+        lays: &[Lay],
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Picture, DocError> {
+        let (wid, hgt) = pag.sze.pt();
+        let mut rec = PictureRecorder::new();
+        let canvas = rec.begin_recording(Rect::from_wh(wid, hgt), None);
+        self.drw_pag(pag, lays, canvas, fnts, font_mgr)?;
+        rec.finish_recording_as_picture(None)
+            .ok_or_else(|| DocError::from_encode("Unable to record page."))
     }
 
-    /// Sets the _line spacing_ of the document.
-    ///
-    /// ### Arguments
+    /// Write a PDF page of laid out elements.
     ///
-    /// * `spc_lne` - The new line spacing.
+    /// Every element is written where [`Doc::lay_secs`] placed it,
+    /// so the PDF has the pages of [`Doc::page_count`]. The header and
+    /// footer heights were taken once by the layout, and aren't measured again.
     ///
     /// ### Returns
     ///
-    /// Self with updated line spacing.
-    pub fn set_spc_lne(mut self, spc_lne: LineSpace) -> Self {
-        self.spc_lne = spc_lne;
-        self
+    /// The document with the page written.
+    pub fn wrt_pag<'a>(
+        &'a self,
+        pag: Pag,
+        lays: &[Lay],
+        pdf: Document<'a>,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Document<'a>, DocError> {
+        let mut pdf_pag = pdf.begin_page(pag.sze.pt(), None);
+        self.drw_pag(pag, lays, pdf_pag.canvas(), fnts, font_mgr)?;
+        Ok(pdf_pag.end_page())
     }
 
-    /// Sets the _spacing after_ paragraphs in the document.
+    /// Returns the header and footer drawn on a page,
+    /// with page number tokens replaced by its number.
     ///
     /// ### Arguments
     ///
-    /// * `spc_par_aft` - The new line spacing after paragraphs. Can be set using the `LineSpace` enum.
-    ///
-    /// ### Returns
-    ///
-    /// Self with updated spacing after paragraphs.
-    pub fn set_spc_par_aft(mut self, spc_par_aft: LineSpace) -> Self {
-        self.spc_par_aft = spc_par_aft;
-        self
+    /// * `num` - The page number and number of pages.
+    pub fn pag_hdr_ftr(&self, num: PageNumber) -> (Option<Par>, Option<Par>) {
+        let fill = |par: &Option<Par>| par.as_ref().map(|par| par.with_pag_num(num));
+        (fill(&self.hdr), fill(&self.ftr))
     }
 
-    /// Sets whether the first line of a paragraph is _indented_.
-    ///
-    /// ### Arguments
+    /// Draw a page of laid out elements to a canvas in units of _points_.
     ///
-    /// * `has_ind` - `true` if the first line is indented, `false` otherwise.
-    ///
-    /// ### Returns
+    /// The header and footer are drawn on every page within the
+    /// page's margins with page number tokens replaced by its number.
+    /// See [`Doc::pag_hdr_ftr`].
     ///
-    /// Self with updated indentation setting.
-    pub fn set_has_ind(mut self, has_ind: bool) -> Self {
-        self.has_ind = has_ind;
-        self
-    }
-}
+    /// Errors are wrapped in [`DocError::RenderError`] naming
+    /// the page index and the failing element's index.
+    pub fn drw_pag(
+        &self,
+        pag: Pag,
+        lays: &[Lay],
+        canvas: &Canvas,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(), DocError> {
+        // Draw the background and watermark beneath the body.
+        self.drw_bg(canvas, pag.sze, fnts, font_mgr)?;
+        let bdy_wid = (pag.sze.width - pag.mrg.width()).pt();
 
-/// Determines the style of text in a paragraph.
-///
-/// - `Normal`: The default, unstyled text.
-/// - `Italic`: Text that's styled with an italic font.
-/// - `Bold`: Text that's styled with a bold font.
-/// - `BoldItalic`: Text that's styled with both bold and italic fonts.
-#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
-pub enum Style {
-    /// The default, unstyled text.
-    #[default]
-    Normal,
-    /// Text that's styled with an italic font.
-    Italic,
-    /// Text that's styled with a bold font.
-    Bold,
-    /// Text that's styled with both bold and italic fonts.
-    BoldItalic,
-}
+        // Draw the header at the top margin.
+        let (hdr, ftr) = self.pag_hdr_ftr(pag.num);
+        if let Some(hdr) = &hdr {
+            let mut paragraph = self.bld_par(hdr, fnts, font_mgr)?;
+            paragraph.layout(hdr.txt_wid(bdy_wid));
+            let x = pag.mrg.lft.pt() + hdr.ind_lft.unwrap_or_default().pt();
+            let y = pag.mrg.top.pt();
+            paragraph.paint(canvas, Point { x, y });
+            self.drw_ldrs(canvas, &paragraph, hdr, x, y, fnts, font_mgr)?;
+            self.drw_lnks(canvas, &paragraph, hdr, x, y, None);
+        }
 
-impl Style {
-    pub fn set(self, ts: &mut TextStyle) {
-        match self {
-            Style::Normal => ts.set_font_style(FontStyle::normal()),
-            Style::Italic => ts.set_font_style(FontStyle::italic()),
-            Style::Bold => ts.set_font_style(FontStyle::bold()),
-            Style::BoldItalic => ts.set_font_style(FontStyle::bold_italic()),
-        };
-    }
-}
+        // Draw the footer above the bottom margin.
+        if let Some(ftr) = &ftr {
+            let mut paragraph = self.bld_par(ftr, fnts, font_mgr)?;
+            paragraph.layout(ftr.txt_wid(bdy_wid));
+            let x = pag.mrg.lft.pt() + ftr.ind_lft.unwrap_or_default().pt();
+            let y = (pag.sze.height - pag.mrg.btm).pt() - paragraph.height();
+            paragraph.paint(canvas, Point { x, y });
+            self.drw_ldrs(canvas, &paragraph, ftr, x, y, fnts, font_mgr)?;
+            self.drw_lnks(canvas, &paragraph, ftr, x, y, None);
+        }
 
-/// Determines _horizontal_ text alignment of a paragraph.
-///
-/// - `Left`: Aligns text to the left edge of the paragraph.
-/// - `Right`: Aligns text to the right edge of the paragraph.
-/// - `Center`: Centers the text horizontally within the paragraph.
-/// - `Justify`: Stretches the text to ensure that each line has
-///     equal width. The last line is aligned to the left.
-#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
-pub enum Align {
-    /// Aligns text to the left edge of the paragraph.
-    #[default]
-    Left,
-    /// Aligns text to the right edge of the paragraph.
-    Right,
-    /// Centers the text horizontally within the paragraph.
-    Center,
-    /// Stretches the text to ensure that each line has
-    /// equal width.
-    ///
-    /// The last line is aligned to the left.
-    Justify,
-}
+        for lay in lays {
+            // Draw the border around each piece of a paragraph and its padding.
+            if let (LayCnt::Par(_), Some(Elm::Par(par))) = (&lay.cnt, self.elms.get(lay.idx)) {
+                if let Some(border) = par.border {
+                    let pad = par.pad();
+                    let rect =
+                        Rect::from_xywh(lay.x, lay.y, lay.wid, lay.hgt).with_outset((pad, pad));
+                    border.wrt(canvas, rect);
+                }
+            }
 
-impl Align {
-    pub fn set(self, ps: &mut ParagraphStyle) {
-        match self {
-            Align::Left => ps.set_text_align(TextAlign::Left),
-            Align::Right => ps.set_text_align(TextAlign::Right),
-            Align::Center => ps.set_text_align(TextAlign::Center),
-            Align::Justify => ps.set_text_align(TextAlign::Justify),
-        };
-    }
-}
+            // Clip the lines of a split paragraph to the element.
+            let clp = lay
+                .is_spl()
+                .then(|| Rect::from_xywh(0.0, lay.y, pag.sze.width.pt(), lay.hgt));
+            if let Some(clp) = clp {
+                canvas.save();
+                canvas.clip_rect(clp, None, None);
+            }
+            let y = lay.y - lay.ofs;
 
-/// Determines the amount of space between lines of a paragraph.
-///
-/// - `Single`: Single line spacing.
-/// - `Double`: Double line spacing.
-/// - `Custom(f32)`: Custom line spacing specified by a floating-point value.
-///
-#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
-pub enum LineSpace {
-    #[default]
-    Single,
-    Double,
-    Custom(f32),
-}
+            match &lay.cnt {
+                LayCnt::Par(paragraph) => {
+                    if let Some(Elm::Par(par)) = self.elms.get(lay.idx) {
+                        drw_hlt(canvas, paragraph, par, lay.x, y, lay.wid);
+                    }
+                    paragraph.paint(canvas, Point { x: lay.x, y });
+                    if let Some(Elm::Par(par)) = self.elms.get(lay.idx) {
+                        self.drw_cap(canvas, paragraph, par, lay.x, y, fnts, font_mgr)
+                            .map_err(|err| DocError::render(pag.idx, lay.idx, err))?;
+                        self.drw_ldrs(canvas, paragraph, par, lay.x, y, fnts, font_mgr)
+                            .map_err(|err| DocError::render(pag.idx, lay.idx, err))?;
+                        self.drw_lnks(canvas, paragraph, par, lay.x, y, clp);
 
-impl LineSpace {
-    pub fn val(self) -> f32 {
-        match self {
-            LineSpace::Single => 1.0,
-            LineSpace::Double => 2.0,
-            LineSpace::Custom(val) => val,
+                        // Mark a heading as a named destination.
+                        if par.heading_level.is_some() && lay.ofs == 0.0 {
+                            let name =
+                                Data::new_copy(format!("{}\0", dst_name(lay.idx)).as_bytes());
+                            canvas.annotate_named_destination(Point { x: lay.x, y: lay.y }, &name);
+                        }
+                    }
+                }
+                LayCnt::Svg(svg) => {
+                    svg.wrt(canvas, lay.x, lay.y, font_mgr)
+                        .map_err(|err| DocError::render(pag.idx, lay.idx, err))?;
+                }
+                LayCnt::Img(image) => {
+                    drw_img(
+                        canvas,
+                        image,
+                        Rect::from_xywh(lay.x, lay.y, lay.wid, lay.hgt),
+                    );
+                }
+                LayCnt::Rule(rule) => rule.wrt(canvas, lay.x, lay.y, lay.wid),
+                LayCnt::Qr(data) => {
+                    drw_qr(canvas, data, lay.x, lay.y, lay.wid)
+                        .map_err(|err| DocError::render(pag.idx, lay.idx, err))?;
+                }
+                LayCnt::Itm(mrk, paragraph, itm) => {
+                    mrk.paint(canvas, Point { x: lay.x, y });
+                    let x = lay.x + mrk.max_width();
+                    paragraph.paint(canvas, Point { x, y });
+                    self.drw_lnks(canvas, paragraph, itm, x, y, clp);
+                }
+                LayCnt::Ftn(paragraph, ftn) => {
+                    paragraph.paint(canvas, Point { x: lay.x, y });
+                    self.drw_lnks(canvas, paragraph, ftn, lay.x, y, clp);
+                }
+                LayCnt::Row(cels, border) => {
+                    drw_row(canvas, cels, border.as_ref(), lay.x, lay.y, lay.hgt);
+                    let mut x = lay.x;
+                    for (wid, paragraph, cel) in cels {
+                        self.drw_lnks(canvas, paragraph, cel, x + CEL_PAD, lay.y + CEL_PAD, clp);
+                        x += wid;
+                    }
+                }
+            }
+
+            if clp.is_some() {
+                canvas.restore();
+            }
         }
+
+        Ok(())
     }
-}
 
-/// A _paragraph_ with formatting options.
-///
-/// Formatting options are inherited from the document.
-///
-/// Setting a paragraph option overrides a doc setting.
-///
-/// ### Fields
-///
-/// - `fnt`: Optional font for the paragraph. This is specified as a `Font` type.
-/// - `fnt_sze`: Optional size of the font in points. This is specified as a `f32`.
-/// - `sty`: Optional text _style_ of the paragraph. Possible values are defined in the `Style` enum.
-/// - `aln`: Optional text _alignment_ of the paragraph. Possible values are defined in the `Align` enum.
-/// - `lne_spc`: Optional line spacing of the paragraph. Possible values are defined in the `LineSpace` enum.
-/// - `has_spc_bfr`: Indicates whether there is _space before_ the paragraph. `Some(true)` if there is space before, `Some(false)` otherwise, or `None` if not specified.
-/// - `has_spc_aft`: Indicates whether there is _space after_ the paragraph. `Some(true)` if there is space after, `Some(false)` otherwise, or `None` if not specified.
-/// - `has_ind`: Indicates whether the first line is _indented_. `Some(true)` if the first line is indented, `Some(false)` otherwise, or `None` if not specified.
-/// - `txt`: Text _content_ of the paragraph, specified as a `String`.
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
-pub struct Par {
-    /// Indentation length of the first line.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub ind: Option<In>,
-    /// Font for the paragraph.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fnt: Option<Font>,
-    /// The size of the font in points.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fnt_sze: Option<f32>,
-    /// Font _style_ of the paragraph.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fnt_sty: Option<Style>,
-    /// Text _alignment_ of the paragraph.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub aln: Option<Align>,
-    /// Line spacing of a paragraph.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub spc_lne: Option<LineSpace>,
-    /// Spacing _after_ the paragraph.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub spc_aft: Option<LineSpace>,
-    /// Indicates whether the first line is _indented_.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub has_ind: Option<bool>,
-    /// Text _content_ of the paragraph.
-    pub txt: String,
-}
+    /// Draw link annotations over a paragraph's linked runs
+    /// painted at `x` and `y` points.
+    ///
+    /// Links outside an optional clip rectangle are skipped.
+    pub fn drw_lnks(
+        &self,
+        canvas: &Canvas,
+        paragraph: &Paragraph,
+        par: &Par,
+        x: f32,
+        y: f32,
+        clp: Option<Rect>,
+    ) {
+        // The first line indentation placeholder precedes the text,
+        // or placeholders of a drop cap begin its lines.
+        let ofs = par.has_ind.unwrap_or(self.has_ind) as usize;
+        let tf = par.tf_par();
+        let par = self.hyp_par(&tf);
+        let rngs = match (self.cap_par(&par), self.hng_ind(&par)) {
+            (Some(_), _) => self.cap_lnk_rngs(paragraph, &par),
+            (None, Some(_)) => hng_lnk_rngs(paragraph, &par),
+            (None, None) => par.lnk_rngs(ofs),
+        };
+        for (rng, url) in rngs {
+            let url = Data::new_copy(format!("{}\0", url).as_bytes());
+            for tb in
+                paragraph.get_rects_for_range(rng, RectHeightStyle::Max, RectWidthStyle::Tight)
+            {
+                let rect = tb.rect.with_offset((x, y));
+                if clp.is_none_or(|clp| (clp.top..clp.bottom).contains(&rect.center_y())) {
+                    canvas.annotate_rect_with_url(rect, &url);
+                }
+            }
+        }
+    }
 
-/// Creates a paragraph with the given text.
-pub fn par(txt: &str) -> Par {
-    Par::default().set_txt(txt.into())
-}
+    /// Returns the pages of `elms` segmented at page and section breaks.
+    ///
+    /// Pages are borrowed views yielded lazily without laying out,
+    /// and pages without elements are skipped.
+    /// See [`Doc::page_indices`] for the rendered page each begins on.
+    pub fn pages(&self) -> impl Iterator<Item = Page<'_>> {
+        let mut pos = 0;
+        self.elms
+            .split(|elm| matches!(elm, Elm::PagBrk | Elm::SecBrk { .. }))
+            .filter_map(move |elms| {
+                let idx = pos;
+                pos += elms.len() + 1;
+                (!elms.is_empty()).then_some(Page { idx, elms })
+            })
+    }
 
-impl Par {
-    /// Replaces all matches of a pattern with another string.
-    pub fn replace(&mut self, from: &str, to: &str) {
-        self.txt = self.txt.replace(from, to)
+    /// Segments `elms` into pages of paragraphs.
+    ///
+    /// Pages without paragraphs are skipped.
+    /// See [`Doc::pages`] for pages of all elements without cloning.
+    pub fn seg_pags(&self) -> Vec<Vec<Par>> {
+        self.pages()
+            .map(|pag| {
+                pag.elms
+                    .iter()
+                    .filter_map(|elm| match elm {
+                        Elm::Par(par) => Some(par.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<Par>>()
+            })
+            .filter(|pars| !pars.is_empty())
+            .collect()
+    }
+
+    /// Copies and appends paragraphs from another document.
+    pub fn copy_pars(&mut self, doc: Doc) {
+        self.elms.extend(doc.elms.iter().cloned())
     }
 
-    /// Sets the _indentation_ length of the first line.
+    /// Appends the elements of another document.
+    ///
+    /// Document-level settings of `other`, such as its size, margins,
+    /// font, and header, are dropped: its elements take this document's
+    /// settings. See [`Doc::copy_pars_flattened`] to keep the look
+    /// of its paragraphs.
     ///
     /// ### Arguments
     ///
-    /// * `ind` - The new indentation length.
+    /// * `other` - The document whose elements are appended.
+    /// * `page_break_between` - Indicates whether `other` begins on a new page.
+    pub fn append(&mut self, other: Doc, page_break_between: bool) {
+        if page_break_between && !self.elms.is_empty() {
+            self.add_pag_brk();
+        }
+        self.elms.extend(other.elms);
+    }
+
+    /// Splits the document into a document for each section
+    /// delimited by _page breaks_.
     ///
-    /// ### Returns
+    /// Each document has the settings of this document.
+    /// Empty sections are skipped.
+    pub fn split_at_breaks(&self) -> Vec<Doc> {
+        let tpl = self.clone_clear();
+        self.elms
+            .split(|elm| matches!(elm, Elm::PagBrk))
+            .filter(|elms| !elms.is_empty())
+            .map(|elms| Doc {
+                elms: elms.to_vec(),
+                ..tpl.clone()
+            })
+            .collect()
+    }
+
+    /// Copies and appends paragraphs from another document
+    /// with settings resolved against the source document.
     ///
-    /// Self with updated indentation length.
-    pub fn set_ind(mut self, ind: Option<In>) -> Self {
-        self.ind = ind;
-        self
+    /// Copied paragraphs look identical regardless of
+    /// the destination document's defaults.
+    pub fn copy_pars_flattened(&mut self, doc: Doc) {
+        self.elms.extend(doc.elms.iter().map(|elm| {
+            match elm {
+                Elm::Par(par) => Elm::Par(par.flatten(&doc)),
+                Elm::List(lst) => Elm::List(List {
+                    itms: lst.itms.iter().map(|itm| itm.flatten(&doc)).collect(),
+                    ..lst.clone()
+                }),
+                Elm::Table {
+                    rows,
+                    col_widths,
+                    border,
+                    header_rows,
+                    col_fmts,
+                } => Elm::Table {
+                    rows: rows
+                        .iter()
+                        .map(|row| row.iter().map(|cel| cel.flatten(&doc)).collect())
+                        .collect(),
+                    col_widths: col_widths.clone(),
+                    border: *border,
+                    header_rows: *header_rows,
+                    col_fmts: col_fmts.clone(),
+                },
+                _ => elm.clone(),
+            }
+        }))
     }
 
-    /// Sets the _font_ for the paragraph.
+    /// Adds a _paragraph_ to the end of the document.
+    pub fn add_par(&mut self, par: Par) {
+        self.elms.push(Elm::Par(par));
+    }
+
+    /// Adds a _list_ to the end of the document.
+    pub fn add_list(&mut self, lst: List) {
+        self.elms.push(Elm::List(lst));
+    }
+
+    /// Adds an _SVG_ graphic to the end of the document.
+    pub fn add_svg(&mut self, svg: Svg) {
+        self.elms.push(Elm::Svg(svg));
+    }
+
+    /// Adds a _PNG_ or _JPEG_ image to the end of the document.
     ///
     /// ### Arguments
     ///
-    /// * `fnt` - The new font.
+    /// * `data` - Encoded image bytes.
+    /// * `wid` - Width of the image.
+    /// * `hgt` - Height of the image.
+    pub fn add_img(&mut self, data: Vec<u8>, wid: In, hgt: In) {
+        self.elms.push(Elm::Img(img(data, wid, hgt)));
+    }
+
+    /// Adds a horizontal _rule_ with default settings to the end of the document.
+    pub fn add_rule(&mut self) {
+        self.elms.push(Elm::Rule(rule()));
+    }
+
+    /// Adds a _frame_ to the end of the flow.
+    pub fn add_frm(&mut self, frm: Frame) {
+        self.frms.push(frm);
+    }
+
+    /// Adds a _page break_ to the end of the document.
+    pub fn add_pag_brk(&mut self) {
+        self.elms.push(Elm::PagBrk);
+    }
+
+    /// Adds a _column break_ to the end of the document.
+    pub fn add_col_brk(&mut self) {
+        self.elms.push(Elm::ColBrk);
+    }
+
+    /// Adds a _section break_ to the end of the document.
     ///
-    /// ### Returns
+    /// ### Arguments
     ///
-    /// Self with updated font.
-    pub fn set_fnt(mut self, fnt: Option<Font>) -> Self {
-        self.fnt = fnt;
-        self
+    /// * `sze` - Size of the following pages, or `None` to keep the current size.
+    /// * `mrg` - Margins of the following pages, or `None` to keep the current margins.
+    pub fn add_sec_brk(&mut self, sze: Option<Sze>, mrg: Option<Mrg>) {
+        self.elms.push(Elm::SecBrk { sze, mrg });
     }
 
-    /// Sets the _size_ of the font in points.
+    /// Creates an _8.5in x 11in_ document from plain text.
     ///
-    /// ### Arguments
+    /// See [`Doc::add_text`] for how text maps to elements.
+    pub fn from_text(txt: &str) -> Doc {
+        let mut doc = new_ansi_letter();
+        doc.add_text(txt);
+        doc
+    }
+
+    /// Adds paragraphs of plain text to the end of the document.
     ///
-    /// * `fnt_sze` - The new font size.
+    /// Blank lines separate paragraphs, single newlines break lines
+    /// within a paragraph, and a form feed `\f` adds a page break.
+    /// Trailing whitespace of each line is trimmed.
     ///
-    /// ### Returns
+    /// ### Arguments
     ///
-    /// Self with updated font size.
-    pub fn set_fnt_sze(mut self, fnt_sze: Option<f32>) -> Self {
-        self.fnt_sze = fnt_sze;
-        self
+    /// * `txt` - The plain text.
+    pub fn add_text(&mut self, txt: &str) {
+        for (idx, pag) in txt.split('\x0c').enumerate() {
+            if idx > 0 {
+                self.add_pag_brk();
+            }
+            let mut lnes: Vec<&str> = vec![];
+            for lne in pag.lines().map(str::trim_end).chain(std::iter::once("")) {
+                if !lne.is_empty() {
+                    lnes.push(lne);
+                } else if !lnes.is_empty() {
+                    self.add_par(par(&lnes.join("\n")));
+                    lnes.clear();
+                }
+            }
+        }
     }
 
-    /// Sets the _font style_ of the paragraph.
+    /// Returns the plain text of the document.
     ///
-    /// ### Arguments
+    /// Paragraphs and list items are separated by blank lines,
+    /// table cells by tabs and table rows by newlines,
+    /// and page and section breaks are form feeds `\f`.
+    /// The inverse of [`Doc::from_text`].
+    pub fn to_text(&self) -> String {
+        fn add_blk(ret: &mut String, txt: &str) {
+            if txt.is_empty() {
+                return;
+            }
+            if !ret.is_empty() && !ret.ends_with('\x0c') {
+                ret.push_str("\n\n");
+            }
+            ret.push_str(txt);
+        }
+
+        let mut ret = String::new();
+        for elm in &self.elms {
+            match elm {
+                Elm::Par(par) => add_blk(&mut ret, &par.all_txt()),
+                Elm::List(lst) => lst
+                    .itms
+                    .iter()
+                    .for_each(|itm| add_blk(&mut ret, &itm.all_txt())),
+                Elm::Table { rows, .. } => {
+                    let rows: Vec<String> = rows
+                        .iter()
+                        .map(|row| {
+                            let cels: Vec<String> = row.iter().map(Par::all_txt).collect();
+                            cels.join("\t")
+                        })
+                        .collect();
+                    add_blk(&mut ret, &rows.join("\n"))
+                }
+                Elm::PagBrk | Elm::SecBrk { .. } => ret.push('\x0c'),
+                Elm::Svg(_) | Elm::Img(_) | Elm::Rule(_) | Elm::QrCode { .. } | Elm::ColBrk => {}
+            }
+        }
+        ret
+    }
+
+    /// Returns the `elms` indices of all _page breaks_.
+    pub fn page_break_indices(&self) -> Vec<usize> {
+        self.elms
+            .iter()
+            .enumerate()
+            .filter(|(_, elm)| matches!(elm, Elm::PagBrk))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Returns the number of whitespace delimited words in all paragraphs,
+    /// list items, and table cells.
+    pub fn word_count(&self) -> usize {
+        self.elms
+            .iter()
+            .flat_map(Elm::pars)
+            .map(Par::word_count)
+            .sum()
+    }
+
+    /// Returns the number of _Unicode_ scalar characters in all paragraphs,
+    /// list items, and table cells.
+    pub fn char_count(&self) -> usize {
+        self.elms
+            .iter()
+            .flat_map(Elm::pars)
+            .map(|par| par.all_txt().chars().count())
+            .sum()
+    }
+
+    /// Removes all manual _page breaks_ from the document.
+    pub fn remove_all_page_breaks(&mut self) {
+        self.elms.retain(|elm| !matches!(elm, Elm::PagBrk));
+    }
+
+    /// Replace text within a paragraph.
+    pub fn replace_par_at(&mut self, idx: usize, from: &str, to: &str) {
+        if let Some(Elm::Par(ref mut par)) = self.elms.get_mut(idx) {
+            par.txt = par.txt.replace(from, to);
+        }
+    }
+
+    /// Inserts a _paragraph_ before the paragraph at an ordinal.
     ///
-    /// * `sty` - The new style.
+    /// `par_idx` counts only paragraphs, not the raw indices of `elms`,
+    /// so page breaks and other elements are skipped. A paragraph past
+    /// the last paragraph is added to the end of the document.
     ///
-    /// ### Returns
+    /// ### Arguments
     ///
-    /// Self with updated style.
-    pub fn set_fnt_sty(mut self, sty: Option<Style>) -> Self {
-        self.fnt_sty = sty;
-        self
+    /// * `par_idx` - Ordinal of the paragraph to insert before.
+    /// * `par` - The paragraph to insert.
+    pub fn insert_par_at(&mut self, par_idx: usize, par: Par) {
+        let idx = self.par_elm_idx(par_idx).unwrap_or(self.elms.len());
+        self.elms.insert(idx, Elm::Par(par));
     }
 
-    /// Sets the _alignment_ of the paragraph.
+    /// Removes the _paragraph_ at an ordinal.
+    ///
+    /// `par_idx` counts only paragraphs, not the raw indices of `elms`,
+    /// so page breaks and other elements are skipped.
     ///
     /// ### Arguments
     ///
-    /// * `aln` - The new alignment.
+    /// * `par_idx` - Ordinal of the paragraph to remove.
     ///
     /// ### Returns
     ///
-    /// Self with updated alignment.
-    pub fn set_aln(mut self, aln: Option<Align>) -> Self {
-        self.aln = aln;
+    /// The removed paragraph, or `None` if there are too few paragraphs.
+    pub fn remove_par_at(&mut self, par_idx: usize) -> Option<Par> {
+        let idx = self.par_elm_idx(par_idx)?;
+        match self.elms.remove(idx) {
+            Elm::Par(par) => Some(par),
+            _ => None,
+        }
+    }
+
+    /// Returns the `elms` index of the paragraph at an ordinal.
+    fn par_elm_idx(&self, par_idx: usize) -> Option<usize> {
+        self.elms
+            .iter()
+            .enumerate()
+            .filter(|(_, elm)| matches!(elm, Elm::Par(_)))
+            .nth(par_idx)
+            .map(|(idx, _)| idx)
+    }
+
+    /// Replaces all matches of a pattern with another string in every paragraph,
+    /// including list items, table cells, and the header and footer.
+    pub fn replace_all(&mut self, from: &str, to: &str) {
+        let pars = self
+            .hdr
+            .iter_mut()
+            .chain(self.ftr.iter_mut())
+            .chain(self.elms.iter_mut().flat_map(Elm::pars_mut));
+        for par in pars {
+            par.replace(from, to);
+        }
+    }
+
+    /// Replaces each `{{key}}` in every paragraph with its value in one pass,
+    /// including list items, table cells, and the header and footer.
+    ///
+    /// Keys missing from `vars` are left in place, such as the
+    /// `{{page}}` token. Values are not themselves searched for keys.
+    ///
+    /// Paragraphs and list items visible only if a variable has a value
+    /// are dropped when the variable is absent or empty.
+    pub fn render_template(&mut self, vars: &HashMap<String, String>) {
+        self.elms.retain_mut(|elm| match elm {
+            Elm::Par(par) => par.is_visible(vars),
+            Elm::List(lst) => {
+                lst.itms.retain(|itm| itm.is_visible(vars));
+                true
+            }
+            _ => true,
+        });
+        let pars = self
+            .hdr
+            .iter_mut()
+            .chain(self.ftr.iter_mut())
+            .chain(self.elms.iter_mut().flat_map(Elm::pars_mut));
+        for par in pars {
+            par.render_template(vars);
+        }
+    }
+
+    /// Returns a copy of the document with every paragraph's
+    /// inherited settings written as explicit overrides.
+    ///
+    /// The result renders identically even if document defaults change.
+    pub fn flatten(&self) -> Doc {
+        let mut ret = self.clone_clear();
+        ret.copy_pars_flattened(self.clone());
+        ret
+    }
+
+    /// Clone the document and clear all elements.
+    ///
+    /// Settings are cloned without copying the elements.
+    pub fn clone_clear(&self) -> Self {
+        Doc {
+            meta: self.meta.clone(),
+            pdf_quality: self.pdf_quality,
+            compress: self.compress,
+            sze: self.sze,
+            mrg: self.mrg,
+            ind: self.ind,
+            fnt: self.fnt.clone(),
+            fallback_fnt: self.fallback_fnt.clone(),
+            fnt_timeout: self.fnt_timeout,
+            fnt_sze: self.fnt_sze,
+            min_fnt_sze: self.min_fnt_sze,
+            fnt_sty: self.fnt_sty,
+            clr: self.clr,
+            aln: self.aln,
+            direction: self.direction,
+            vert_aln: self.vert_aln,
+            hyphenate: self.hyphenate,
+            lang: self.lang.clone(),
+            hyp_pats: self.hyp_pats.clone(),
+            hyp_cache: self.hyp_cache.clone(),
+            spc_lne: self.spc_lne,
+            spc_par_bfr: self.spc_par_bfr,
+            spc_par_aft: self.spc_par_aft,
+            collapse_spacing: self.collapse_spacing,
+            spc_mdl: self.spc_mdl,
+            has_ind: self.has_ind,
+            widow_lines: self.widow_lines,
+            orphan_lines: self.orphan_lines,
+            bg_clr: self.bg_clr,
+            watermark: self.watermark.clone(),
+            crop_zone: self.crop_zone,
+            hdr: self.hdr.clone(),
+            ftr: self.ftr.clone(),
+            columns: self.columns,
+            column_gap: self.column_gap,
+            frms: self.frms.clone(),
+            fields: self.fields.clone(),
+            date_fmt: self.date_fmt.clone(),
+            elms: vec![],
+        }
+    }
+
+    /// Sets the _title_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `title` - The new title.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated title.
+    pub fn set_title(mut self, title: &str) -> Self {
+        self.meta.title = title.into();
         self
     }
 
-    /// Sets the _line spacing_ of the paragraph.
+    /// Sets the _author_ of the document.
     ///
     /// ### Arguments
     ///
-    /// * `spc_lne` - The new line spacing.
+    /// * `author` - The new author.
     ///
     /// ### Returns
     ///
-    /// Self with updated line spacing.
-    pub fn set_spc_lne(mut self, spc_lne: Option<LineSpace>) -> Self {
-        self.spc_lne = spc_lne;
+    /// Self with updated author.
+    pub fn set_author(mut self, author: &str) -> Self {
+        self.meta.author = author.into();
         self
     }
 
-    /// Sets the _spacing after_ the paragraph.
-    //
+    /// Sets the _subject_ of the document.
+    ///
     /// ### Arguments
-    //
-    /// * `spc_aft` - The new spacing after the paragraph. Can be set using the `LineSpace` enum.
-    //
+    ///
+    /// * `subject` - The new subject.
+    ///
     /// ### Returns
     ///
-    /// Self with updated spacing after the paragraph.
-    pub fn set_spc_aft(mut self, spc_aft: Option<LineSpace>) -> Self {
-        self.spc_aft = spc_aft;
+    /// Self with updated subject.
+    pub fn set_subject(mut self, subject: &str) -> Self {
+        self.meta.subject = subject.into();
         self
     }
 
-    /// Sets whether the first line of the paragraph is _indented_.
+    /// Sets the comma separated _keywords_ of the document.
     ///
     /// ### Arguments
     ///
-    /// * `has_ind` - `true` if the first line is indented, `false` otherwise.
+    /// * `keywords` - The new keywords.
     ///
     /// ### Returns
     ///
-    /// Self with updated indentation setting.
-    pub fn set_has_ind(mut self, has_ind: Option<bool>) -> Self {
-        self.has_ind = has_ind;
+    /// Self with updated keywords.
+    pub fn set_keywords(mut self, keywords: &str) -> Self {
+        self.meta.keywords = keywords.into();
         self
     }
 
-    /// Sets the _text content_ of the paragraph.
+    /// Sets the _creator_ of the document.
     ///
     /// ### Arguments
     ///
-    /// * `txt` - The new text content.
+    /// * `creator` - The new creator.
     ///
     /// ### Returns
     ///
-    /// Self with updated text content.
-    pub fn set_txt(mut self, txt: String) -> Self {
-        self.txt = txt;
+    /// Self with updated creator.
+    pub fn set_creator(mut self, creator: &str) -> Self {
+        self.meta.creator = creator.into();
+        self
+    }
+
+    /// Sets the time the document was _created_.
+    ///
+    /// ### Arguments
+    ///
+    /// * `created` - Seconds since the Unix epoch, or `None` for the time the PDF is written.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated creation time.
+    pub fn set_created(mut self, created: Option<u64>) -> Self {
+        self.meta.created = created;
         self
     }
-}
 
-pub fn create_fnt_col(font: Font, font_mgr: &FontMgr) -> Result<FontCollection, DocError> {
-    // Get font data from network or cache.
-    let font_data = font.get_with_cache().map_err(DocError::from)?;
+    /// Sets the time the document was _modified_.
+    ///
+    /// ### Arguments
+    ///
+    /// * `modified` - Seconds since the Unix epoch, or `None` for the time the PDF is written.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated modification time.
+    pub fn set_modified(mut self, modified: Option<u64>) -> Self {
+        self.meta.modified = modified;
+        self
+    }
 
-    // Load typeface from font data.
-    if let Some(typeface) = font_mgr.new_from_data(&font_data, None) {
-        // Create a font collection.
-        let mut tfp = TypefaceFontProvider::new();
-        tfp.register_typeface(typeface, Some(font.to_string().as_str()));
-        let mut fnt_col = FontCollection::new();
-        fnt_col.set_default_font_manager(Some(tfp.into()), None);
-        return Ok(fnt_col);
+    /// Sets the _quality_ of raster images embedded in a PDF.
+    ///
+    /// Lower quality produces smaller files for image-heavy documents.
+    ///
+    /// ### Arguments
+    ///
+    /// * `pdf_quality` - The new quality from `0.0` to `1.0`, where `1.0` is lossless.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated PDF quality.
+    pub fn set_pdf_quality(mut self, pdf_quality: f32) -> Self {
+        self.pdf_quality = pdf_quality;
+        self
     }
 
-    Err(DocError::from(
-        format!("Unable to parse font `{}`.", font).as_str(),
-    ))
-}
+    /// Sets whether PDF streams are _compressed_.
+    ///
+    /// ### Arguments
+    ///
+    /// * `compress` - `true` to compress, `false` for larger files written faster.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated compression.
+    pub fn set_compress(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
 
-/// Elements of a [`Doc`].
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub enum Elm {
-    /// A _paragraph_ element.
-    Par(Par),
-    /// A _page break_ element.
-    PagBrk,
+    /// Sets the _size_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `sze` - The new size of the document.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated size.
+    pub fn set_sze(mut self, sze: Sze) -> Self {
+        self.sze = sze;
+        self
+    }
+
+    /// Sets the page _orientation_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ori` - The new orientation.
+    ///
+    /// ### Returns
+    ///
+    /// Self with width and height ordered for the orientation.
+    pub fn set_orientation(mut self, ori: Orientation) -> Self {
+        self.sze = self.sze.orient(ori);
+        self
+    }
+
+    /// Sets the _margin_ lengths of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `mrg` - The new margin lengths.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated margins.
+    pub fn set_mrg(mut self, mrg: Mrg) -> Self {
+        self.mrg = mrg;
+        self
+    }
+
+    /// Sets the _margin_ lengths of the document in _millimeters_.
+    ///
+    /// ### Arguments
+    ///
+    /// * `lft` - The new left margin.
+    /// * `rht` - The new right margin.
+    /// * `btm` - The new bottom margin.
+    /// * `top` - The new top margin.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated margins.
+    pub fn set_mrg_mm(self, lft: f32, rht: f32, btm: f32, top: f32) -> Self {
+        self.set_mrg(Mrg::from_mm(lft, rht, btm, top))
+    }
+
+    /// Sets the _indentation_ of a paragraph's first line.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ind` - The new indentation, fixed or relative to the font size.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated indentation.
+    pub fn set_ind(mut self, ind: Indent) -> Self {
+        self.ind = ind;
+        self
+    }
+
+    /// Sets the _font_ for the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fnt` - The new font.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font.
+    pub fn set_fnt(mut self, fnt: FontSource) -> Self {
+        self.fnt = fnt;
+        self
+    }
+
+    /// Sets the _fallback font_ used in place of a font which fails to load.
+    ///
+    /// A fallback is reported as a warning in the returned [`Diagnostics`].
+    ///
+    /// ### Arguments
+    ///
+    /// * `fallback_fnt` - The new fallback font, or `None` to fail on a font load error.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated fallback font.
+    pub fn set_fallback_fnt(mut self, fallback_fnt: Option<FontSource>) -> Self {
+        self.fallback_fnt = fallback_fnt;
+        self
+    }
+
+    /// Sets the longest wait for each fetch of a Google font.
+    ///
+    /// A fetch failing over the network or timing out is retried
+    /// with backoff before failing, or before the fallback font
+    /// replaces the font. A fetch still running after the last wait
+    /// is left to finish on a detached thread.
+    ///
+    /// ### Arguments
+    ///
+    /// * `timeout` - The new longest wait for each fetch.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font fetch timeout.
+    pub fn set_font_fetch_timeout(mut self, timeout: Duration) -> Self {
+        self.fnt_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the _font size_ in points.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fnt_sze` - The new font size.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font size.
+    pub fn set_fnt_sze(mut self, fnt_sze: f32) -> Self {
+        self.fnt_sze = fnt_sze;
+        self
+    }
+
+    /// Sets the smallest _font size_ in points reached by [`Doc::fit_to_page`].
+    ///
+    /// ### Arguments
+    ///
+    /// * `min_fnt_sze` - The new minimum font size.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated minimum font size.
+    pub fn set_min_fnt_sze(mut self, min_fnt_sze: f32) -> Self {
+        self.min_fnt_sze = min_fnt_sze;
+        self
+    }
+
+    /// Sets the _font style_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `sty` - The new text style.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated text style.
+    pub fn set_fnt_sty(mut self, sty: Style) -> Self {
+        self.fnt_sty = sty;
+        self
+    }
+
+    /// Sets the _text color_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `clr` - The new text color.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated text color.
+    pub fn set_clr(mut self, clr: Color) -> Self {
+        self.clr = clr;
+        self
+    }
+
+    /// Sets the _text alignment_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `aln` - The new text alignment.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated text alignment.
+    pub fn set_aln(mut self, aln: Align) -> Self {
+        self.aln = aln;
+        self
+    }
+
+    /// Sets the _text direction_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `direction` - The new text direction.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated text direction.
+    pub fn set_direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the _vertical alignment_ of the content of each page.
+    ///
+    /// ### Arguments
+    ///
+    /// * `vert_aln` - The new vertical alignment.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated vertical alignment.
+    pub fn set_vert_aln(mut self, vert_aln: VertAlign) -> Self {
+        self.vert_aln = vert_aln;
+        self
+    }
+
+    /// Sets whether long words _hyphenate_ across lines.
+    ///
+    /// ### Arguments
+    ///
+    /// * `hyphenate` - `true` to break words at hyphenation points, `false` otherwise.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated hyphenation setting.
+    pub fn set_hyphenate(mut self, hyphenate: bool) -> Self {
+        self.hyphenate = hyphenate;
+        self
+    }
+
+    /// Sets the _language_ of the document text.
+    ///
+    /// ### Arguments
+    ///
+    /// * `lang` - The new language tag, such as `en-US`.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated language.
+    pub fn set_lang(mut self, lang: &str) -> Self {
+        self.lang = lang.into();
+        self
+    }
+
+    /// Sets the _hyphenation patterns_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `hyp_pats` - Whitespace separated _TeX_ style patterns, or `None` to use the built-in patterns for `lang`.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated hyphenation patterns.
+    pub fn set_hyp_pats(mut self, hyp_pats: Option<String>) -> Self {
+        self.hyp_pats = hyp_pats;
+        self
+    }
+
+    /// Sets the _line spacing_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `spc_lne` - The new line spacing.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated line spacing.
+    pub fn set_spc_lne(mut self, spc_lne: LineSpace) -> Self {
+        self.spc_lne = spc_lne;
+        self
+    }
+
+    /// Sets the _spacing after_ paragraphs in the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `spc_par_aft` - The new line spacing after paragraphs. Can be set using the `LineSpace` enum.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated spacing after paragraphs.
+    pub fn set_spc_par_aft(mut self, spc_par_aft: LineSpace) -> Self {
+        self.spc_par_aft = spc_par_aft;
+        self
+    }
+
+    /// Sets the _spacing before_ paragraphs in the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `spc_par_bfr` - The new line spacing before paragraphs. Can be set using the `LineSpace` enum.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated spacing before paragraphs.
+    pub fn set_spc_par_bfr(mut self, spc_par_bfr: LineSpace) -> Self {
+        self.spc_par_bfr = spc_par_bfr;
+        self
+    }
+
+    /// Sets the number of _columns_ dividing the margins of a page.
+    ///
+    /// Elements flow down each column before the next.
+    ///
+    /// ### Arguments
+    ///
+    /// * `columns` - The new number of columns.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated columns.
+    pub fn set_columns(mut self, columns: u8) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the _gap_ between adjacent columns.
+    ///
+    /// ### Arguments
+    ///
+    /// * `column_gap` - The new column gap.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated column gap.
+    pub fn set_column_gap(mut self, column_gap: In) -> Self {
+        self.column_gap = column_gap;
+        self
+    }
+
+    /// Sets the minimum number of _widow_ lines of a split paragraph
+    /// continuing at the top of a frame.
+    ///
+    /// ### Arguments
+    ///
+    /// * `widow_lines` - The new minimum number of lines, or `0` for no minimum.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated widow lines.
+    pub fn set_widow_lines(mut self, widow_lines: u8) -> Self {
+        self.widow_lines = widow_lines;
+        self
+    }
+
+    /// Sets the minimum number of _orphan_ lines of a split paragraph
+    /// left at the bottom of a frame.
+    ///
+    /// ### Arguments
+    ///
+    /// * `orphan_lines` - The new minimum number of lines, or `0` for no minimum.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated orphan lines.
+    pub fn set_orphan_lines(mut self, orphan_lines: u8) -> Self {
+        self.orphan_lines = orphan_lines;
+        self
+    }
+
+    /// Sets whether adjacent paragraph spacing _collapses_.
+    ///
+    /// ### Arguments
+    ///
+    /// * `collapse_spacing` - `true` to take the larger of adjacent after and before spacing, `false` to add them.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated spacing collapse setting.
+    pub fn set_collapse_spacing(mut self, collapse_spacing: bool) -> Self {
+        self.collapse_spacing = collapse_spacing;
+        self
+    }
+
+    /// Sets the _spacing model_ of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `spc_mdl` - The new spacing model.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated spacing model.
+    pub fn set_spc_mdl(mut self, spc_mdl: SpacingModel) -> Self {
+        self.spc_mdl = spc_mdl;
+        self
+    }
+
+    /// Sets whether the first line of a paragraph is _indented_.
+    ///
+    /// ### Arguments
+    ///
+    /// * `has_ind` - `true` if the first line is indented, `false` otherwise.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated indentation setting.
+    pub fn set_has_ind(mut self, has_ind: bool) -> Self {
+        self.has_ind = has_ind;
+        self
+    }
+
+    /// Sets the _background color_ filling every page.
+    ///
+    /// ### Arguments
+    ///
+    /// * `bg_clr` - The new background color, or `None` for no background.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated background color.
+    pub fn set_bg_clr(mut self, bg_clr: Option<Color>) -> Self {
+        self.bg_clr = bg_clr;
+        self
+    }
+
+    /// Sets the _watermark_ drawn beneath the body of every page.
+    ///
+    /// ### Arguments
+    ///
+    /// * `watermark` - The new watermark, or `None` for no watermark.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated watermark.
+    pub fn set_watermark(mut self, watermark: Option<Watermark>) -> Self {
+        self.watermark = watermark;
+        self
+    }
+
+    /// Sets the _header_ drawn at the top of every page.
+    ///
+    /// ### Arguments
+    ///
+    /// * `hdr` - The new header, or `None` for no header.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated header.
+    pub fn set_hdr(mut self, hdr: Option<Par>) -> Self {
+        self.hdr = hdr;
+        self
+    }
+
+    /// Sets the _footer_ drawn at the bottom of every page.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ftr` - The new footer, or `None` for no footer.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated footer.
+    pub fn set_ftr(mut self, ftr: Option<Par>) -> Self {
+        self.ftr = ftr;
+        self
+    }
+}
+
+/// Determines the style of text in a paragraph.
+///
+/// - `Normal`: The default, unstyled text.
+/// - `Italic`: Text that's styled with an italic font.
+/// - `Bold`: Text that's styled with a bold font.
+/// - `BoldItalic`: Text that's styled with both bold and italic fonts.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// The default, unstyled text.
+    #[default]
+    Normal,
+    /// Text that's styled with an italic font.
+    Italic,
+    /// Text that's styled with a bold font.
+    Bold,
+    /// Text that's styled with both bold and italic fonts.
+    BoldItalic,
+}
+
+impl Style {
+    pub fn set(self, ts: &mut TextStyle) {
+        match self {
+            Style::Normal => ts.set_font_style(FontStyle::normal()),
+            Style::Italic => ts.set_font_style(FontStyle::italic()),
+            Style::Bold => ts.set_font_style(FontStyle::bold()),
+            Style::BoldItalic => ts.set_font_style(FontStyle::bold_italic()),
+        };
+    }
+}
+
+/// An outline _stroke_ painted around text glyphs.
+///
+/// Display type is outlined, or outlined and filled when `has_fll` is set.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Stroke {
+    /// Color of the stroke.
+    pub clr: Color,
+    /// Width of the stroke in points.
+    pub wid: f32,
+    /// Indicates whether the glyphs are also _filled_ with the stroke color.
+    pub has_fll: bool,
+}
+
+impl Stroke {
+    /// Returns a new [`Stroke`].
+    pub fn new(clr: Color, wid: f32, has_fll: bool) -> Self {
+        Self { clr, wid, has_fll }
+    }
+
+    /// Returns a text paint for the stroke.
+    pub fn paint(&self) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(self.clr);
+        paint.set_stroke_width(self.wid);
+        if self.has_fll {
+            paint.set_style(PaintStyle::StrokeAndFill);
+        } else {
+            paint.set_style(PaintStyle::Stroke);
+        }
+        paint
+    }
+}
+
+/// A _border_ drawn around a paragraph and its padding.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Border {
+    /// Width of the border line.
+    pub wid: In,
+    /// Color of the border.
+    pub clr: Color,
+    /// Radius of the border's rounded corners.
+    pub rad: In,
+}
+
+impl Border {
+    /// Returns a new [`Border`].
+    pub fn new(wid: In, clr: Color, rad: In) -> Self {
+        Self { wid, clr, rad }
+    }
+
+    /// Writes the border around a rectangle in points.
+    pub fn wrt(&self, canvas: &Canvas, rect: Rect) {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(self.clr);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(self.wid.pt());
+        let rad = self.rad.pt();
+        canvas.draw_round_rect(rect, rad, rad, &paint);
+    }
+}
+
+/// Determines _horizontal_ text alignment of a paragraph.
+///
+/// - `Left`: Aligns text to the left edge of the paragraph.
+/// - `Right`: Aligns text to the right edge of the paragraph.
+/// - `Center`: Centers the text horizontally within the paragraph.
+/// - `Justify`: Stretches the text to ensure that each line has
+///   equal width. The last line is aligned to the start of the text direction.
+/// - `JustifyAll`: Stretches the text of every line, including the last,
+///   to equal width.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    /// Aligns text to the left edge of the paragraph.
+    #[default]
+    Left,
+    /// Aligns text to the right edge of the paragraph.
+    Right,
+    /// Centers the text horizontally within the paragraph.
+    Center,
+    /// Stretches the text to ensure that each line has
+    /// equal width.
+    ///
+    /// The last line is aligned to the start of the text direction.
+    Justify,
+    /// Stretches the text to ensure that each line, including
+    /// the last line, has equal width.
+    ///
+    /// The spaces of a short last line widen to span the paragraph,
+    /// while a last line without spaces is aligned as with `Justify`.
+    JustifyAll,
+}
+
+impl Align {
+    pub fn set(self, ps: &mut ParagraphStyle) {
+        match self {
+            Align::Left => ps.set_text_align(TextAlign::Left),
+            Align::Right => ps.set_text_align(TextAlign::Right),
+            Align::Center => ps.set_text_align(TextAlign::Center),
+            Align::Justify | Align::JustifyAll => ps.set_text_align(TextAlign::Justify),
+        };
+    }
+}
+
+/// Determines the _indentation_ of a paragraph's first line.
+///
+/// - `Fixed`: A fixed length.
+/// - `Em`: A multiple of the paragraph's font size, scaling with it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Indent {
+    /// A fixed length.
+    Fixed(In),
+    /// A multiple of the paragraph's font size.
+    Em(f32),
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Indent::Fixed(In::default())
+    }
+}
+
+impl Indent {
+    /// Returns the indentation in points for a font size in points.
+    pub fn pt(self, fnt_sze: f32) -> f32 {
+        match self {
+            Indent::Fixed(len) => len.pt(),
+            Indent::Em(ems) => ems * fnt_sze,
+        }
+    }
+}
+
+/// Determines _vertical_ alignment of the content of a page.
+///
+/// - `Top`: Content begins at the top margin.
+/// - `Center`: Content is centered between the margins.
+/// - `Bottom`: Content ends at the bottom margin.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VertAlign {
+    /// Content begins at the top margin.
+    #[default]
+    Top,
+    /// Content is centered between the margins.
+    Center,
+    /// Content ends at the bottom margin.
+    Bottom,
+}
+
+impl VertAlign {
+    /// Returns the fraction of a page's unused height placed above its content.
+    pub fn frc(self) -> f32 {
+        match self {
+            VertAlign::Top => 0.0,
+            VertAlign::Center => 0.5,
+            VertAlign::Bottom => 1.0,
+        }
+    }
+}
+
+/// Determines the extent of a paragraph's background _highlight_.
+///
+/// - `Lines`: Fills behind the text of each line, hugging the line widths.
+/// - `Box`: Fills the paragraph's text box across its full width.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// Fills behind the text of each line, hugging the line widths.
+    #[default]
+    Lines,
+    /// Fills the paragraph's text box across its full width.
+    Box,
+}
+
+/// Determines the _direction_ of paragraph text.
+///
+/// Runs of the opposite direction within a paragraph, such as
+/// numbers or Latin words within Arabic, are ordered bidirectionally.
+///
+/// - `Ltr`: Text reads left to right, starting at the left.
+/// - `Rtl`: Text reads right to left, starting at the right.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TextDirection {
+    /// Text reads left to right, starting at the left.
+    #[default]
+    Ltr,
+    /// Text reads right to left, starting at the right.
+    Rtl,
+}
+
+impl TextDirection {
+    pub fn set(self, ps: &mut ParagraphStyle) {
+        match self {
+            TextDirection::Ltr => ps.set_text_direction(textlayout::TextDirection::LTR),
+            TextDirection::Rtl => ps.set_text_direction(textlayout::TextDirection::RTL),
+        };
+    }
+}
+
+/// Determines the amount of space between lines of a paragraph.
+///
+/// - `Single`: Single line spacing.
+/// - `Double`: Double line spacing.
+/// - `Custom(f32)`: Custom line spacing specified by a floating-point value.
+///
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum LineSpace {
+    #[default]
+    Single,
+    Double,
+    Custom(f32),
+}
+
+impl LineSpace {
+    pub fn val(self) -> f32 {
+        match self {
+            LineSpace::Single => 1.0,
+            LineSpace::Double => 2.0,
+            LineSpace::Custom(val) => val,
+        }
+    }
+}
+
+/// Determines the pages on which content renders in duplex layouts.
+///
+/// - `Odd`: Odd-numbered (recto) pages.
+/// - `Even`: Even-numbered (verso) pages.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    /// Odd-numbered (recto) pages.
+    Odd,
+    /// Even-numbered (verso) pages.
+    Even,
+}
+
+impl Parity {
+    /// Returns `true` if a zero-based page index has the parity.
+    ///
+    /// The first page, index `0`, is page number `1`.
+    pub fn is_match(self, pag_idx: usize) -> bool {
+        match self {
+            Parity::Odd => pag_idx % 2 == 0,
+            Parity::Even => pag_idx % 2 == 1,
+        }
+    }
+}
+
+/// Determines how [`LineSpace`] values are interpreted.
+///
+/// - `Multiplier`: Line spacing multiplies the font size, and paragraph spacing multiplies the first line height.
+/// - `Points`: Line spacing is the line height in points, and paragraph spacing is a gap in points.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SpacingModel {
+    /// Spacing values are multipliers of a base height.
+    #[default]
+    Multiplier,
+    /// Spacing values are absolute lengths in points.
+    Points,
+}
+
+impl SpacingModel {
+    /// Returns the text style height multiplier for a line spacing.
+    pub fn lne_hgt(self, spc_lne: LineSpace, fnt_sze: f32) -> f32 {
+        match self {
+            SpacingModel::Multiplier => spc_lne.val(),
+            SpacingModel::Points => spc_lne.val() / fnt_sze,
+        }
+    }
+
+    /// Returns a paragraph spacing in points given the paragraph's nominal line height.
+    ///
+    /// - `Multiplier`: `lne_hgt * spc_par.val()`, so `Custom(1.0)` leaves one blank line.
+    /// - `Points`: `spc_par.val()` points.
+    ///
+    /// The spacing is a single gap between paragraphs, independent
+    /// of the number of lines in either paragraph.
+    pub fn par_spc(self, spc_par: LineSpace, lne_hgt: f32) -> f32 {
+        match self {
+            SpacingModel::Multiplier => lne_hgt * spc_par.val(),
+            SpacingModel::Points => spc_par.val(),
+        }
+    }
+}
+
+/// A _paragraph_ with formatting options.
+///
+/// Formatting options are inherited from the document.
+///
+/// Setting a paragraph option overrides a doc setting.
+///
+/// ### Fields
+///
+/// - `ind_lft`: Optional left indentation of every line. This is specified as an `In` type.
+/// - `ind_rht`: Optional right indentation of every line. This is specified as an `In` type.
+/// - `fnt`: Optional font for the paragraph. This is specified as a `FontSource` type.
+/// - `fnt_sze`: Optional size of the font in points. This is specified as a `f32`.
+/// - `fnt_sty`: Optional text _style_ of the paragraph. Possible values are defined in the `Style` enum.
+/// - `clr`: Optional text _color_ of the paragraph. This is specified as a `Color` type.
+/// - `bg_clr`: Optional background _color_ highlighting the paragraph text. This is specified as a `Color` type.
+/// - `highlight`: Optional extent of the background highlight. Possible values are defined in the `Highlight` enum.
+/// - `aln`: Optional text _alignment_ of the paragraph. Possible values are defined in the `Align` enum.
+/// - `direction`: Optional text _direction_ of the paragraph. Possible values are defined in the `TextDirection` enum.
+/// - `spc_lne`: Optional line spacing of the paragraph. Possible values are defined in the `LineSpace` enum.
+/// - `spc_bfr`: Optional _space before_ the paragraph, skipped at the top of a page. Possible values are defined in the `LineSpace` enum.
+/// - `spc_aft`: Optional _space after_ the paragraph. Possible values are defined in the `LineSpace` enum.
+/// - `has_ind`: Indicates whether the first line is _indented_. `Some(true)` if the first line is indented, `Some(false)` otherwise, or `None` if not specified.
+/// - `hang_ind`: Optional _hanging indentation_ of every line after the first when the first line is not indented. This is specified as an `In` type.
+/// - `border`: Optional _border_ drawn around the paragraph and its padding. This is specified as a `Border` type.
+/// - `padding`: Optional _padding_ between the text and its border on every side. This is specified as an `In` type.
+/// - `keep_together`: Indicates whether the paragraph is _kept together_ in one frame rather than split across frames.
+/// - `keep_with_next`: Indicates whether the paragraph is _kept with the next_ element in one frame, such as a heading with its body.
+/// - `tabs`: _Tab stops_ to which tab characters in the text advance, each with an optional leader. This is specified as a `Vec<TabStop>`.
+/// - `drop_cap`: Optional number of lines spanned by a _drop cap_ of the first character. This is specified as a `u8`.
+/// - `text_transform`: Optional case _transform_ of the rendered text. Possible values are defined in the `TextTransform` enum.
+/// - `features`: OpenType _font features_ with their values, such as `("liga", 0)`. This is specified as a `Vec<(String, u32)>`.
+/// - `visible_if`: Optional template variable key; the paragraph is dropped by `Doc::render_template` when the variable is absent or empty.
+/// - `txt`: Text _content_ of the paragraph, specified as a `String`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Par {
+    /// Indentation of the first line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ind: Option<Indent>,
+    /// Indentation length of every line from the _left_ of the text area.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ind_lft: Option<In>,
+    /// Indentation length of every line from the _right_ of the text area.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ind_rht: Option<In>,
+    /// Font for the paragraph.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fnt: Option<FontSource>,
+    /// The size of the font in points.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fnt_sze: Option<f32>,
+    /// Font _style_ of the paragraph.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fnt_sty: Option<Style>,
+    /// Text _color_ of the paragraph.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clr: Option<Color>,
+    /// Background _color_ highlighting the paragraph text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_clr: Option<Color>,
+    /// Extent of the background highlight.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<Highlight>,
+    /// Text _alignment_ of the paragraph.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aln: Option<Align>,
+    /// Text _direction_ of the paragraph.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<TextDirection>,
+    /// Line spacing of a paragraph.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spc_lne: Option<LineSpace>,
+    /// Spacing _before_ the paragraph.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spc_bfr: Option<LineSpace>,
+    /// Spacing _after_ the paragraph.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spc_aft: Option<LineSpace>,
+    /// Indicates whether the first line is _indented_.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_ind: Option<bool>,
+    /// _Hanging indentation_ length of every line after the first,
+    /// applied when the first line is not indented.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hang_ind: Option<In>,
+    /// Outline _stroke_ of the paragraph text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stroke: Option<Stroke>,
+    /// _Border_ drawn around the paragraph and its padding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<Border>,
+    /// _Padding_ between the paragraph text and its border on every side.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub padding: Option<In>,
+    /// Page _parity_ on which the paragraph renders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_parity: Option<Parity>,
+    /// Indicates whether the paragraph is _kept together_ in one frame rather than split.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_together: Option<bool>,
+    /// Indicates whether the paragraph is _kept with the next_ element in one frame.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_with_next: Option<bool>,
+    /// _Heading level_ of the paragraph, starting at `1` for a top level heading.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading_level: Option<u8>,
+    /// _Tab stops_ to which tab characters in the text advance.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tabs: Vec<TabStop>,
+    /// Number of lines spanned by a _drop cap_ of the first character.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_cap: Option<u8>,
+    /// Case _transform_ of the rendered text, leaving `txt` and `runs` unchanged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_transform: Option<TextTransform>,
+    /// OpenType _font features_ with their values, such as `("onum", 1)`
+    /// for old-style numerals. Later entries override earlier ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<(String, u32)>,
+    /// Template variable key which must have a value for the paragraph to be _visible_.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visible_if: Option<String>,
+    /// Text _content_ of the paragraph.
+    ///
+    /// Soft hyphens, [`SHY`](crate::hyp::SHY), break a word only at the end
+    /// of a line, and non-breaking spaces, [`NBSP`](crate::hyp::NBSP), never break.
+    pub txt: String,
+    /// Text _runs_ of the paragraph, used instead of `txt` when not empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runs: Vec<Run>,
+}
+
+/// A _run_ of paragraph text with an optional hyperlink.
+///
+/// Setting a run option overrides a paragraph setting.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Run {
+    /// Text _content_ of the run.
+    pub txt: String,
+    /// _URL_ the run links to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Font for the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fnt: Option<FontSource>,
+    /// The size of the font in points.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fnt_sze: Option<f32>,
+    /// Font _style_ of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fnt_sty: Option<Style>,
+    /// Text _color_ of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clr: Option<Color>,
+    /// Background _color_ highlighting the run text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_clr: Option<Color>,
+    /// Text of the _footnote_ the run marks.
+    ///
+    /// A footnote marker is drawn as a superscript.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ftn: Option<String>,
+    /// Space added to each space of the run in points,
+    /// justifying the last line of a paragraph.
+    #[serde(skip)]
+    pub(crate) wrd_spc: Option<f32>,
+}
+
+impl Run {
+    /// Returns a new [`Run`].
+    pub fn new(txt: &str, link: Option<&str>) -> Self {
+        Self {
+            txt: txt.into(),
+            link: link.map(|link| link.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the _font_ for the run.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fnt` - The new font, or `None` to use the paragraph font.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font.
+    pub fn set_fnt(mut self, fnt: Option<FontSource>) -> Self {
+        self.fnt = fnt;
+        self
+    }
+
+    /// Sets the _font size_ of the run in points.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fnt_sze` - The new font size, or `None` to use the paragraph font size.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font size.
+    pub fn set_fnt_sze(mut self, fnt_sze: Option<f32>) -> Self {
+        self.fnt_sze = fnt_sze;
+        self
+    }
+
+    /// Sets the _font style_ of the run.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fnt_sty` - The new font style, or `None` to use the paragraph font style.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font style.
+    pub fn set_fnt_sty(mut self, fnt_sty: Option<Style>) -> Self {
+        self.fnt_sty = fnt_sty;
+        self
+    }
+
+    /// Sets the text _color_ of the run.
+    ///
+    /// ### Arguments
+    ///
+    /// * `clr` - The new color, or `None` to use the paragraph color.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated color.
+    pub fn set_clr(mut self, clr: Option<Color>) -> Self {
+        self.clr = clr;
+        self
+    }
+
+    /// Sets the _background color_ highlighting the run text.
+    ///
+    /// ### Arguments
+    ///
+    /// * `bg_clr` - The new background color, or `None` to use the paragraph highlight.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated background color.
+    pub fn set_bg_clr(mut self, bg_clr: Option<Color>) -> Self {
+        self.bg_clr = bg_clr;
+        self
+    }
+
+    /// Returns a paragraph text style with the run settings applied.
+    pub fn ts(&self, par_ts: &TextStyle) -> TextStyle {
+        let mut ret = par_ts.clone();
+        if let Some(fnt) = &self.fnt {
+            ret.set_font_families(&[fnt.name()]);
+        }
+        if let Some(fnt_sze) = self.fnt_sze {
+            ret.set_font_size(fnt_sze);
+        }
+        if let Some(fnt_sty) = self.fnt_sty {
+            fnt_sty.set(&mut ret);
+        }
+        if let Some(clr) = self.clr {
+            // Recolor the paragraph's paint, keeping the width and style of a stroke.
+            let mut paint = if ret.has_foreground() {
+                ret.foreground()
+            } else {
+                Paint::default()
+            };
+            paint.set_color(clr);
+            ret.set_foreground_paint(&paint);
+        }
+        if let Some(bg_clr) = self.bg_clr {
+            let mut paint = Paint::default();
+            paint.set_color(bg_clr);
+            ret.set_background_paint(&paint);
+        }
+        if self.ftn.is_some() {
+            set_sup(&mut ret);
+        }
+        if let Some(wrd_spc) = self.wrd_spc {
+            ret.set_word_spacing(wrd_spc);
+        }
+        ret
+    }
+}
+
+/// Rasterizes a recorded page of a size as a _PNG_ image at a target _DPI_.
+fn rst_png(pic: &Picture, sze: Sze, dpi: f32) -> Result<Vec<u8>, DocError> {
+    // Create a raster surface sized to the page.
+    let wid = (*sze.width * dpi).round() as i32;
+    let hgt = (*sze.height * dpi).round() as i32;
+    let mut surface = surfaces::raster_n32_premul((wid, hgt))
+        .ok_or_else(|| DocError::from_encode("Unable to create raster surface."))?;
+
+    // Draw the page scaled from points to pixels.
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::WHITE);
+    canvas.scale((dpi / PT_PER_IN, dpi / PT_PER_IN));
+    canvas.draw_picture(pic, None, None);
+
+    // Encode the page.
+    let img = surface.image_snapshot();
+    let data = img
+        .encode(None, EncodedImageFormat::PNG, None)
+        .ok_or_else(|| DocError::from_encode("Unable to encode PNG."))?;
+
+    Ok(data.as_bytes().to_vec())
+}
+
+/// Draw a boxed background highlight of a paragraph
+/// painted at `x` and `y` points across `wid` points.
+///
+/// Highlights hugging the line widths are drawn with the text.
+fn drw_hlt(canvas: &Canvas, paragraph: &Paragraph, par: &Par, x: f32, y: f32, wid: f32) {
+    if let (Some(bg_clr), Some(Highlight::Box)) = (par.bg_clr, par.highlight) {
+        let mut paint = Paint::default();
+        paint.set_color(bg_clr);
+        canvas.draw_rect(Rect::from_xywh(x, y, wid, paragraph.height()), &paint);
+    }
+}
+
+/// Returns text with each `{{key}}` replaced by its value in `vars`.
+fn fill_tpl(txt: &str, vars: &HashMap<String, String>) -> String {
+    let mut ret = String::with_capacity(txt.len());
+    let mut rst = txt;
+    while let Some(beg) = rst.find("{{") {
+        let Some(len) = rst[beg + 2..].find("}}") else {
+            break;
+        };
+        let end = beg + 2 + len + 2;
+        ret.push_str(&rst[..beg]);
+        match vars.get(&rst[beg + 2..end - 2]) {
+            Some(val) => ret.push_str(val),
+            None => ret.push_str(&rst[beg..end]),
+        }
+        rst = &rst[end..];
+    }
+    ret.push_str(rst);
+    ret
+}
+
+/// Creates a paragraph with the given text.
+pub fn par(txt: &str) -> Par {
+    Par::default().set_txt(txt.into())
+}
+
+impl Par {
+    /// Replaces all matches of a pattern with another string.
+    pub fn replace(&mut self, from: &str, to: &str) {
+        self.txt = self.txt.replace(from, to);
+        for run in self.runs.iter_mut() {
+            run.txt = run.txt.replace(from, to);
+        }
+    }
+
+    /// Replaces each `{{key}}` with its value in one pass.
+    ///
+    /// Keys missing from `vars` are left in place.
+    pub fn render_template(&mut self, vars: &HashMap<String, String>) {
+        self.txt = fill_tpl(&self.txt, vars);
+        for run in self.runs.iter_mut() {
+            run.txt = fill_tpl(&run.txt, vars);
+        }
+    }
+
+    /// Returns a copy of the paragraph with page number tokens replaced.
+    ///
+    /// `{{page}}` is replaced with the page number
+    /// and `{{pages}}` with the number of pages.
+    pub fn with_pag_num(&self, num: PageNumber) -> Par {
+        let mut ret = self.clone();
+        ret.txt = num.fmt(&self.txt);
+        for run in ret.runs.iter_mut() {
+            run.txt = num.fmt(&run.txt);
+        }
+        ret
+    }
+
+    /// Returns a copy of the paragraph with every unset option
+    /// resolved against the document's defaults.
+    pub fn flatten(&self, doc: &Doc) -> Par {
+        Par {
+            ind: Some(self.ind.unwrap_or(doc.ind)),
+            ind_lft: self.ind_lft,
+            ind_rht: self.ind_rht,
+            fnt: Some(self.fnt.clone().unwrap_or_else(|| doc.fnt.clone())),
+            fnt_sze: Some(self.fnt_sze.unwrap_or(doc.fnt_sze)),
+            fnt_sty: Some(self.fnt_sty.unwrap_or(doc.fnt_sty)),
+            clr: Some(self.clr.unwrap_or(doc.clr)),
+            bg_clr: self.bg_clr,
+            highlight: self.highlight,
+            aln: Some(self.aln.unwrap_or(doc.aln)),
+            direction: Some(self.direction.unwrap_or(doc.direction)),
+            spc_lne: Some(self.spc_lne.unwrap_or(doc.spc_lne)),
+            spc_bfr: Some(self.spc_bfr.unwrap_or(doc.spc_par_bfr)),
+            spc_aft: Some(self.spc_aft.unwrap_or(doc.spc_par_aft)),
+            has_ind: Some(self.has_ind.unwrap_or(doc.has_ind)),
+            hang_ind: self.hang_ind,
+            runs: self.runs.clone(),
+            stroke: self.stroke,
+            border: self.border,
+            padding: self.padding,
+            page_parity: self.page_parity,
+            keep_together: self.keep_together,
+            keep_with_next: self.keep_with_next,
+            heading_level: self.heading_level,
+            tabs: self.tabs.clone(),
+            drop_cap: self.drop_cap,
+            text_transform: self.text_transform,
+            features: self.features.clone(),
+            visible_if: self.visible_if.clone(),
+            txt: self.txt.clone(),
+        }
+    }
+
+    /// Sets the _indentation_ of the first line.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ind` - The new indentation, fixed or relative to the font size.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated indentation.
+    pub fn set_ind(mut self, ind: Option<Indent>) -> Self {
+        self.ind = ind;
+        self
+    }
+
+    /// Sets the _left indentation_ length of every line.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ind_lft` - The new left indentation length.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated left indentation length.
+    pub fn set_ind_lft(mut self, ind_lft: Option<In>) -> Self {
+        self.ind_lft = ind_lft;
+        self
+    }
+
+    /// Sets the _right indentation_ length of every line.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ind_rht` - The new right indentation length.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated right indentation length.
+    pub fn set_ind_rht(mut self, ind_rht: Option<In>) -> Self {
+        self.ind_rht = ind_rht;
+        self
+    }
+
+    /// Returns the width of the paragraph's lines in points
+    /// within a text area `wid` points wide.
+    ///
+    /// Lines are inset by the indentation and the padding.
+    pub fn txt_wid(&self, wid: f32) -> f32 {
+        wid - self.ind_lft.unwrap_or_default().pt()
+            - self.ind_rht.unwrap_or_default().pt()
+            - 2.0 * self.pad()
+    }
+
+    /// Returns the padding on every side of the paragraph in points.
+    pub fn pad(&self) -> f32 {
+        self.padding.unwrap_or_default().pt()
+    }
+
+    /// Sets the _border_ drawn around the paragraph and its padding.
+    ///
+    /// ### Arguments
+    ///
+    /// * `border` - The new border, or `None` for no border.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated border.
+    pub fn set_border(mut self, border: Option<Border>) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Sets the _padding_ between the paragraph text and its border.
+    ///
+    /// ### Arguments
+    ///
+    /// * `padding` - The new padding on every side, or `None` for no padding.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated padding.
+    pub fn set_padding(mut self, padding: Option<In>) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the _font_ for the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fnt` - The new font.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font.
+    pub fn set_fnt(mut self, fnt: Option<FontSource>) -> Self {
+        self.fnt = fnt;
+        self
+    }
+
+    /// Sets the _size_ of the font in points.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fnt_sze` - The new font size.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font size.
+    pub fn set_fnt_sze(mut self, fnt_sze: Option<f32>) -> Self {
+        self.fnt_sze = fnt_sze;
+        self
+    }
+
+    /// Sets the _font style_ of the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `sty` - The new style.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated style.
+    pub fn set_fnt_sty(mut self, sty: Option<Style>) -> Self {
+        self.fnt_sty = sty;
+        self
+    }
+
+    /// Sets the _text color_ of the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `clr` - The new text color.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated text color.
+    pub fn set_clr(mut self, clr: Option<Color>) -> Self {
+        self.clr = clr;
+        self
+    }
+
+    /// Sets the _background color_ highlighting the paragraph text.
+    ///
+    /// ### Arguments
+    ///
+    /// * `bg_clr` - The new background color, or `None` for no highlight.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated background color.
+    pub fn set_bg_clr(mut self, bg_clr: Option<Color>) -> Self {
+        self.bg_clr = bg_clr;
+        self
+    }
+
+    /// Sets the extent of the background _highlight_.
+    ///
+    /// ### Arguments
+    ///
+    /// * `highlight` - The new extent, or `None` to hug the line widths.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated highlight extent.
+    pub fn set_highlight(mut self, highlight: Option<Highlight>) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Sets the _alignment_ of the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `aln` - The new alignment.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated alignment.
+    pub fn set_aln(mut self, aln: Option<Align>) -> Self {
+        self.aln = aln;
+        self
+    }
+
+    /// Sets the _text direction_ of the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `direction` - The new text direction.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated text direction.
+    pub fn set_direction(mut self, direction: Option<TextDirection>) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the _line spacing_ of the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `spc_lne` - The new line spacing.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated line spacing.
+    pub fn set_spc_lne(mut self, spc_lne: Option<LineSpace>) -> Self {
+        self.spc_lne = spc_lne;
+        self
+    }
+
+    /// Sets the _spacing before_ the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `spc_bfr` - The new spacing before the paragraph. Can be set using the `LineSpace` enum.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated spacing before the paragraph.
+    pub fn set_spc_bfr(mut self, spc_bfr: Option<LineSpace>) -> Self {
+        self.spc_bfr = spc_bfr;
+        self
+    }
+
+    /// Sets the _spacing after_ the paragraph.
+    //
+    /// ### Arguments
+    //
+    /// * `spc_aft` - The new spacing after the paragraph. Can be set using the `LineSpace` enum.
+    //
+    /// ### Returns
+    ///
+    /// Self with updated spacing after the paragraph.
+    pub fn set_spc_aft(mut self, spc_aft: Option<LineSpace>) -> Self {
+        self.spc_aft = spc_aft;
+        self
+    }
+
+    /// Sets whether the first line of the paragraph is _indented_.
+    ///
+    /// ### Arguments
+    ///
+    /// * `has_ind` - `true` if the first line is indented, `false` otherwise.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated indentation setting.
+    pub fn set_has_ind(mut self, has_ind: Option<bool>) -> Self {
+        self.has_ind = has_ind;
+        self
+    }
+
+    /// Sets the outline _stroke_ of the paragraph text.
+    ///
+    /// ### Arguments
+    ///
+    /// * `stroke` - The new stroke.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated stroke.
+    pub fn set_stroke(mut self, stroke: Option<Stroke>) -> Self {
+        self.stroke = stroke;
+        self
+    }
+
+    /// Sets the page _parity_ on which the paragraph renders.
+    ///
+    /// The paragraph is skipped where it would begin on a page of the
+    /// other parity, including where it breaks onto the following page.
+    ///
+    /// ### Arguments
+    ///
+    /// * `page_parity` - The new page parity, or `None` to render on every page.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated page parity.
+    pub fn set_page_parity(mut self, page_parity: Option<Parity>) -> Self {
+        self.page_parity = page_parity;
+        self
+    }
+
+    /// Sets whether the paragraph is _kept together_ in one frame.
+    ///
+    /// A paragraph kept together continues in the next frame
+    /// when it overflows, rather than splitting at a line boundary.
+    ///
+    /// ### Arguments
+    ///
+    /// * `keep_together` - The new keep together flag.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated keep together flag.
+    pub fn set_keep_together(mut self, keep_together: Option<bool>) -> Self {
+        self.keep_together = keep_together;
+        self
+    }
+
+    /// Sets whether the paragraph is _kept with the next_ element in one frame.
+    ///
+    /// A paragraph kept with the next element continues in the next
+    /// frame when it fits but the next element's first line does not.
+    ///
+    /// ### Arguments
+    ///
+    /// * `keep_with_next` - The new keep with next flag.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated keep with next flag.
+    pub fn set_keep_with_next(mut self, keep_with_next: Option<bool>) -> Self {
+        self.keep_with_next = keep_with_next;
+        self
+    }
+
+    /// Sets the _heading level_ of the paragraph.
+    ///
+    /// Headings are bookmarked in the outline written to the _PDF_,
+    /// shown by viewers in their bookmark panel, and returned by [`Doc::outline`].
+    ///
+    /// ### Arguments
+    ///
+    /// * `heading_level` - The new heading level, or `None` for body text.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated heading level.
+    pub fn set_heading(mut self, heading_level: Option<u8>) -> Self {
+        self.heading_level = heading_level;
+        self
+    }
+
+    /// Sets the _tab stops_ of the paragraph.
+    ///
+    /// The nth tab character in the text advances to the nth tab stop.
+    ///
+    /// ### Arguments
+    ///
+    /// * `tabs` - The new tab stops.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated tab stops.
+    pub fn set_tabs(mut self, tabs: Vec<TabStop>) -> Self {
+        self.tabs = tabs;
+        self
+    }
+
+    /// Sets the _hanging indentation_ of every line after the first.
+    ///
+    /// Applies when the first line is not indented, such as
+    /// a bibliography entry with its first line flush.
+    ///
+    /// ### Arguments
+    ///
+    /// * `hang_ind` - The new hanging indentation length, or `None` for no hanging indentation.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated hanging indentation length.
+    pub fn set_hang_ind(mut self, hang_ind: Option<In>) -> Self {
+        self.hang_ind = hang_ind;
+        self
+    }
+
+    /// Sets a _drop cap_ of the first character spanning lines.
+    ///
+    /// The first lines flow to the right of the drop cap.
+    /// Paragraphs with tab stops, or spanning no lines, have no drop cap.
+    ///
+    /// ### Arguments
+    ///
+    /// * `lines` - The number of lines spanned by the drop cap.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated drop cap.
+    pub fn set_drop_cap(mut self, lines: u8) -> Self {
+        self.drop_cap = Some(lines);
+        self
+    }
+
+    /// Sets the case _transform_ of the rendered text.
+    ///
+    /// The stored text is unchanged.
+    ///
+    /// ### Arguments
+    ///
+    /// * `text_transform` - The new case transform, or `None` for the text as stored.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated case transform.
+    pub fn set_text_transform(mut self, text_transform: Option<TextTransform>) -> Self {
+        self.text_transform = text_transform;
+        self
+    }
+
+    /// Sets the OpenType _font features_ of the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `features` - The new feature tags with their values, such as `("liga", 0)`.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font features.
+    pub fn set_features(mut self, features: Vec<(String, u32)>) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Sets an OpenType _font feature_, replacing an earlier value of the feature.
+    ///
+    /// ### Arguments
+    ///
+    /// * `tag` - The four letter feature tag, such as `onum`.
+    /// * `val` - The feature value, `0` to disable and `1` to enable.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated font feature.
+    pub fn set_feature(mut self, tag: &str, val: u32) -> Self {
+        self.features.retain(|(cur, _)| cur != tag);
+        self.features.push((tag.into(), val));
+        self
+    }
+
+    /// Sets whether standard _ligatures_, such as `fi`, are used.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ligatures` - `false` to disable the `liga` feature.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated ligatures.
+    pub fn set_ligatures(self, ligatures: bool) -> Self {
+        self.set_feature("liga", ligatures as u32)
+    }
+
+    /// Sets the template variable on which the paragraph is _visible_.
+    ///
+    /// [`Doc::render_template`] drops the paragraph when the
+    /// variable is absent or empty.
+    ///
+    /// ### Arguments
+    ///
+    /// * `key` - The variable key, or `None` to always keep the paragraph.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated visibility condition.
+    pub fn set_visible_if(mut self, key: Option<&str>) -> Self {
+        self.visible_if = key.map(|key| key.into());
+        self
+    }
+
+    /// Returns `true` if the paragraph is visible with template variables.
+    pub fn is_visible(&self, vars: &HashMap<String, String>) -> bool {
+        self.visible_if
+            .as_ref()
+            .is_none_or(|key| vars.get(key).is_some_and(|val| !val.is_empty()))
+    }
+
+    /// Returns the text content of the paragraph, joining runs when present.
+    pub fn all_txt(&self) -> String {
+        if self.runs.is_empty() {
+            return self.txt.clone();
+        }
+        self.runs.iter().map(|run| run.txt.as_str()).collect()
+    }
+
+    /// Returns the number of whitespace delimited words in the paragraph.
+    pub fn word_count(&self) -> usize {
+        self.all_txt().split_whitespace().count()
+    }
+
+    /// Returns `true` if the paragraph renders on a page index.
+    pub fn is_on_pag(&self, pag_idx: usize) -> bool {
+        self.page_parity.is_none_or(|pty| pty.is_match(pag_idx))
+    }
+
+    /// Appends a text _run_ with an optional font style.
+    ///
+    /// Text content set before the first run becomes the first run.
+    ///
+    /// ### Arguments
+    ///
+    /// * `txt` - Text of the run.
+    /// * `fnt_sty` - Font style of the run, or `None` to use the paragraph font style.
+    ///
+    /// ### Returns
+    ///
+    /// Self with the appended run.
+    pub fn add_run(mut self, txt: &str, fnt_sty: Option<Style>) -> Self {
+        self.push_run(Run::new(txt, None).set_fnt_sty(fnt_sty));
+        self
+    }
+
+    /// Appends a styled text _run_.
+    ///
+    /// Text content set before the first run becomes the first run.
+    ///
+    /// ### Arguments
+    ///
+    /// * `run` - The run with its own font, size, style, or color.
+    ///
+    /// ### Returns
+    ///
+    /// Self with the appended run.
+    pub fn add_styled_run(mut self, run: Run) -> Self {
+        self.push_run(run);
+        self
+    }
+
+    /// Appends a text _run_ linking to a _URL_.
+    ///
+    /// Text content set before the first run becomes the first run.
+    ///
+    /// ### Arguments
+    ///
+    /// * `txt` - Text of the run.
+    /// * `url` - URL the run links to.
+    ///
+    /// ### Returns
+    ///
+    /// Self with the appended run.
+    pub fn add_link(mut self, txt: &str, url: &str) -> Self {
+        self.push_run(Run::new(txt, Some(url)));
+        self
+    }
+
+    fn push_run(&mut self, run: Run) {
+        if self.runs.is_empty() && !self.txt.is_empty() {
+            self.runs.push(Run::new(&self.txt, None));
+        }
+        self.runs.push(run);
+    }
+
+    /// Returns the _UTF-16_ ranges and URLs of linked runs.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ofs` - Number of _UTF-16_ units preceding the first run.
+    pub fn lnk_rngs(&self, ofs: usize) -> Vec<(Range<usize>, &str)> {
+        let mut ret = vec![];
+        let mut pos = ofs;
+        for run in &self.runs {
+            let end = pos + run.txt.encode_utf16().count();
+            if let Some(link) = &run.link {
+                ret.push((pos..end, link.as_str()));
+            }
+            pos = end;
+        }
+        ret
+    }
+
+    /// Sets the _text content_ of the paragraph.
+    ///
+    /// ### Arguments
+    ///
+    /// * `txt` - The new text content.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated text content.
+    pub fn set_txt(mut self, txt: String) -> Self {
+        self.txt = txt;
+        self
+    }
+}
+
+pub fn create_fnt_col(
+    font: &FontSource,
+    timeout: Option<Duration>,
+    font_mgr: &FontMgr,
+) -> Result<FontCollection, DocError> {
+    create_fnt_col_as(font, &font.name(), timeout, font_mgr)
+}
+
+/// Creates a font collection registering a font under a family name.
+///
+/// A Google font fetch waits at most `timeout` for each attempt.
+pub fn create_fnt_col_as(
+    font: &FontSource,
+    name: &str,
+    timeout: Option<Duration>,
+    font_mgr: &FontMgr,
+) -> Result<FontCollection, DocError> {
+    // Get font data from network, cache, file, or memory.
+    let font_data = font.data_within(timeout)?;
+
+    // Load typeface from font data.
+    if let Some(typeface) = font_mgr.new_from_data(&font_data, None) {
+        // Create a font collection.
+        let mut tfp = TypefaceFontProvider::new();
+        tfp.register_typeface(typeface, Some(name));
+        let mut fnt_col = FontCollection::new();
+        fnt_col.set_default_font_manager(Some(tfp.into()), None);
+        return Ok(fnt_col);
+    }
+
+    Err(DocError::from(
+        format!("Unable to parse font `{}`.", font.name()).as_str(),
+    ))
+}
+
+/// Elements of a [`Doc`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum Elm {
+    /// A _paragraph_ element.
+    Par(Par),
+    /// A bulleted or numbered _list_ element.
+    List(List),
+    /// An _SVG_ graphic element.
+    Svg(Svg),
+    /// A raster _image_ element.
+    Img(Img),
+    /// A horizontal _rule_ element.
+    Rule(Rule),
+    /// A _table_ element with fixed column widths.
+    ///
+    /// Each row advances the layout by its tallest cell,
+    /// and continues on the next page when it overflows
+    /// below the repeated header rows.
+    Table {
+        /// Cell paragraphs of each row.
+        rows: Vec<Vec<Par>>,
+        /// Width of each column.
+        col_widths: Vec<In>,
+        /// Rule drawn around each cell, or `None` for no borders.
+        border: Option<Rule>,
+        /// Number of header rows repeated at the top of each following page.
+        #[serde(default)]
+        header_rows: usize,
+        /// Number format of each column's numeric cells,
+        /// or `None` to draw a column's text unchanged.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        col_fmts: Vec<Option<NumberFormat>>,
+    },
+    /// A square _QR code_ element including its quiet zone.
+    ///
+    /// Drawing a QR code requires the `qr` feature.
+    QrCode {
+        /// Text encoded by the QR code.
+        data: String,
+        /// Width and height of the QR code.
+        size: In,
+    },
+    /// A _page break_ element.
+    PagBrk,
+    /// A _column break_ element.
+    ///
+    /// Content continues in the next column or frame,
+    /// or on the next page after the last column.
+    ColBrk,
+    /// A _section break_ element.
+    ///
+    /// Following pages use the section's size and margins,
+    /// or the previous section's when `None`.
+    SecBrk {
+        /// Size of the section's pages.
+        sze: Option<Sze>,
+        /// Margins of the section's pages.
+        mrg: Option<Mrg>,
+    },
+}
+
+impl Elm {
+    /// Returns the paragraphs of the element,
+    /// including list items and table cells.
+    pub fn pars(&self) -> Vec<&Par> {
+        match self {
+            Elm::Par(par) => vec![par],
+            Elm::List(lst) => lst.itms.iter().collect(),
+            Elm::Table { rows, .. } => rows.iter().flatten().collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the mutable paragraphs of the element.
+    pub fn pars_mut(&mut self) -> Vec<&mut Par> {
+        match self {
+            Elm::Par(par) => vec![par],
+            Elm::List(lst) => lst.itms.iter_mut().collect(),
+            Elm::Table { rows, .. } => rows.iter_mut().flatten().collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// A bulleted or numbered _list_ of paragraphs.
+///
+/// Each item is laid out as a paragraph with a hanging indent
+/// and a marker drawn in the marker column. Items are not
+/// first-line indented.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct List {
+    /// Kind of marker for each item.
+    pub kind: ListKind,
+    /// Width of the marker column.
+    pub mrk_ind: In,
+    /// Paragraphs of the list.
+    pub itms: Vec<Par>,
+}
+
+/// Creates a list with the given kind and items.
+pub fn list(kind: ListKind, itms: Vec<Par>) -> List {
+    List {
+        kind,
+        mrk_ind: In(0.25),
+        itms,
+    }
+}
+
+impl List {
+    /// Sets the width of the _marker column_.
+    ///
+    /// ### Arguments
+    ///
+    /// * `mrk_ind` - The new marker column width.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated marker column width.
+    pub fn set_mrk_ind(mut self, mrk_ind: In) -> Self {
+        self.mrk_ind = mrk_ind;
+        self
+    }
+}
+
+/// Determines the marker of each list item.
+///
+/// - `Bullet(char)`: A bullet character.
+/// - `Numbered`: A number incrementing from `1` for each item.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    /// A bullet character.
+    Bullet(char),
+    /// A number incrementing from `1` for each item.
+    Numbered,
+}
+
+impl Default for ListKind {
+    fn default() -> Self {
+        ListKind::Bullet('•')
+    }
+}
+
+impl ListKind {
+    /// Returns the marker text of an item.
+    ///
+    /// ### Arguments
+    ///
+    /// * `itm_idx` - Index of the item in the list.
+    pub fn mrk(self, itm_idx: usize) -> String {
+        match self {
+            ListKind::Bullet(c) => c.to_string(),
+            ListKind::Numbered => format!("{}.", itm_idx + 1),
+        }
+    }
+}
+
+/// An _SVG_ graphic rendered as vector content.
+///
+/// The graphic is drawn within a rectangle at the left margin
+/// and advances the layout by its height.
+///
+/// A graphic with a `viewBox` and no `width` or `height` scales
+/// to fill the rectangle, while a graphic with a fixed `width`
+/// and `height` is drawn at its own size, clipped to the rectangle.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Svg {
+    /// Width of the graphic.
+    pub wid: In,
+    /// Height of the graphic.
+    pub hgt: In,
+    /// SVG _markup_ of the graphic.
+    pub txt: String,
+}
+
+/// Creates an SVG graphic with the given markup and size.
+pub fn svg(txt: &str, wid: In, hgt: In) -> Svg {
+    Svg {
+        wid,
+        hgt,
+        txt: txt.into(),
+    }
+}
+
+impl Svg {
+    /// Writes the graphic to a canvas at `x` and `y` points.
+    ///
+    /// Returns the height of the graphic in points.
+    pub fn wrt(
+        &self,
+        canvas: &Canvas,
+        x: f32,
+        y: f32,
+        font_mgr: &FontMgr,
+    ) -> Result<f32, DocError> {
+        let mut dom = svg::Dom::from_bytes(self.txt.as_bytes(), font_mgr.clone())
+            .map_err(|err| DocError::SvgParseError(StringError::new(&err.to_string())))?;
+        let (wid, hgt) = (self.wid.pt(), self.hgt.pt());
+        dom.set_container_size((wid, hgt));
+
+        canvas.save();
+        canvas.translate((x, y));
+        canvas.clip_rect(Rect::from_wh(wid, hgt), None, None);
+        dom.render(canvas);
+        canvas.restore();
+
+        Ok(hgt)
+    }
+}
+
+/// A raster _image_ decoded from _PNG_ or _JPEG_ bytes.
+///
+/// The image is drawn within a rectangle aligned within
+/// the text width and advances the layout by its height.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Img {
+    /// Width of the image.
+    pub wid: In,
+    /// Height of the image.
+    pub hgt: In,
+    /// Horizontal _alignment_ of the image within the text width.
+    ///
+    /// `Justify` aligns to the left.
+    pub aln: Align,
+    /// Encoded bytes of the image, serialized as _base64_ text.
+    #[serde(with = "b64")]
+    pub data: Vec<u8>,
+}
+
+/// Serializes bytes as _base64_ text.
+///
+/// An array of bytes is also read, as written before images were
+/// serialized as text.
+mod b64 {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Enc {
+        Txt(String),
+        Byts(Vec<u8>),
+    }
+
+    pub fn serialize<S: Serializer>(data: &[u8], ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(&STANDARD.encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deser: D) -> Result<Vec<u8>, D::Error> {
+        match Enc::deserialize(deser)? {
+            Enc::Txt(txt) => STANDARD.decode(txt).map_err(de::Error::custom),
+            Enc::Byts(byts) => Ok(byts),
+        }
+    }
+}
+
+/// Creates an image with the given encoded bytes and size.
+pub fn img(data: Vec<u8>, wid: In, hgt: In) -> Img {
+    Img {
+        wid,
+        hgt,
+        aln: Align::Left,
+        data,
+    }
+}
+
+impl Img {
+    /// Sets the horizontal _alignment_ of the image.
+    ///
+    /// ### Arguments
+    ///
+    /// * `aln` - The new alignment.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated alignment.
+    pub fn set_aln(mut self, aln: Align) -> Self {
+        self.aln = aln;
+        self
+    }
+
+    /// Returns the distance from the left of the page to the image in points.
+    ///
+    /// ### Arguments
+    ///
+    /// * `x` - Distance from the left of the page to the text area in points.
+    /// * `wid` - Width of the text area in points.
+    pub fn x(&self, x: f32, wid: f32) -> f32 {
+        match self.aln {
+            Align::Left | Align::Justify | Align::JustifyAll => x,
+            Align::Center => x + (wid - self.wid.pt()) / 2.0,
+            Align::Right => x + wid - self.wid.pt(),
+        }
+    }
+
+    /// Returns the image decoded from its encoded bytes.
+    pub fn decode(&self) -> Result<Image, DocError> {
+        Image::from_encoded(Data::new_copy(&self.data))
+            .ok_or_else(|| DocError::ImgDecodeError(StringError::new("Unable to decode image.")))
+    }
+
+    /// Writes the image to a canvas at `x` and `y` points.
+    ///
+    /// Returns the height of the image in points.
+    pub fn wrt(&self, canvas: &Canvas, x: f32, y: f32) -> Result<f32, DocError> {
+        let hgt = self.hgt.pt();
+        drw_img(
+            canvas,
+            &self.decode()?,
+            Rect::from_xywh(x, y, self.wid.pt(), hgt),
+        );
+
+        Ok(hgt)
+    }
+}
+
+/// Draws a decoded image within a rectangle.
+pub(crate) fn drw_img(canvas: &Canvas, image: &Image, rect: Rect) {
+    canvas.draw_image_rect(image, None, rect, &Paint::default());
+}
+
+/// A horizontal _rule_ dividing sections.
+///
+/// The rule is centered within the text width
+/// and has space above and below it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Rule {
+    /// Thickness of the rule.
+    pub thk: In,
+    /// Color of the rule.
+    pub clr: Color,
+    /// Fraction of the text width spanned by the rule.
+    pub wid_frc: f32,
+    /// Space above and below the rule.
+    pub spc: In,
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        rule()
+    }
+}
+
+/// Creates a thin black rule spanning the text width.
+pub fn rule() -> Rule {
+    Rule {
+        thk: In(1.0 / PT_PER_IN),
+        clr: BLACK,
+        wid_frc: 1.0,
+        spc: In(6.0 / PT_PER_IN),
+    }
+}
+
+impl Rule {
+    /// Sets the _thickness_ of the rule.
+    ///
+    /// ### Arguments
+    ///
+    /// * `thk` - The new thickness.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated thickness.
+    pub fn set_thk(mut self, thk: In) -> Self {
+        self.thk = thk;
+        self
+    }
+
+    /// Sets the _color_ of the rule.
+    ///
+    /// ### Arguments
+    ///
+    /// * `clr` - The new color.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated color.
+    pub fn set_clr(mut self, clr: Color) -> Self {
+        self.clr = clr;
+        self
+    }
+
+    /// Sets the fraction of the text width spanned by the rule.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wid_frc` - The new width fraction from `0.0` to `1.0`.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated width fraction.
+    pub fn set_wid_frc(mut self, wid_frc: f32) -> Self {
+        self.wid_frc = wid_frc;
+        self
+    }
+
+    /// Returns the height of the rule and its spacing in points.
+    pub fn hgt(&self) -> f32 {
+        (self.spc * 2.0 + self.thk).pt()
+    }
+
+    /// Writes the rule to a canvas.
+    ///
+    /// ### Arguments
+    ///
+    /// * `x` - Distance from the left of the page to the text area in points.
+    /// * `y` - Distance from the top of the page to the space above the rule in points.
+    /// * `wid` - Width of the text area in points.
+    pub fn wrt(&self, canvas: &Canvas, x: f32, y: f32, wid: f32) {
+        let rule_wid = wid * self.wid_frc.clamp(0.0, 1.0);
+        let mut paint = Paint::default();
+        paint.set_color(self.clr);
+        canvas.draw_rect(
+            Rect::from_xywh(
+                x + (wid - rule_wid) / 2.0,
+                y + self.spc.pt(),
+                rule_wid,
+                self.thk.pt(),
+            ),
+            &paint,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pag::*;
+
+    #[test]
+    fn test_new_ansi_letter() {
+        let doc = new_ansi_letter();
+        assert_eq!(doc.sze, Sze::new(In(8.5), In(11.0)));
+        assert_eq!(doc.mrg, Mrg::new(In(1.0), In(1.0), In(1.0), In(1.0)));
+        assert_eq!(doc.ind, Indent::Fixed(In(0.5)));
+    }
+
+    #[test]
+    fn test_new_legal() {
+        let doc = new_legal();
+        assert_eq!(doc.sze, Sze::new(In(8.5), In(14.0)));
+        assert_eq!(doc.mrg, MRG_IN_1);
+        assert_eq!(doc.ind, Indent::Fixed(In(0.5)));
+    }
+
+    #[test]
+    fn test_indent_pt() {
+        assert_eq!(Indent::Fixed(In(0.5)).pt(12.0), 36.0);
+        assert_eq!(Indent::Em(2.0).pt(12.0), 24.0);
+        assert_eq!(Indent::Em(2.0).pt(18.0), 36.0);
+        assert_eq!(Indent::default().pt(12.0), 0.0);
+    }
+
+    #[test]
+    fn test_new_a4() {
+        let doc = new_a4();
+        assert_eq!(doc.sze.pt().0.round(), 595.0);
+        assert_eq!(doc.sze.pt().1.round(), 842.0);
+        assert_eq!((doc.mrg.lft.0 * MM_PER_IN).round(), 25.0);
+        assert_eq!((doc.mrg.top.0 * MM_PER_IN).round(), 25.0);
+        assert_eq!(doc.spc_lne, LineSpace::Custom(1.3));
+    }
+
+    #[test]
+    fn test_new_a4_letter() {
+        let doc = new_a4_letter();
+        assert_eq!(doc.sze, A4);
+        assert_eq!(doc.mrg, MRG_MM_25);
+        assert_eq!(doc.ind, new_ansi_letter().ind);
+        assert_eq!(doc.spc_lne, new_ansi_letter().spc_lne);
+        assert_ne!(doc.spc_lne, new_a4().spc_lne);
+    }
+
+    #[test]
+    fn test_set_orientation() {
+        let doc = new_ansi_letter().set_orientation(Orientation::Landscape);
+        assert_eq!(doc.sze, Sze::new(In(11.0), In(8.5)));
+        let doc = doc.set_orientation(Orientation::Portrait);
+        assert_eq!(doc.sze, ANSI_LETTER);
+    }
+
+    #[test]
+    fn test_set_mrg_mm() {
+        let doc = new_a4().set_mrg_mm(20.0, 20.0, 25.4, 25.4);
+        assert_eq!(doc.mrg.lft, In(20.0 / MM_PER_IN));
+        assert_eq!(doc.mrg.rht, In(20.0 / MM_PER_IN));
+        assert_eq!(doc.mrg.btm, In(1.0));
+        assert_eq!(doc.mrg.top, In(1.0));
+    }
+
+    #[test]
+    fn test_clone_clear() {
+        let mut doc = new_a4()
+            .set_fnt_sze(14.0)
+            .set_fnt_sty(Style::Italic)
+            .set_aln(Align::Center)
+            .set_spc_par_aft(LineSpace::Double)
+            .set_has_ind(false);
+        doc.add_par(par("Hello"));
+        doc.add_pag_brk();
+
+        let cpy = doc.clone_clear();
+        assert!(cpy.elms.is_empty());
+        assert_eq!(cpy.sze, doc.sze);
+        assert_eq!(cpy.mrg, doc.mrg);
+        assert_eq!(cpy.ind, doc.ind);
+        assert_eq!(cpy.fnt, doc.fnt);
+        assert_eq!(cpy.fnt_sze, 14.0);
+        assert_eq!(cpy.fnt_sty, Style::Italic);
+        assert_eq!(cpy.aln, Align::Center);
+        assert_eq!(cpy.spc_lne, doc.spc_lne);
+        assert_eq!(cpy.spc_par_aft, LineSpace::Double);
+        assert!(!cpy.has_ind);
+        assert_eq!(doc.elms.len(), 2);
+    }
+
+    #[test]
+    fn test_flatten() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Hello"));
+        doc.add_pag_brk();
+        let flt = doc.flatten();
+        assert_eq!(flt.elms.len(), 2);
+
+        let Some(Elm::Par(par)) = flt.elms.first() else {
+            panic!("Expected a paragraph");
+        };
+        assert!(par.ind.is_some());
+        assert!(par.fnt.is_some());
+        assert!(par.fnt_sze.is_some());
+        assert!(par.fnt_sty.is_some());
+        assert!(par.clr.is_some());
+        assert!(par.aln.is_some());
+        assert!(par.spc_lne.is_some());
+        assert!(par.spc_bfr.is_some());
+        assert!(par.spc_aft.is_some());
+        assert!(par.has_ind.is_some());
+
+        // Changed defaults don't affect a flattened document.
+        let chg = flt.clone().set_fnt_sze(30.0).set_aln(Align::Right);
+        assert_eq!(
+            serde_json::to_string(&chg.flatten().elms).unwrap(),
+            serde_json::to_string(&flt.elms).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_spacing_model_points() {
+        let doc = new_ansi_letter()
+            .set_fnt_sze(12.0)
+            .set_spc_mdl(SpacingModel::Points)
+            .set_spc_lne(LineSpace::Custom(18.0))
+            .set_spc_par_bfr(LineSpace::Custom(6.0))
+            .set_spc_par_aft(LineSpace::Custom(9.0));
+
+        // An 18pt line height for 12pt text.
+        assert_eq!(doc.spc_mdl.lne_hgt(doc.spc_lne, doc.fnt_sze), 1.5);
+
+        // Paragraph gaps are points regardless of line height.
+        let lne_hgt = 18.0;
+        let bfr = doc.spc_mdl.par_spc(doc.spc_par_bfr, lne_hgt);
+        let aft = doc.spc_mdl.par_spc(doc.spc_par_aft, lne_hgt);
+        assert_eq!(bfr, 6.0);
+        assert_eq!(aft, 9.0);
+        assert_eq!(spc_gap(aft, bfr, doc.collapse_spacing), 15.0);
+    }
+
+    #[test]
+    fn test_spacing_model_multiplier() {
+        let mdl = SpacingModel::Multiplier;
+        assert_eq!(mdl.lne_hgt(LineSpace::Double, 12.0), 2.0);
+        assert_eq!(mdl.par_spc(LineSpace::Custom(0.5), 18.0), 9.0);
+    }
+
+    #[test]
+    fn test_page_parity() {
+        let odd = par("Chapter").set_page_parity(Some(Parity::Odd));
+        assert!(odd.is_on_pag(0));
+        assert!(!odd.is_on_pag(1));
+        assert!(odd.is_on_pag(2));
+
+        let even = par("Verso").set_page_parity(Some(Parity::Even));
+        assert!(!even.is_on_pag(0));
+        assert!(even.is_on_pag(1));
+
+        assert!(par("Any").is_on_pag(1));
+    }
+
+    #[test]
+    fn test_par_txt_wid() {
+        let wid = In(6.5).pt();
+        assert_eq!(par("Body").txt_wid(wid), wid);
+
+        let quote = par("Quote")
+            .set_ind_lft(Some(In(0.5)))
+            .set_ind_rht(Some(In(0.25)));
+        assert_eq!(quote.txt_wid(wid), In(5.75).pt());
+    }
+
+    #[test]
+    fn test_list_kind_mrk() {
+        assert_eq!(ListKind::Bullet('-').mrk(3), "-");
+        let mrks: Vec<String> = (0..3).map(|idx| ListKind::Numbered.mrk(idx)).collect();
+        assert_eq!(mrks, vec!["1.", "2.", "3."]);
+    }
+
+    #[test]
+    fn test_list_save_pdf() {
+        let mut doc = new_ansi_letter();
+        doc.add_list(list(
+            ListKind::Numbered,
+            vec![par("One"), par("Two").set_fnt_sty(Some(Style::Bold))],
+        ));
+        doc.add_list(list(ListKind::default(), vec![par("Three")]));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert_eq!(pags[0].len(), 3);
+        assert_eq!(pags[0][0].idx, 0);
+        assert_eq!(pags[0][1].idx, 0);
+        assert_eq!(pags[0][2].idx, 1);
+        assert!(pags[0][1].y > pags[0][0].y);
+        assert!(matches!(pags[0][0].cnt, LayCnt::Itm(..)));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_list");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_par_lnk_rngs() {
+        let par = par("See ")
+            .add_link("the docs", "https://docs.rs/pdf-doc")
+            .add_run(" for more.", None);
+        assert_eq!(par.runs.len(), 3);
+        assert_eq!(par.runs[0], Run::new("See ", None));
+        assert_eq!(par.lnk_rngs(0), vec![(4..12, "https://docs.rs/pdf-doc")]);
+        assert_eq!(par.lnk_rngs(1), vec![(5..13, "https://docs.rs/pdf-doc")]);
+    }
+
+    #[test]
+    fn test_link_save_pdf() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("See ").add_link("the docs", "https://docs.rs/pdf-doc"));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_link");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+
+        let pdf = std::fs::read(pth.with_extension("pdf")).expect("Failed to read pdf");
+        let url = b"https://docs.rs/pdf-doc";
+        assert!(pdf.windows(url.len()).any(|win| win == url));
+    }
+
+    #[test]
+    fn test_list_link_save_pdf() {
+        let mut doc = new_ansi_letter();
+        doc.add_list(list(
+            ListKind::default(),
+            vec![par("See ").add_link("the docs", "https://docs.rs/pdf-doc")],
+        ));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_list_link");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+
+        let pdf = std::fs::read(pth.with_extension("pdf")).expect("Failed to read pdf");
+        let url = b"https://docs.rs/pdf-doc";
+        assert!(pdf.windows(url.len()).any(|win| win == url));
+    }
+
+    #[test]
+    fn test_highlight_save_pdf() {
+        let ylw = Color::rgb(255, 240, 120);
+        let ts = Run::new("Run", None)
+            .set_bg_clr(Some(ylw))
+            .ts(&TextStyle::new());
+        assert!(ts.has_background());
+
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Highlighted lines of text.").set_bg_clr(Some(ylw)));
+        doc.add_par(
+            par("A highlighted box.")
+                .set_bg_clr(Some(ylw))
+                .set_highlight(Some(Highlight::Box)),
+        );
+        doc.add_par(par("Some ").add_styled_run(Run::new("marked", None).set_bg_clr(Some(ylw))));
+        let flt = doc.elms[1].pars()[0].flatten(&doc);
+        assert_eq!(flt.highlight, Some(Highlight::Box));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_highlight");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_border_save_pdf() {
+        let mut doc = new_ansi_letter();
+        let txt = "Note: a boxed callout with a rounded border inset by its padding.";
+        let bdr = Border::new(In(0.02), Color::rgb(200, 0, 0), In(0.1));
+        doc.add_par(par(txt));
+        doc.add_par(par(txt).set_border(Some(bdr)).set_padding(Some(In(0.25))));
+        doc.add_par(par("After"));
+
+        // The padding insets the text and adds to the paragraph height.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        let (pln, box_, aft) = (&pags[0][0], &pags[0][1], &pags[0][2]);
+        assert_eq!(box_.x, pln.x + In(0.25).pt());
+        assert_eq!(box_.wid, pln.wid - In(0.5).pt());
+        assert!(aft.y >= box_.y + box_.hgt + In(0.25).pt());
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_border");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_pdf_quality() {
+        // Draw an opaque image of noise, which compresses poorly without loss.
+        let mut surface = surfaces::raster_n32_premul((128, 128)).unwrap();
+        let mut paint = Paint::default();
+        let mut rnd: u32 = 1;
+        for y in 0..128 {
+            for x in 0..128 {
+                rnd = rnd.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                paint.set_color(skia_safe::Color::new(0xFF00_0000 | (rnd >> 8)));
+                surface
+                    .canvas()
+                    .draw_rect(Rect::from_xywh(x as f32, y as f32, 1.0, 1.0), &paint);
+            }
+        }
+        let data = surface
+            .image_snapshot()
+            .encode(None, EncodedImageFormat::PNG, None)
+            .unwrap();
+
+        let mut doc = new_ansi_letter();
+        doc.add_img(data.as_bytes().to_vec(), In(2.0), In(2.0));
+        assert_eq!(doc.pdf_meta().encoding_quality, None);
+        let hgh = doc.to_pdf_bytes().expect("Failed to write pdf");
+        let low = doc
+            .clone()
+            .set_pdf_quality(0.3)
+            .to_pdf_bytes()
+            .expect("Failed to write pdf");
+        assert!(low.len() < hgh.len());
+        let raw = doc
+            .set_compress(false)
+            .to_pdf_bytes()
+            .expect("Failed to write pdf");
+        assert!(raw.len() > hgh.len());
+    }
+
+    #[test]
+    fn test_meta_save_pdf() {
+        let mut doc = new_ansi_letter()
+            .set_title("Quantum Letters")
+            .set_author("Rana Ian")
+            .set_subject("Physics")
+            .set_keywords("qcd, qed")
+            .set_creator("pdf-doc");
+        doc.add_svg(svg(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#,
+            In(1.0),
+            In(1.0),
+        ));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_meta");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+
+        let pdf = std::fs::read(pth.with_extension("pdf")).expect("Failed to read pdf");
+        for txt in [b"Quantum Letters".as_slice(), b"Rana Ian".as_slice()] {
+            assert!(pdf.windows(txt.len()).any(|win| win == txt));
+        }
+    }
+
+    #[test]
+    fn test_reproducible_pdf() {
+        let mut doc = new_ansi_letter()
+            .set_created(Some(1_700_000_000))
+            .set_modified(Some(1_700_000_000));
+        doc.add_par(par("Pinned"));
+        let fst = doc.to_pdf_bytes().expect("Failed to write pdf");
+        std::thread::sleep(std::time::Duration::from_millis(1_100));
+        let snd = doc.to_pdf_bytes().expect("Failed to write pdf");
+        assert_eq!(fst, snd);
+        let pdr = b"pdf-doc ";
+        assert!(fst.windows(pdr.len()).any(|win| win == pdr));
+    }
+
+    #[test]
+    fn test_word_char_count() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Hello,  wide\tworld"));
+        doc.add_pag_brk();
+        doc.add_par(par("Café ").add_run("au lait", Some(Style::Italic)));
+        doc.add_rule();
+        assert_eq!(par("Hello,  wide\tworld").word_count(), 3);
+        assert_eq!(par(" ").word_count(), 0);
+        assert_eq!(doc.word_count(), 6);
+        assert_eq!(doc.char_count(), 18 + 12);
+
+        doc.add_list(list(
+            ListKind::default(),
+            vec![par("One two"), par("Three")],
+        ));
+        assert_eq!(doc.word_count(), 9);
+        assert_eq!(doc.char_count(), 18 + 12 + 7 + 5);
+    }
+
+    #[test]
+    fn test_insert_remove_par_at() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("a"));
+        doc.add_pag_brk();
+        doc.add_par(par("c"));
+
+        // Ordinals skip the page break.
+        doc.insert_par_at(1, par("b"));
+        doc.insert_par_at(9, par("d"));
+        let txts: Vec<String> = doc
+            .elms
+            .iter()
+            .filter_map(|elm| match elm {
+                Elm::Par(par) => Some(par.txt.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(txts, vec!["a", "b", "c", "d"]);
+        assert!(matches!(doc.elms[2], Elm::Par(_)));
+        assert_eq!(doc.page_break_indices(), vec![1]);
+
+        assert_eq!(doc.remove_par_at(2).map(|par| par.txt), Some("c".into()));
+        assert!(doc.remove_par_at(3).is_none());
+        assert_eq!(doc.elms.len(), 4);
+    }
+
+    #[test]
+    fn test_replace_all() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Dear NAME,"));
+        doc.add_pag_brk();
+        doc.add_par(par("Regards, ").add_run("NAME", Some(Style::Italic)));
+        doc.replace_all("NAME", "Paul");
+        assert_eq!(doc.word_count(), 4);
+        assert!(matches!(&doc.elms[0], Elm::Par(par) if par.txt == "Dear Paul,"));
+        assert!(matches!(&doc.elms[2], Elm::Par(par) if par.all_txt() == "Regards, Paul"));
+
+        let mut doc = new_ansi_letter().set_hdr(Some(par("To NAME")));
+        doc.add_list(list(ListKind::default(), vec![par("Item for NAME")]));
+        doc.replace_all("NAME", "Paul");
+        assert_eq!(doc.hdr.as_ref().unwrap().txt, "To Paul");
+        assert_eq!(doc.elms[0].pars()[0].txt, "Item for Paul");
+    }
+
+    #[test]
+    fn test_render_template() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Dear {{name}} of {{city}}, page {{page}} {{open"));
+        let vars = HashMap::from([
+            ("name".to_string(), "{{city}}".to_string()),
+            ("city".to_string(), "Bristol".to_string()),
+        ]);
+        doc.render_template(&vars);
+
+        // Values aren't expanded again and unknown keys are kept.
+        assert!(matches!(
+            &doc.elms[0],
+            Elm::Par(par) if par.txt == "Dear {{city}} of Bristol, page {{page}} {{open"
+        ));
+
+        let mut doc = new_ansi_letter().set_ftr(Some(par("{{city}} office")));
+        doc.add_list(list(ListKind::default(), vec![par("Visit {{city}}")]));
+        doc.render_template(&vars);
+        assert_eq!(doc.ftr.as_ref().unwrap().txt, "Bristol office");
+        assert_eq!(doc.elms[0].pars()[0].txt, "Visit Bristol");
+    }
+
+    #[test]
+    fn test_features() {
+        let par = par("Office")
+            .set_feature("onum", 1)
+            .set_ligatures(true)
+            .set_ligatures(false);
+        assert_eq!(
+            par.features,
+            vec![("onum".to_string(), 1), ("liga".to_string(), 0)]
+        );
+
+        let mut doc = new_ansi_letter();
+        doc.add_par(par);
+        doc.write_pdf(&mut Vec::new()).expect("Failed to write PDF");
+    }
+
+    #[test]
+    fn test_visible_if() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Dear {{name}},"));
+        doc.add_par(par("PS: {{ps}}").set_visible_if(Some("ps")));
+        doc.add_par(par("Regards"));
+        let mut ltr = doc.clone();
+        ltr.render_template(&HashMap::from([("name".to_string(), "Albert".to_string())]));
+        assert_eq!(ltr.to_text(), "Dear Albert,\n\nRegards");
+
+        let mut ltr = doc.clone();
+        ltr.render_template(&HashMap::from([("ps".to_string(), String::new())]));
+        assert_eq!(ltr.elms.len(), 2);
+        let vars = HashMap::from([("ps".to_string(), "Bring snacks.".to_string())]);
+        doc.render_template(&vars);
+        assert_eq!(doc.elms.len(), 3);
+        assert!(doc.to_text().contains("PS: Bring snacks."));
+    }
+
+    #[test]
+    fn test_fonts_subset() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("A"));
+        let one = doc.to_pdf_bytes().expect("Failed to write pdf");
+        let fnt_len = doc.fnt.data().expect("Failed to load font").len();
+        assert!(one.len() < fnt_len / 2);
+
+        // Drawing more glyphs embeds more of the font.
+        doc.add_par(par("The quick brown fox jumps over the lazy dog."));
+        let more = doc.to_pdf_bytes().expect("Failed to write pdf");
+        assert!(more.len() > one.len());
+    }
+
+    #[test]
+    fn test_append() {
+        let mut doc = new_ansi_letter();
+        let mut sec = new_a4().set_fnt_sze(20.0);
+        sec.add_par(par("Section"));
+
+        // A leading page break is not added to an empty document.
+        doc.append(sec.clone(), true);
+        assert_eq!(doc.elms.len(), 1);
+        doc.append(sec.clone(), true);
+        assert!(matches!(doc.elms[1], Elm::PagBrk));
+        doc.append(sec, false);
+        assert_eq!(doc.elms.len(), 4);
+        assert_eq!(doc.page_break_indices(), vec![1]);
+
+        // The target's settings win.
+        assert_eq!(doc.sze, new_ansi_letter().sze);
+        assert_eq!(doc.fnt_sze, new_ansi_letter().fnt_sze);
+    }
+
+    #[test]
+    fn test_split_at_breaks() {
+        let mut doc = new_a4().set_fnt_sze(14.0);
+        doc.add_pag_brk();
+        doc.add_par(par("Dear Albert,"));
+        doc.add_par(par("Regards"));
+        doc.add_pag_brk();
+        doc.add_pag_brk();
+        doc.add_par(par("Dear Richard,"));
+        let docs = doc.split_at_breaks();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].elms.len(), 2);
+        assert_eq!(docs[1].to_text(), "Dear Richard,");
+        for sec in docs.iter() {
+            assert_eq!(sec.sze, doc.sze);
+            assert_eq!(sec.fnt_sze, 14.0);
+        }
+        assert!(new_a4().split_at_breaks().is_empty());
+    }
+
+    #[test]
+    fn test_page_break_indices() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("a"));
+        doc.add_pag_brk();
+        doc.add_par(par("b"));
+        doc.add_par(par("c"));
+        doc.add_pag_brk();
+        doc.add_pag_brk();
+        assert_eq!(doc.page_break_indices(), vec![1, 4, 5]);
+
+        doc.remove_all_page_breaks();
+        assert!(doc.page_break_indices().is_empty());
+        assert_eq!(doc.elms.len(), 3);
+    }
+
+    #[test]
+    fn test_svg_save_pdf() {
+        let mut doc = new_ansi_letter();
+        doc.add_svg(svg(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50"><rect width="100" height="50" fill="blue"/></svg>"#,
+            In(1.0),
+            In(0.5),
+        ));
+        doc.add_svg(svg(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"><circle cx="5" cy="5" r="5" fill="red"/></svg>"#,
+            In(2.0),
+            In(2.0),
+        ));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_svg");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_stroke_save_pdf() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(
+            par("Heading")
+                .set_fnt_sze(Some(36.0))
+                .set_stroke(Some(Stroke::new(Color::rgb(0, 0, 255), 1.5, false))),
+        );
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_stroke");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_run_clr_stroke() {
+        let mut par_ts = TextStyle::new();
+        par_ts.set_foreground_paint(&Stroke::new(Color::rgb(0, 0, 255), 1.5, false).paint());
+        let ts = Run::new("Run", None)
+            .set_clr(Some(Color::rgb(255, 0, 0)))
+            .ts(&par_ts);
+        let paint = ts.foreground();
+        assert_eq!(paint.color(), skia_safe::Color::from(Color::rgb(255, 0, 0)));
+        assert_eq!(paint.stroke_width(), 1.5);
+        assert_eq!(paint.style(), PaintStyle::Stroke);
+    }
+
+    #[test]
+    fn test_clr_save_pdf() {
+        let mut doc = new_ansi_letter().set_clr(Color::rgb(64, 64, 64));
+        doc.add_par(par("Heading").set_clr(Some(Color::rgb(0, 0, 255))));
+        doc.add_par(par("Body"));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_clr");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_par_clr_round_trip() {
+        let org = par("Heading").set_clr(Some(Color::rgb(0, 0, 255)));
+        let json = serde_json::to_string(&org).expect("Failed to serialize");
+        assert!(json.contains(r#""clr":{"r":0,"g":0,"b":255,"a":255}"#));
+
+        let de: Par = serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(de.clr, org.clr);
+    }
+
+    #[test]
+    fn test_from_text() {
+        let txt = "First line\r\nsame paragraph.  \n\n \n\nSecond\n\x0cThird\n";
+        let doc = Doc::from_text(txt);
+        assert_eq!(doc.sze, ANSI_LETTER);
+        assert_eq!(doc.elms.len(), 4);
+        assert!(matches!(&doc.elms[0], Elm::Par(par) if par.txt == "First line\nsame paragraph."));
+        assert!(matches!(&doc.elms[1], Elm::Par(par) if par.txt == "Second"));
+        assert!(matches!(doc.elms[2], Elm::PagBrk));
+        assert!(matches!(&doc.elms[3], Elm::Par(par) if par.txt == "Third"));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_from_text");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_to_text() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Hello ").add_run("world", Some(Style::Bold)));
+        doc.add_rule();
+        doc.add_list(list(ListKind::Numbered, vec![par("One"), par("Two")]));
+        doc.add_pag_brk();
+        doc.add_par(par("Line\nbreak"));
+        assert_eq!(doc.to_text(), "Hello world\n\nOne\n\nTwo\x0cLine\nbreak");
+
+        let txt = "First\nline\n\nSecond\x0cThird";
+        assert_eq!(Doc::from_text(txt).to_text(), txt);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut org = new_a4().set_title("Report");
+        org.add_par(par("Hello"));
+        org.add_pag_brk();
+
+        let json = org.to_json().expect("Failed to serialize");
+        let de = Doc::from_json(&json).expect("Failed to deserialize");
+        assert_eq!(de.meta.title, "Report");
+        assert_eq!(de.sze, org.sze);
+        assert_eq!(de.elms.len(), 2);
+
+        assert!(matches!(
+            Doc::from_json("not json"),
+            Err(DocError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_pdf_bytes() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Hello"));
+        let pdf = doc.to_pdf_bytes().expect("Failed to write pdf");
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_write_pdf_streams() {
+        /// Counts the bytes written before the document closes.
+        struct Cnt(Vec<usize>);
+        impl Write for Cnt {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.push(buf.len());
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut doc = new_ansi_letter();
+        for idx in 0..20 {
+            doc.add_par(par(&format!("Page {}", idx)));
+            doc.add_pag_brk();
+        }
+        let mut cnt = Cnt(vec![]);
+        doc.write_pdf(&mut cnt).expect("Failed to write pdf");
+        assert!(cnt.0.len() > 20);
+        assert_eq!(
+            cnt.0.iter().sum::<usize>(),
+            doc.to_pdf_bytes().expect("Failed to write pdf").len()
+        );
+    }
+
+    #[test]
+    fn test_hdr_ftr_save_pdf() {
+        let mut doc = new_ansi_letter()
+            .set_hdr(Some(par("Header").set_aln(Some(Align::Center))))
+            .set_ftr(Some(par("Footer").set_aln(Some(Align::Right))));
+        doc.add_par(par("One"));
+        doc.add_pag_brk();
+        doc.add_par(par("Two"));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_hdr_ftr");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_pag_num_save_pdf() {
+        let ftr = par("{{page}} of {{pages}}").set_aln(Some(Align::Center));
+        let mut doc = new_ansi_letter().set_ftr(Some(ftr));
+        doc.add_par(par("One"));
+        doc.add_pag_brk();
+        doc.add_par(par("Two"));
+        doc.add_pag_brk();
+        doc.add_par(par("Three"));
+
+        // Each laid out page draws its own number in the footer.
+        let cnt = doc.page_count().expect("Failed to count pages");
+        let txts: Vec<String> = (0..cnt)
+            .map(|pag_idx| {
+                let (hdr, ftr) = doc.pag_hdr_ftr(PageNumber::new(pag_idx + 1, cnt));
+                assert!(hdr.is_none());
+                ftr.expect("Expected a footer").all_txt()
+            })
+            .collect();
+        assert_eq!(txts, vec!["1 of 3", "2 of 3", "3 of 3"]);
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_pag_num");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_render_page_png_dpi() {
+        let mut doc = new_ansi_letter();
+        doc.add_svg(svg(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#,
+            In(1.0),
+            In(1.0),
+        ));
+
+        let png = doc
+            .render_page_png_dpi(0, 150.0)
+            .expect("Failed to render png");
+
+        // Read dimensions from the PNG header.
+        let wid = u32::from_be_bytes(png[16..20].try_into().unwrap());
+        let hgt = u32::from_be_bytes(png[20..24].try_into().unwrap());
+        assert_eq!(wid, (8.5 * 150.0) as u32);
+        assert_eq!(hgt, 11 * 150);
+    }
+
+    #[test]
+    fn test_save_png() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("First"));
+        doc.add_pag_brk();
+        doc.add_par(par("Second"));
+
+        let dir = std::env::temp_dir().join("pdf_doc_test_save_png");
+        let cnt = doc.save_png(&dir, 72.0).expect("Failed to save png");
+        assert_eq!(cnt, 2);
+        assert!(dir.join("page-001.png").exists());
+        assert!(dir.join("page-002.png").exists());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_save_png_par() {
+        let mut doc = new_ansi_letter();
+        for idx in 0..5 {
+            doc.add_par(par(&format!("Page {}", idx)));
+            doc.add_pag_brk();
+        }
+
+        // Parallel pages match sequential pages.
+        let seq = std::env::temp_dir().join("pdf_doc_test_save_png_seq");
+        let par_dir = std::env::temp_dir().join("pdf_doc_test_save_png_par");
+        assert_eq!(doc.save_png(&seq, 36.0).expect("Failed to save png"), 5);
+        assert_eq!(
+            doc.save_png_par(&par_dir, 36.0)
+                .expect("Failed to save png"),
+            5
+        );
+        for idx in 1..=5 {
+            let name = format!("page-{:03}.png", idx);
+            assert_eq!(
+                fs::read(seq.join(&name)).unwrap(),
+                fs::read(par_dir.join(&name)).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_save_svg() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("First"));
+        doc.add_pag_brk();
+        doc.add_par(par("Second"));
+
+        let dir = std::env::temp_dir().join("pdf_doc_test_save_svg");
+        let cnt = doc.save_svg(&dir).expect("Failed to save svg");
+        assert_eq!(cnt, 2);
+        let txt = fs::read_to_string(dir.join("page-001.svg")).expect("Failed to read svg");
+        assert!(txt.contains("<svg"));
+        assert!(dir.join("page-002.svg").exists());
+    }
+
+    /// Returns the number of pages of PDF bytes.
+    fn pdf_pag_cnt(data: &[u8]) -> usize {
+        let tag = b"/Type /Page";
+        data.windows(tag.len() + 1)
+            .filter(|w| w.starts_with(tag) && w[tag.len()] != b's')
+            .count()
+    }
+
+    #[test]
+    fn test_wrt_pdf_btm_mrg() {
+        // A frame extending below the bottom margin precedes one at the top.
+        let mut doc = new_ansi_letter();
+        doc.add_frm(Frame::new(0, In(1.0), In(9.9), In(6.5), In(0.9)));
+        doc.add_frm(Frame::new(0, In(1.0), In(1.0), In(6.5), In(1.0)));
+        doc.add_par(par("Below the margin"));
+        doc.add_col_brk();
+        doc.add_par(par("At the top"));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert_eq!(pags.len(), 1);
+        assert!(pags[0][0].y + pags[0][0].hgt > (doc.sze.height - doc.mrg.btm).pt());
+
+        // Every element is written on the page it was laid out on.
+        let data = doc.to_pdf_bytes().expect("Failed to write PDF");
+        assert_eq!(pdf_pag_cnt(&data), doc.page_count().unwrap());
+        assert_eq!(pdf_pag_cnt(&data), 1);
+    }
+
+    #[test]
+    fn test_wrt_pag_tall_elm() {
+        let doc = new_ansi_letter();
+        let blk = svg(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#,
+            In(1.0),
+            In(12.0),
+        );
+        let lays = vec![Lay {
+            idx: 0,
+            x: doc.mrg.lft.pt(),
+            y: doc.mrg.top.pt(),
+            wid: blk.wid.pt(),
+            hgt: blk.hgt.pt(),
+            ofs: 0.0,
+            cnt: LayCnt::Svg(blk),
+        }];
+
+        // An element taller than the page is written rather than dropped.
+        let mut memory = Vec::new();
+        let pdf = pdf::new_document(&mut memory, None);
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pdf = doc
+            .wrt_pag(
+                Pag::new(0, PageNumber::new(1, 1), doc.sze, doc.mrg),
+                &lays,
+                pdf,
+                &mut fnts,
+                &font_mgr,
+            )
+            .expect("Failed to write page");
+        pdf.close();
+        assert_eq!(pdf_pag_cnt(&memory), 1);
+    }
+
+    /// Returns a PNG of a solid square.
+    fn png(sze: i32) -> Vec<u8> {
+        let mut surface = surfaces::raster_n32_premul((sze, sze)).unwrap();
+        surface.canvas().clear(skia_safe::Color::BLUE);
+        let img = surface.image_snapshot();
+        let data = img.encode(None, EncodedImageFormat::PNG, None).unwrap();
+        data.as_bytes().to_vec()
+    }
+
+    #[test]
+    fn test_rule_save_pdf() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Above"));
+        doc.add_rule();
+        doc.elms
+            .push(Elm::Rule(rule().set_thk(In(0.05)).set_wid_frc(0.5)));
+        doc.add_par(par("Below"));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert_eq!(pags[0].len(), 4);
+        assert_eq!(pags[0][1].hgt, rule().hgt());
+        assert_eq!(pags[0][2].wid, In(6.5).pt());
+        assert!(pags[0][3].y >= pags[0][2].y + pags[0][2].hgt);
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_rule");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_pages() {
+        let mut doc = new_ansi_letter();
+        doc.add_pag_brk();
+        doc.add_par(par("One"));
+        doc.add_rule();
+        doc.add_pag_brk();
+        doc.add_pag_brk();
+        doc.add_par(par("Two"));
+        let pags: Vec<Page> = doc.pages().collect();
+        assert_eq!(pags.len(), 2);
+        assert_eq!((pags[0].idx, pags[0].elms.len()), (1, 2));
+        assert_eq!((pags[1].idx, pags[1].elms.len()), (5, 1));
+        let idxs: Vec<usize> = pags[1].iter().map(|(idx, _)| idx).collect();
+        assert_eq!(idxs, vec![5]);
+
+        let seg = doc.seg_pags();
+        assert_eq!(seg.len(), 2);
+        assert_eq!(seg[0].len(), 1);
+        assert_eq!(seg[1][0].txt, "Two");
+    }
+
+    #[test]
+    fn test_sec_brk_save_pdf() {
+        let mut doc = new_ansi_letter();
+        let mrg = Mrg::new(In(0.5), In(0.5), In(0.5), In(0.5));
+        doc.add_sec_brk(None, Some(mrg));
+        doc.add_par(par("Cover"));
+        doc.add_sec_brk(Some(ANSI_LETTER.landscape()), None);
+        doc.add_par(par("Wide"));
+        doc.add_sec_brk(Some(ANSI_LETTER), Some(MRG_IN_1));
+        doc.add_par(par("Body"));
+        assert_eq!(doc.seg_pags().len(), 3);
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let (pags, geos) = doc
+            .lay_secs(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert_eq!(pags.len(), 3);
+
+        // A section break on an empty page applies to that page.
+        assert_eq!(geos[0], (ANSI_LETTER, mrg));
+        assert_eq!(pags[0][0].x, In(0.5).pt());
+        assert_eq!(pags[0][0].y, In(0.5).pt());
+
+        // Following sections keep settings they don't override.
+        assert_eq!(geos[1], (ANSI_LETTER.landscape(), mrg));
+        assert_eq!(pags[1][0].wid, In(10.0).pt());
+        assert_eq!(geos[2], (ANSI_LETTER, MRG_IN_1));
+        assert_eq!(pags[2][0].wid, In(6.5).pt());
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_sec_brk");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_img_save_pdf() {
+        let mut doc = new_ansi_letter();
+        doc.add_img(png(16), In(1.0), In(1.0));
+        doc.elms.push(Elm::Img(
+            img(png(16), In(2.0), In(1.0)).set_aln(Align::Center),
+        ));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert_eq!(pags[0][0].x, doc.mrg.lft.pt());
+        assert_eq!(pags[0][0].y, doc.mrg.top.pt());
+        assert_eq!(pags[0][1].x, In(3.25).pt());
+        assert_eq!(pags[0][1].y, In(2.0).pt());
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_img");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_img_json_base64() {
+        let data = png(4);
+        let img = img(data.clone(), In(1.0), In(1.0));
+        let json = serde_json::to_value(&img).expect("Failed to serialize");
+        assert!(json["data"].is_string());
+        let ret: Img = serde_json::from_value(json).expect("Failed to deserialize");
+        assert_eq!(ret.data, data);
+
+        // Bytes written as an array are still read.
+        let json = serde_json::json!({ "wid": 1.0, "hgt": 1.0, "aln": "Left", "data": [1, 2, 3] });
+        let ret: Img = serde_json::from_value(json).expect("Failed to deserialize");
+        assert_eq!(ret.data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_img_decode_error() {
+        let mut doc = new_ansi_letter();
+        doc.add_img(vec![0, 1, 2, 3], In(1.0), In(1.0));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_img_error");
+        match doc.save_pdf(&pth) {
+            Err(DocError::RenderError { source, .. }) => {
+                assert!(matches!(*source, DocError::ImgDecodeError(_)));
+            }
+            ret => panic!("Expected a render error, got {:?}", ret),
+        }
+    }
+
+    #[test]
+    fn test_render_error_names_element() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Found"));
+        doc.add_par(par("Missing").set_fnt(Some(FontSource::File("missing.ttf".into()))));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_render_error");
+        match doc.save_pdf(&pth) {
+            Err(DocError::RenderError {
+                page,
+                element,
+                source,
+            }) => {
+                assert_eq!(page, 0);
+                assert_eq!(element, 1);
+                assert!(matches!(*source, DocError::FileError(_)));
+            }
+            ret => panic!("Expected a render error, got {:?}", ret),
+        }
+    }
+
+    #[test]
+    fn test_copy_pars_flattened() {
+        let mut src = new_ansi_letter()
+            .set_fnt_sze(14.0)
+            .set_aln(Align::Center)
+            .set_has_ind(false);
+        src.add_par(par("Hello").set_fnt_sty(Some(Style::Bold)));
+
+        let mut dst = new_ansi_letter()
+            .set_fnt_sze(10.0)
+            .set_ind(Indent::Fixed(In(0.25)));
+        dst.copy_pars_flattened(src.clone());
+
+        let Some(Elm::Par(cpy)) = dst.elms.first() else {
+            panic!("Expected a paragraph");
+        };
+        assert_eq!(cpy.ind, Some(src.ind));
+        assert_eq!(cpy.fnt, Some(src.fnt.clone()));
+        assert_eq!(cpy.fnt_sze, Some(14.0));
+        assert_eq!(cpy.fnt_sty, Some(Style::Bold));
+        assert_eq!(cpy.clr, Some(BLACK));
+        assert_eq!(cpy.aln, Some(Align::Center));
+        assert_eq!(cpy.spc_lne, Some(src.spc_lne));
+        assert_eq!(cpy.spc_bfr, Some(src.spc_par_bfr));
+        assert_eq!(cpy.spc_aft, Some(src.spc_par_aft));
+        assert_eq!(cpy.has_ind, Some(false));
+        assert_eq!(cpy.txt, "Hello");
+    }
 }
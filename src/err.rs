@@ -3,24 +3,100 @@ use serde_json;
 use std::fmt::Display;
 use std::fmt::{self, Formatter};
 use std::io;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum DocError {
     SerializationError(serde_json::Error),
+    #[cfg(feature = "yaml")]
+    YamlError(serde_yaml::Error),
+    #[cfg(feature = "bin")]
+    BinError(StringError),
     FileError(io::Error),
     FontLoadError(google_fonts::FontError),
+    FontTimeoutError {
+        font: String,
+        timeout: Duration,
+    },
     FontParseError(StringError),
+    SvgParseError(StringError),
+    ImgDecodeError(StringError),
+    #[cfg(feature = "net")]
+    NetworkError(reqwest::Error),
+    PageIndexError(usize),
+    ElementIndexError(usize),
+    EncodeError(StringError),
+    InvalidLayout(StringError),
+    RenderError {
+        page: usize,
+        element: usize,
+        source: Box<DocError>,
+    },
 }
 
-impl std::error::Error for DocError {}
+impl DocError {
+    /// Wraps an error with the page and element index where it occurred.
+    pub fn render(page: usize, element: usize, err: DocError) -> DocError {
+        DocError::RenderError {
+            page,
+            element,
+            source: Box::new(err),
+        }
+    }
+
+    /// Returns an encode error with a message.
+    pub fn from_encode(msg: &str) -> DocError {
+        DocError::EncodeError(StringError::new(msg))
+    }
+
+    /// Returns an invalid layout error with a message.
+    pub fn from_layout(msg: &str) -> DocError {
+        DocError::InvalidLayout(StringError::new(msg))
+    }
+}
+
+impl std::error::Error for DocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DocError::RenderError { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl Display for DocError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             DocError::SerializationError(err) => write!(f, "Serialization error: {}", err),
+            #[cfg(feature = "yaml")]
+            DocError::YamlError(err) => write!(f, "YAML error: {}", err),
+            #[cfg(feature = "bin")]
+            DocError::BinError(err) => write!(f, "Binary error: {}", err),
             DocError::FileError(err) => write!(f, "File error: {}", err),
             DocError::FontLoadError(err) => write!(f, "Font load error: {}", err),
+            DocError::FontTimeoutError { font, timeout } => write!(
+                f,
+                "Font timeout error: `{}` not fetched within {:?}",
+                font, timeout
+            ),
             DocError::FontParseError(err) => write!(f, "Font parse error: {}", err),
+            DocError::SvgParseError(err) => write!(f, "SVG parse error: {}", err),
+            DocError::ImgDecodeError(err) => write!(f, "Image decode error: {}", err),
+            #[cfg(feature = "net")]
+            DocError::NetworkError(err) => write!(f, "Network error: {}", err),
+            DocError::PageIndexError(idx) => write!(f, "Page index out of range: {}", idx),
+            DocError::ElementIndexError(idx) => write!(f, "Element not laid out: {}", idx),
+            DocError::EncodeError(err) => write!(f, "Encode error: {}", err),
+            DocError::InvalidLayout(err) => write!(f, "Invalid layout: {}", err),
+            DocError::RenderError {
+                page,
+                element,
+                source,
+            } => write!(
+                f,
+                "Render error on page {}, element {}: {}",
+                page, element, source
+            ),
         }
     }
 }
@@ -31,6 +107,27 @@ impl From<serde_json::Error> for DocError {
     }
 }
 
+#[cfg(feature = "yaml")]
+impl From<serde_yaml::Error> for DocError {
+    fn from(err: serde_yaml::Error) -> DocError {
+        DocError::YamlError(err)
+    }
+}
+
+#[cfg(feature = "bin")]
+impl From<rmp_serde::encode::Error> for DocError {
+    fn from(err: rmp_serde::encode::Error) -> DocError {
+        DocError::BinError(StringError::new(&err.to_string()))
+    }
+}
+
+#[cfg(feature = "bin")]
+impl From<rmp_serde::decode::Error> for DocError {
+    fn from(err: rmp_serde::decode::Error) -> DocError {
+        DocError::BinError(StringError::new(&err.to_string()))
+    }
+}
+
 impl From<io::Error> for DocError {
     fn from(err: io::Error) -> DocError {
         DocError::FileError(err)
@@ -43,6 +140,13 @@ impl From<google_fonts::FontError> for DocError {
     }
 }
 
+#[cfg(feature = "net")]
+impl From<reqwest::Error> for DocError {
+    fn from(err: reqwest::Error) -> DocError {
+        DocError::NetworkError(err)
+    }
+}
+
 impl From<&str> for DocError {
     fn from(msg: &str) -> DocError {
         DocError::FontParseError(StringError::new(msg))
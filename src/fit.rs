@@ -0,0 +1,102 @@
+use crate::doc::*;
+use crate::err::*;
+use crate::unit::*;
+
+/// Default smallest font size in points reached by [`Doc::fit_to_page`].
+pub const MIN_FNT_SZE: f32 = 6.0;
+
+/// Factor by which each step of [`Doc::fit_to_page`] scales font sizes.
+const FIT_STP: f32 = 0.95;
+
+impl Doc {
+    /// Reduces font sizes until `elms` fit on one page, returning the scale factor.
+    ///
+    /// Every font size is scaled by the same factor, in steps, until
+    /// [`Doc::measure_height`] fits between the margins, header, and footer.
+    /// Spacing in points scales with the font; spacing multipliers scale
+    /// with it already. A document which fits is unchanged, with a factor of `1.0`.
+    ///
+    /// Errors with [`DocError::InvalidLayout`] if the content overflows
+    /// with the document font size at `min_fnt_sze`, leaving `self` unchanged.
+    pub fn fit_to_page(&mut self) -> Result<f32, DocError> {
+        let min_scl = (self.min_fnt_sze / self.fnt_sze).min(1.0);
+        let mut scl: f32 = 1.0;
+        loop {
+            let mut doc = self.clone();
+            doc.scl_fnts(scl);
+            if doc.measure_height()? <= doc.txt_hgt()? {
+                *self = doc;
+                return Ok(scl);
+            }
+            if scl <= min_scl {
+                return Err(DocError::from_layout(
+                    "content overflows one page at the minimum font size",
+                ));
+            }
+            scl = (scl * FIT_STP).max(min_scl);
+        }
+    }
+
+    /// Returns the height of the page body in points, between
+    /// the margins, the header, and the footer.
+    fn txt_hgt(&self) -> Result<f32, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+        let (hdr_hgt, ftr_hgt) = doc.hdr_ftr_hgt(&mut cache.fnts, &cache.font_mgr)?;
+        Ok((self.sze.height - self.mrg.height()).pt() - hdr_hgt - ftr_hgt)
+    }
+
+    /// Scales the font sizes of the document, its paragraphs and their runs.
+    fn scl_fnts(&mut self, scl: f32) {
+        let scl_spc = |spc: LineSpace| LineSpace::Custom(spc.val() * scl);
+        self.fnt_sze *= scl;
+        if self.spc_mdl == SpacingModel::Points {
+            self.spc_lne = scl_spc(self.spc_lne);
+            self.spc_par_bfr = scl_spc(self.spc_par_bfr);
+            self.spc_par_aft = scl_spc(self.spc_par_aft);
+        }
+        let spc_mdl = self.spc_mdl;
+        for par in self.elms.iter_mut().flat_map(Elm::pars_mut) {
+            par.fnt_sze = par.fnt_sze.map(|fnt_sze| fnt_sze * scl);
+            for run in par.runs.iter_mut() {
+                run.fnt_sze = run.fnt_sze.map(|fnt_sze| fnt_sze * scl);
+            }
+            if spc_mdl == SpacingModel::Points {
+                par.spc_lne = par.spc_lne.map(scl_spc);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_to_page() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Short."));
+        assert_eq!(doc.fit_to_page().expect("Failed to fit"), 1.0);
+        assert_eq!(doc.fnt_sze, 12.0);
+
+        let mut doc = new_ansi_letter();
+        for _ in 0..30 {
+            doc.add_par(par("A paragraph repeated until the page overflows."));
+        }
+        assert!(doc.page_count().expect("Failed to count pages") > 1);
+        let scl = doc.fit_to_page().expect("Failed to fit");
+        assert!(scl < 1.0);
+        assert_eq!(doc.fnt_sze, 12.0 * scl);
+        assert!(doc.fnt_sze >= doc.min_fnt_sze);
+        assert_eq!(doc.page_count().expect("Failed to count pages"), 1);
+    }
+
+    #[test]
+    fn test_fit_to_page_min() {
+        let mut doc = new_ansi_letter().set_min_fnt_sze(11.0);
+        for _ in 0..100 {
+            doc.add_par(par("A paragraph repeated until the page overflows."));
+        }
+        assert!(doc.fit_to_page().is_err());
+        assert_eq!(doc.fnt_sze, 12.0);
+    }
+}
@@ -0,0 +1,198 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::doc::*;
+use crate::meta::*;
+
+/// Key of the field filled with the date of rendering.
+pub const DTE_KEY: &str = "date";
+
+/// Key of the field filled with the time of rendering.
+pub const TME_KEY: &str = "time";
+
+/// Default format of the `{{date}}` field.
+pub const DTE_FMT: &str = "%Y-%m-%d";
+
+/// Format of the `{{time}}` field.
+pub const TME_FMT: &str = "%H:%M";
+
+/// Names of the months for the `%B` and `%b` specifiers.
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+impl Doc {
+    /// Sets the value of a custom `{{key}}` _field_ filled in when rendering.
+    ///
+    /// A custom field overrides the built-in `{{date}}` and `{{time}}` fields.
+    ///
+    /// ### Arguments
+    ///
+    /// * `key` - The key of the field, without braces.
+    /// * `val` - The value of the field.
+    ///
+    /// ### Returns
+    ///
+    /// Self with the updated field.
+    pub fn set_field(mut self, key: &str, val: &str) -> Self {
+        self.fields.insert(key.into(), val.into());
+        self
+    }
+
+    /// Sets the _format_ of the `{{date}}` field.
+    ///
+    /// ### Arguments
+    ///
+    /// * `date_fmt` - The new format. See [`fmt_dte`] for the supported specifiers.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated date format.
+    pub fn set_date_fmt(mut self, date_fmt: &str) -> Self {
+        self.date_fmt = date_fmt.into();
+        self
+    }
+
+    /// Returns the values of every field filled in when rendering,
+    /// with the custom fields and the built-in `{{date}}` and `{{time}}`.
+    ///
+    /// The date and time are the _UTC_ creation time of the document,
+    /// or the current time when it is unset.
+    pub fn fld_vals(&self) -> HashMap<String, String> {
+        let secs = self.meta.created.unwrap_or_else(now_secs);
+        let mut ret = HashMap::from([
+            (DTE_KEY.to_string(), fmt_dte(secs, &self.date_fmt)),
+            (TME_KEY.to_string(), fmt_dte(secs, TME_FMT)),
+        ]);
+        ret.extend(self.fields.clone());
+        ret
+    }
+
+    /// Returns the document with its fields filled in
+    /// in every paragraph, header, and footer.
+    ///
+    /// Page number tokens and keys without a value are left in place.
+    /// A document without fields is borrowed rather than copied.
+    pub fn fill_fields(&self) -> Cow<Doc> {
+        let has_fld =
+            |par: &Par| par.txt.contains("{{") || par.runs.iter().any(|run| run.txt.contains("{{"));
+        let pars = self
+            .hdr
+            .iter()
+            .chain(self.ftr.iter())
+            .chain(self.elms.iter().flat_map(Elm::pars));
+        if !pars.any(has_fld) {
+            return Cow::Borrowed(self);
+        }
+
+        let vals = self.fld_vals();
+        let mut ret = self.clone();
+        let pars = ret
+            .hdr
+            .iter_mut()
+            .chain(ret.ftr.iter_mut())
+            .chain(ret.elms.iter_mut().flat_map(Elm::pars_mut));
+        for par in pars {
+            par.render_template(&vals);
+        }
+        Cow::Owned(ret)
+    }
+}
+
+/// Returns the _UTC_ date and time of seconds since the _Unix_ epoch
+/// formatted with `strftime`-like specifiers.
+///
+/// Supported specifiers are `%Y` year, `%m` month, `%d` day, `%e` day
+/// without padding, `%B` month name, `%b` abbreviated month name,
+/// `%H` hour, `%M` minute, `%S` second, and `%%` a percent sign.
+/// Other characters are copied unchanged.
+pub fn fmt_dte(secs: u64, fmt: &str) -> String {
+    let dte = pdf_dte(secs);
+    let month = MONTHS[(dte.month as usize).clamp(1, 12) - 1];
+    let mut ret = String::with_capacity(fmt.len());
+    let mut chrs = fmt.chars();
+    while let Some(c) = chrs.next() {
+        if c != '%' {
+            ret.push(c);
+            continue;
+        }
+        match chrs.next() {
+            Some('Y') => ret.push_str(&dte.year.to_string()),
+            Some('m') => ret.push_str(&format!("{:02}", dte.month)),
+            Some('d') => ret.push_str(&format!("{:02}", dte.day)),
+            Some('e') => ret.push_str(&dte.day.to_string()),
+            Some('B') => ret.push_str(month),
+            Some('b') => ret.push_str(&month[..3]),
+            Some('H') => ret.push_str(&format!("{:02}", dte.hour)),
+            Some('M') => ret.push_str(&format!("{:02}", dte.minute)),
+            Some('S') => ret.push_str(&format!("{:02}", dte.second)),
+            Some('%') => ret.push('%'),
+            Some(c) => {
+                ret.push('%');
+                ret.push(c);
+            }
+            None => ret.push('%'),
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt_dte() {
+        // 2023-11-14 22:13:20 UTC
+        let secs = 1_700_000_000;
+        assert_eq!(fmt_dte(secs, DTE_FMT), "2023-11-14");
+        assert_eq!(fmt_dte(secs, TME_FMT), "22:13");
+        assert_eq!(
+            fmt_dte(secs, "%e %B %Y, %b %S%%"),
+            "14 November 2023, Nov 20%"
+        );
+        assert_eq!(fmt_dte(secs, "%q %"), "%q %");
+    }
+
+    #[test]
+    fn test_fill_fields() {
+        let mut doc = new_ansi_letter()
+            .set_created(Some(1_700_000_000))
+            .set_date_fmt("%B %e, %Y")
+            .set_field("name", "Albert")
+            .set_ftr(Some(par("{{page}} of {{pages}}, {{time}}")));
+        doc.add_par(par("Dear {{name}}, on {{date}} {{open}}"));
+        doc.add_par(par("Plain"));
+
+        let fld = doc.fill_fields();
+        assert!(matches!(fld, Cow::Owned(_)));
+        assert!(matches!(
+            &fld.elms[0],
+            Elm::Par(par) if par.txt == "Dear Albert, on November 14, 2023 {{open}}"
+        ));
+        assert_eq!(
+            fld.ftr.as_ref().unwrap().txt,
+            "{{page}} of {{pages}}, 22:13"
+        );
+
+        // The stored text is unchanged.
+        assert!(matches!(&doc.elms[0], Elm::Par(par) if par.txt.starts_with("Dear {{name}}")));
+        let pth = std::env::temp_dir().join("pdf_doc_test_fill_fields");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+
+        let mut pln = new_ansi_letter();
+        pln.add_par(par("Plain"));
+        assert!(matches!(pln.fill_fields(), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::doc::*;
+
+/// A _match_ of searched text within a paragraph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// Ordinal of the paragraph, counting only paragraphs in `elms`.
+    pub par_idx: usize,
+    /// Byte offset of the match within the paragraph text.
+    pub ofs: usize,
+}
+
+impl Doc {
+    /// Returns the location of every occurrence of text in all paragraphs.
+    ///
+    /// Occurrences within a paragraph do not overlap.
+    ///
+    /// ### Arguments
+    ///
+    /// * `needle` - The text to find.
+    pub fn find(&self, needle: &str) -> Vec<Match> {
+        if needle.is_empty() {
+            return vec![];
+        }
+        self.fnd_by(|txt| txt.match_indices(needle).map(|(ofs, _)| ofs).collect())
+    }
+
+    /// Returns the location of every occurrence of text
+    /// in all paragraphs, ignoring case.
+    ///
+    /// Offsets refer to the paragraph text, not its lowercase form.
+    ///
+    /// ### Arguments
+    ///
+    /// * `needle` - The text to find.
+    pub fn find_ignore_case(&self, needle: &str) -> Vec<Match> {
+        let ndl: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+        if ndl.is_empty() {
+            return vec![];
+        }
+        self.fnd_by(|txt| {
+            let mut ret = vec![];
+            let mut nxt = 0;
+            for (ofs, _) in txt.char_indices() {
+                if ofs < nxt {
+                    continue;
+                }
+                if let Some(len) = ci_pfx(&txt[ofs..], &ndl) {
+                    ret.push(ofs);
+                    nxt = ofs + len;
+                }
+            }
+            ret
+        })
+    }
+
+    /// Returns the matches of each paragraph found by a function
+    /// returning byte offsets within the paragraph text.
+    fn fnd_by(&self, fnd: impl Fn(&str) -> Vec<usize>) -> Vec<Match> {
+        self.elms
+            .iter()
+            .filter_map(|elm| match elm {
+                Elm::Par(par) => Some(par),
+                _ => None,
+            })
+            .enumerate()
+            .flat_map(|(par_idx, par)| {
+                fnd(&par.all_txt())
+                    .into_iter()
+                    .map(move |ofs| Match { par_idx, ofs })
+            })
+            .collect()
+    }
+}
+
+/// Returns the length in bytes of the start of text
+/// matching lowercase characters, ignoring case.
+fn ci_pfx(txt: &str, ndl: &[char]) -> Option<usize> {
+    let mut pos = 0;
+    for (idx, c) in txt.char_indices() {
+        if pos == ndl.len() {
+            return Some(idx);
+        }
+        for lwr in c.to_lowercase() {
+            if ndl.get(pos) != Some(&lwr) {
+                return None;
+            }
+            pos += 1;
+        }
+    }
+    (pos == ndl.len()).then_some(txt.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("The cat sat on the mat."));
+        doc.add_pag_brk();
+        doc.add_par(par("The").add_run(" end", Some(Style::Bold)));
+        assert_eq!(
+            doc.find("the"),
+            vec![Match {
+                par_idx: 0,
+                ofs: 15
+            }]
+        );
+        assert_eq!(doc.find("he end")[0].par_idx, 1);
+        assert_eq!(doc.find("aaa").len(), 0);
+        assert!(doc.find("").is_empty());
+
+        let ofss: Vec<(usize, usize)> = doc
+            .find_ignore_case("THE")
+            .iter()
+            .map(|mtc| (mtc.par_idx, mtc.ofs))
+            .collect();
+        assert_eq!(ofss, vec![(0, 0), (0, 15), (1, 0)]);
+    }
+
+    #[test]
+    fn test_ci_pfx() {
+        let ndl: Vec<char> = "éa".chars().collect();
+        assert_eq!(ci_pfx("ÉAt", &ndl), Some(3));
+        assert_eq!(ci_pfx("ÉA", &ndl), Some(3));
+        assert_eq!(ci_pfx("Eat", &ndl), None);
+        assert_eq!(ci_pfx("É", &ndl), None);
+    }
+}
@@ -0,0 +1,390 @@
+use crate::dgn::*;
+use crate::doc::*;
+use crate::err::*;
+use google_fonts::{Font, FontError};
+use serde::{Deserialize, Serialize};
+use skia_safe::{textlayout::FontCollection, FontMgr};
+use std::collections::hash_map::{DefaultHasher, Entry::Vacant};
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Number of attempts to fetch a Google font before failing.
+const FETCH_TRIES: u32 = 3;
+
+/// Wait before retrying a failed fetch, doubling with each retry.
+const FETCH_BACKOFF: Duration = Duration::from_millis(500);
+
+/// A _source_ from which a font typeface is loaded.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FontSource {
+    /// A Google font fetched from the network or a local cache.
+    Google(Font),
+    /// A font file on the local file system.
+    File(PathBuf),
+    /// Font file data held in memory.
+    Bytes(Vec<u8>),
+}
+
+impl Default for FontSource {
+    fn default() -> Self {
+        FontSource::Google(Font::DomineVariable)
+    }
+}
+
+impl From<Font> for FontSource {
+    fn from(fnt: Font) -> Self {
+        FontSource::Google(fnt)
+    }
+}
+
+impl FontSource {
+    /// Returns the family name with which the typeface is registered.
+    ///
+    /// Names of file and in-memory fonts end with a hash of the path
+    /// or data, so distinct sources never share a family.
+    pub fn name(&self) -> String {
+        match self {
+            FontSource::Google(fnt) => fnt.to_string(),
+            FontSource::File(pth) => {
+                let stm = pth
+                    .file_stem()
+                    .map(|stm| stm.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "File".into());
+                format!("{}-{:016x}", stm, self.hsh())
+            }
+            FontSource::Bytes(_) => format!("Bytes-{:016x}", self.hsh()),
+        }
+    }
+
+    /// Returns a hash of the font source.
+    fn hsh(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the font file data.
+    pub fn data(&self) -> Result<Vec<u8>, DocError> {
+        self.data_within(None)
+    }
+
+    /// Returns the font file data, waiting at most `timeout`
+    /// for each attempt to fetch a Google font.
+    ///
+    /// A fetch failing over the network or timing out is retried
+    /// with backoff. See [`fetch`] for how a timed out fetch continues.
+    pub fn data_within(&self, timeout: Option<Duration>) -> Result<Vec<u8>, DocError> {
+        match self {
+            FontSource::Google(fnt) => {
+                let thd_fnt = fnt.clone();
+                fetch(&fnt.to_string(), move || thd_fnt.get_with_cache(), timeout)
+            }
+            FontSource::File(pth) => fs::read(pth).map_err(DocError::FileError),
+            FontSource::Bytes(dat) => Ok(dat.clone()),
+        }
+    }
+}
+
+/// Fetches the data of a named font with `get`, retrying network
+/// failures and timeouts with backoff, and waiting at most `timeout`
+/// for each attempt.
+///
+/// With a timeout, `get` runs on its own thread. An attempt timing out
+/// leaves it running and the next attempt waits on the same fetch, so
+/// at most one fetch is in flight. When the last attempt times out,
+/// the thread is detached rather than stopped: it runs until `get`
+/// returns, such as a Google font fetch caching the font for later
+/// documents.
+fn fetch<F>(name: &str, get: F, timeout: Option<Duration>) -> Result<Vec<u8>, DocError>
+where
+    F: Fn() -> Result<Vec<u8>, FontError> + Send + Clone + 'static,
+{
+    let spawn = || {
+        let (snd, rcv) = mpsc::channel();
+        let get = get.clone();
+        thread::spawn(move || {
+            let _ = snd.send(get());
+        });
+        rcv
+    };
+    let mut inf = None;
+    let mut wait = FETCH_BACKOFF;
+    let mut tries = 1;
+    loop {
+        let res = match timeout {
+            None => get().map_err(DocError::from),
+            Some(timeout) => match inf.get_or_insert_with(spawn).recv_timeout(timeout) {
+                Ok(res) => {
+                    inf = None;
+                    res.map_err(DocError::from)
+                }
+                Err(err) => {
+                    if err == RecvTimeoutError::Disconnected {
+                        inf = None;
+                    }
+                    Err(DocError::FontTimeoutError {
+                        font: name.into(),
+                        timeout,
+                    })
+                }
+            },
+        };
+        match res {
+            Err(err) if is_transient(&err) && tries < FETCH_TRIES => {
+                thread::sleep(wait);
+                wait *= 2;
+                tries += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Returns `true` if a font load error may pass when retried.
+fn is_transient(err: &DocError) -> bool {
+    matches!(
+        err,
+        DocError::FontLoadError(FontError::Network(_)) | DocError::FontTimeoutError { .. }
+    )
+}
+
+impl Doc {
+    /// Returns the distinct fonts used by the document.
+    pub fn fnt_srcs(&self) -> Vec<FontSource> {
+        let mut ret = vec![self.fnt.clone()];
+        let pars = self
+            .hdr
+            .iter()
+            .chain(self.ftr.iter())
+            .chain(self.elms.iter().flat_map(Elm::pars));
+        let fnts = pars.flat_map(|par| {
+            par.fnt
+                .iter()
+                .chain(par.runs.iter().filter_map(|run| run.fnt.as_ref()))
+        });
+        for fnt in fnts {
+            if !ret.contains(fnt) {
+                ret.push(fnt.clone());
+            }
+        }
+        ret
+    }
+
+    /// Loads the fonts used by the document.
+    ///
+    /// A font which fails to load is replaced by `fallback_fnt`,
+    /// registered under the family name of the failed font.
+    ///
+    /// ### Returns
+    ///
+    /// Diagnostics with a warning for each replaced font.
+    pub fn load_fnts(
+        &self,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Diagnostics, DocError> {
+        let mut dgn = Diagnostics::default();
+        for fnt in self.fnt_srcs() {
+            if let Vacant(e) = fnts.entry(fnt.clone()) {
+                let fnt_col = match (
+                    create_fnt_col(&fnt, self.fnt_timeout, font_mgr),
+                    &self.fallback_fnt,
+                ) {
+                    (Ok(fnt_col), _) => fnt_col,
+                    (Err(err), Some(fallback_fnt)) => {
+                        let fnt_col = create_fnt_col_as(
+                            fallback_fnt,
+                            &fnt.name(),
+                            self.fnt_timeout,
+                            font_mgr,
+                        )?;
+                        dgn.wrn(format!(
+                            "Font `{}` replaced by `{}`: {}",
+                            fnt.name(),
+                            fallback_fnt.name(),
+                            err
+                        ));
+                        fnt_col
+                    }
+                    (Err(err), None) => return Err(err),
+                };
+                e.insert(fnt_col);
+            }
+        }
+        Ok(dgn)
+    }
+}
+
+/// Fonts loaded once and shared across renders of documents,
+/// such as many letters rendered from one template.
+///
+/// A font keeps the collection it first loaded with, including
+/// a fallback font, regardless of the document rendering it.
+///
+/// A cache is neither `Send` nor `Sync`, as skia font collections
+/// and managers aren't; create a cache for each thread.
+#[derive(Debug, Default)]
+pub struct FontCache {
+    /// Loaded font collections by source.
+    pub fnts: HashMap<FontSource, FontCollection>,
+    /// Font manager creating font collections.
+    pub font_mgr: FontMgr,
+}
+
+impl FontCache {
+    /// Returns a new empty [`FontCache`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of loaded fonts.
+    pub fn len(&self) -> usize {
+        self.fnts.len()
+    }
+
+    /// Returns `true` if no fonts are loaded.
+    pub fn is_empty(&self) -> bool {
+        self.fnts.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_font_cache() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Dear {{name}},"));
+        let mut cache = FontCache::new();
+        assert!(cache.is_empty());
+
+        // Later renders reuse the fonts loaded by the first.
+        for name in ["Albert", "Richard"] {
+            let mut ltr = doc.clone();
+            ltr.replace_all("{{name}}", name);
+            let pth = std::env::temp_dir().join(format!("pdf_doc_test_cache_{}", name));
+            ltr.save_pdf_with_cache(&pth, &mut cache)
+                .expect("Failed to save pdf");
+            assert_eq!(cache.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_name() {
+        assert_eq!(
+            FontSource::from(Font::DomineVariable).name(),
+            Font::DomineVariable.to_string()
+        );
+        let fil = FontSource::File(PathBuf::from("fonts/Acme-Regular.ttf"));
+        assert!(fil.name().starts_with("Acme-Regular-"));
+        assert_ne!(
+            fil.name(),
+            FontSource::File(PathBuf::from("other/Acme-Regular.ttf")).name()
+        );
+        assert_eq!(fil.name(), fil.clone().name());
+
+        let byt = FontSource::Bytes(vec![0, 1]);
+        assert!(byt.name().starts_with("Bytes-"));
+        assert_ne!(byt.name(), FontSource::Bytes(vec![1, 0]).name());
+        assert_eq!(byt.name(), byt.clone().name());
+    }
+
+    #[test]
+    fn test_fnt_srcs() {
+        let fnt = FontSource::File(PathBuf::from("Acme-Regular.ttf"));
+        let mut doc = new_ansi_letter().set_hdr(Some(par("Header").set_fnt(Some(fnt.clone()))));
+        doc.add_par(par("Hello").set_fnt(Some(fnt.clone())));
+        doc.add_par(par("World"));
+        assert_eq!(doc.fnt_srcs(), vec![doc.fnt.clone(), fnt]);
+    }
+
+    #[test]
+    fn test_load_fnts_fallback() {
+        let missing = FontSource::File(PathBuf::from("missing.ttf"));
+        let mut doc = new_ansi_letter().set_fnt(missing.clone());
+        doc.add_par(par("Hello"));
+        let font_mgr = FontMgr::new();
+
+        // Without a fallback a missing font fails.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        assert!(doc.load_fnts(&mut fnts, &font_mgr).is_err());
+
+        // With a fallback a missing font is replaced with a warning.
+        let doc = doc.set_fallback_fnt(Some(crate::tst::tst_fnt()));
+        let dgn = doc
+            .load_fnts(&mut fnts, &font_mgr)
+            .expect("Failed to load fonts");
+        assert_eq!(dgn.wrns.len(), 1);
+        assert!(fnts.contains_key(&missing));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_load_fnts_fallback");
+        let dgn = doc.save_pdf(&pth).expect("Failed to save pdf");
+        assert_eq!(dgn.wrns.len(), 1);
+    }
+
+    #[test]
+    fn test_data() {
+        assert_eq!(FontSource::Bytes(vec![0, 1]).data().unwrap(), vec![0, 1]);
+        assert!(matches!(
+            FontSource::File(PathBuf::from("missing.ttf")).data(),
+            Err(DocError::FileError(_))
+        ));
+    }
+
+    #[test]
+    fn test_font_fetch_timeout() {
+        let doc = new_ansi_letter().set_font_fetch_timeout(Duration::from_secs(30));
+        assert_eq!(doc.fnt_timeout, Some(Duration::from_secs(30)));
+        let font_mgr = FontMgr::new();
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        doc.load_fnts(&mut fnts, &font_mgr)
+            .expect("Failed to load fonts");
+        assert!(fnts.contains_key(&doc.fnt));
+
+        // Only network failures and timeouts are retried.
+        let err = DocError::FontTimeoutError {
+            font: Font::DomineVariable.to_string(),
+            timeout: Duration::from_secs(30),
+        };
+        assert!(is_transient(&err));
+        assert!(!is_transient(&DocError::from("Unparsable")));
+    }
+
+    #[test]
+    fn test_fetch_timeout() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        // Returns a fetcher taking `dur`, and its count of calls.
+        let slow = |dur: Duration| {
+            let cnt = Arc::new(AtomicUsize::new(0));
+            let thd_cnt = cnt.clone();
+            let get = move || {
+                thd_cnt.fetch_add(1, Ordering::SeqCst);
+                thread::sleep(dur);
+                Ok::<Vec<u8>, FontError>(vec![1, 2])
+            };
+            (get, cnt)
+        };
+        let timeout = Some(Duration::from_millis(10));
+
+        // A timed out fetch finishing during the backoff is received by the retry.
+        let (get, cnt) = slow(Duration::from_millis(100));
+        assert_eq!(fetch("Slow", get, timeout).unwrap(), vec![1, 2]);
+        assert_eq!(cnt.load(Ordering::SeqCst), 1);
+
+        // Every attempt times out waiting on the same fetch.
+        let (get, cnt) = slow(Duration::from_secs(10));
+        assert!(matches!(
+            fetch("Stalled", get, timeout),
+            Err(DocError::FontTimeoutError { font, .. }) if font == "Stalled"
+        ));
+        assert_eq!(cnt.load(Ordering::SeqCst), 1);
+    }
+}
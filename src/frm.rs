@@ -0,0 +1,212 @@
+use crate::doc::*;
+use crate::inch::*;
+use crate::mrg::*;
+use crate::sze::*;
+use crate::unit::*;
+use serde::{Deserialize, Serialize};
+
+/// A rectangle on a page through which `elms` flow.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Frame {
+    /// Index of the page.
+    pub pag: usize,
+    /// Distance from the left of the page to the frame.
+    pub x: In,
+    /// Distance from the top of the page to the frame.
+    pub y: In,
+    /// Width of the frame.
+    pub wid: In,
+    /// Height of the frame.
+    pub hgt: In,
+}
+
+impl Frame {
+    /// Returns a new [`Frame`].
+    pub fn new(pag: usize, x: In, y: In, wid: In, hgt: In) -> Self {
+        Self {
+            pag,
+            x,
+            y,
+            wid,
+            hgt,
+        }
+    }
+
+    /// Returns the distance from the top of the page
+    /// to the bottom of the frame in points.
+    pub fn btm(&self) -> f32 {
+        (self.y + self.hgt).pt()
+    }
+}
+
+impl Doc {
+    /// Returns the frame of a page bounded by the margins.
+    pub fn dft_frm(&self, pag: usize) -> Frame {
+        self.dft_frm_in(pag, self.sze, self.mrg)
+    }
+
+    /// Returns the frame of a page of a size bounded by margins.
+    pub fn dft_frm_in(&self, pag: usize, sze: Sze, mrg: Mrg) -> Frame {
+        Frame::new(
+            pag,
+            mrg.lft,
+            mrg.top,
+            sze.width - mrg.width(),
+            sze.height - mrg.height(),
+        )
+    }
+
+    /// Returns a column of the frame of a page bounded by the margins.
+    ///
+    /// The margins are divided into `columns` of equal width
+    /// separated by `column_gap`.
+    pub fn col_frm(&self, pag: usize, col: usize) -> Frame {
+        self.col_frm_in(pag, col, self.sze, self.mrg)
+    }
+
+    /// Returns a column of the frame of a page of a size bounded by margins.
+    pub fn col_frm_in(&self, pag: usize, col: usize, sze: Sze, mrg: Mrg) -> Frame {
+        let frm = self.dft_frm_in(pag, sze, mrg);
+        let cols = self.columns.max(1);
+        let wid = (frm.wid - self.column_gap * (cols - 1)) / cols;
+        Frame {
+            x: frm.x + (wid + self.column_gap) * col as f32,
+            wid,
+            ..frm
+        }
+    }
+
+    /// Returns the frame at a position in the flow.
+    ///
+    /// Positions past the end of `frms` continue in the default
+    /// column frames of the pages following the last frame.
+    pub fn frm_at(&self, idx: usize) -> Frame {
+        self.frm_at_in(idx, self.sze, self.mrg)
+    }
+
+    /// Returns the frame at a position in the flow
+    /// with default frames of a page size bounded by margins.
+    pub fn frm_at_in(&self, idx: usize, sze: Sze, mrg: Mrg) -> Frame {
+        let cols = self.columns.max(1) as usize;
+        let col_frm =
+            |pag: usize, idx: usize| self.col_frm_in(pag + idx / cols, idx % cols, sze, mrg);
+        match self.frms.last() {
+            Some(_) if idx < self.frms.len() => self.frms[idx],
+            Some(lst) => col_frm(lst.pag + 1, idx - self.frms.len()),
+            None => col_frm(0, idx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fnt::*;
+    use skia_safe::{textlayout::FontCollection, FontMgr};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_frm_at() {
+        let mut doc = new_ansi_letter();
+        assert_eq!(doc.frm_at(2), doc.dft_frm(2));
+
+        let frm = Frame::new(1, In(1.0), In(1.0), In(3.0), In(2.0));
+        doc.add_frm(frm);
+        assert_eq!(doc.frm_at(0), frm);
+        assert_eq!(doc.frm_at(1), doc.dft_frm(2));
+        assert_eq!(doc.frm_at(2), doc.dft_frm(3));
+    }
+
+    #[test]
+    fn test_col_frm() {
+        let doc = new_ansi_letter().set_columns(2).set_column_gap(In(0.5));
+        assert_eq!(
+            doc.frm_at(0),
+            Frame::new(0, In(1.0), In(1.0), In(3.0), In(9.0))
+        );
+        assert_eq!(
+            doc.frm_at(1),
+            Frame::new(0, In(4.5), In(1.0), In(3.0), In(9.0))
+        );
+        assert_eq!(
+            doc.frm_at(2),
+            Frame::new(1, In(1.0), In(1.0), In(3.0), In(9.0))
+        );
+    }
+
+    #[test]
+    fn test_lay_cols() {
+        let mut doc = new_ansi_letter().set_columns(2).set_column_gap(In(0.5));
+        for _ in 0..60 {
+            doc.add_par(par("A paragraph repeated until the column overflows."));
+        }
+        doc.add_pag_brk();
+        doc.add_par(par("After the break."));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+
+        // Paragraphs flow down the first column then the second.
+        assert_eq!(pags[0][0].x, In(1.0).pt());
+        assert_eq!(pags[0][0].wid, In(3.0).pt());
+        assert!(pags[0].iter().any(|lay| lay.x == In(4.5).pt()));
+
+        // A page break continues at the first column of the next page.
+        let lst = pags.last().unwrap().last().unwrap();
+        assert_eq!(lst.idx, 61);
+        assert_eq!(lst.x, In(1.0).pt());
+        assert_eq!(lst.y, In(1.0).pt());
+    }
+
+    #[test]
+    fn test_lay_col_brk() {
+        let mut doc = new_ansi_letter().set_columns(2).set_column_gap(In(0.5));
+        doc.add_par(par("First column."));
+        doc.add_col_brk();
+        doc.add_par(par("Second column."));
+        doc.add_col_brk();
+        doc.add_par(par("Next page."));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+
+        // A column break continues at the top of the next column,
+        // and after the last column at the first column of the next page.
+        assert_eq!(pags.len(), 2);
+        assert_eq!((pags[0][1].idx, pags[0][1].x), (2, In(4.5).pt()));
+        assert_eq!(pags[0][1].y, pags[0][0].y);
+        assert_eq!((pags[1][0].idx, pags[1][0].x), (4, In(1.0).pt()));
+    }
+
+    #[test]
+    fn test_lay_frms_overflow() {
+        let mut doc = new_ansi_letter();
+        doc.add_frm(Frame::new(0, In(1.0), In(1.0), In(3.0), In(1.0)));
+        doc.add_frm(Frame::new(0, In(4.5), In(1.0), In(3.0), In(9.0)));
+        let txt = "Frames flow a single stream of paragraphs through rectangles placed anywhere on a page.";
+        doc.add_par(par(txt));
+        doc.add_par(par(txt));
+        doc.add_par(par(txt));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert_eq!(pags.len(), 1);
+        assert_eq!(pags[0].len(), 3);
+
+        // The first paragraph fills the first frame and the rest overflow.
+        assert_eq!(pags[0][0].x, In(1.0).pt());
+        assert_eq!(pags[0][1].x, In(4.5).pt());
+        assert_eq!(pags[0][1].y, In(1.0).pt());
+        assert_eq!(pags[0][2].x, In(4.5).pt());
+        assert!(pags[0][2].y > pags[0][1].y);
+    }
+}
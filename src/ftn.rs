@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use crate::doc::*;
+use crate::err::*;
+use crate::fnt::*;
+use skia_safe::{
+    textlayout::{FontCollection, Paragraph, TextStyle},
+    FontMgr,
+};
+
+/// Size of a superscript as a fraction of the font size.
+const SUP_RTO: f32 = 0.6;
+
+/// Height a superscript is raised as a fraction of the font size.
+const SUP_RSE: f32 = 0.35;
+
+/// Size of footnote text as a fraction of the document font size.
+const FTN_RTO: f32 = 0.8;
+
+/// Fraction of the text width spanned by the footnote separator.
+const FTN_SEP_FRC: f32 = 1.0 / 3.0;
+
+/// Sets a text style to a superscript of its font size.
+pub fn set_sup(ts: &mut TextStyle) {
+    let fnt_sze = ts.font_size();
+    ts.set_font_size(fnt_sze * SUP_RTO);
+    ts.set_baseline_shift(-fnt_sze * SUP_RSE);
+}
+
+impl Par {
+    /// Appends a _footnote_ marker.
+    ///
+    /// The marker is drawn as a superscript, and the footnote is
+    /// drawn with the same marker at the bottom of the page
+    /// on which the paragraph begins.
+    ///
+    /// Text content set before the first run becomes the first run.
+    ///
+    /// ### Arguments
+    ///
+    /// * `mrk` - The marker, such as the note number.
+    /// * `txt` - Text of the footnote.
+    ///
+    /// ### Returns
+    ///
+    /// Self with the appended footnote marker.
+    pub fn add_footnote(self, mrk: &str, txt: &str) -> Self {
+        let mut run = Run::new(mrk, None);
+        run.ftn = Some(txt.into());
+        self.add_styled_run(run)
+    }
+
+    /// Returns the runs marking footnotes.
+    pub fn ftns(&self) -> Vec<&Run> {
+        self.runs.iter().filter(|run| run.ftn.is_some()).collect()
+    }
+}
+
+impl Doc {
+    /// Returns the paragraph of a footnote marked by a run.
+    ///
+    /// The footnote begins with its superscript marker
+    /// and is set smaller than the document font.
+    pub fn ftn_par(&self, mrk: &Run) -> Par {
+        let txt = mrk.ftn.as_deref().unwrap_or_default();
+        Par {
+            fnt_sze: Some(self.fnt_sze * FTN_RTO),
+            aln: Some(Align::Left),
+            spc_lne: Some(LineSpace::Single),
+            has_ind: Some(false),
+            ..Default::default()
+        }
+        .add_styled_run(mrk.clone())
+        .add_run(&format!(" {}", txt), None)
+    }
+
+    /// Returns the rule separating footnotes from the page body.
+    pub fn ftn_sep(&self) -> Rule {
+        rule().set_wid_frc(FTN_SEP_FRC)
+    }
+
+    /// Builds the footnotes of a paragraph laid out to a width in points.
+    pub(crate) fn bld_ftns(
+        &self,
+        par: &Par,
+        wid: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Vec<(Paragraph, Par)>, DocError> {
+        par.ftns()
+            .into_iter()
+            .map(|mrk| {
+                let ftn = self.ftn_par(mrk);
+                Ok((self.bld_par_at(&ftn, wid, fnts, font_mgr)?, ftn))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lay::*;
+
+    #[test]
+    fn test_add_footnote() {
+        let par = par("Claim")
+            .add_footnote("1", "Source.")
+            .add_run(" holds.", None);
+        assert_eq!(par.all_txt(), "Claim1 holds.");
+        let ftns = par.ftns();
+        assert_eq!(ftns.len(), 1);
+        assert_eq!(ftns[0].txt, "1");
+        assert_eq!(ftns[0].ftn.as_deref(), Some("Source."));
+
+        let doc = new_ansi_letter();
+        let ftn = doc.ftn_par(ftns[0]);
+        assert_eq!(ftn.all_txt(), "1 Source.");
+        assert!(ftn.fnt_sze.unwrap() < doc.fnt_sze);
+    }
+
+    #[test]
+    fn test_footnote_save_pdf() {
+        let mut doc = new_ansi_letter();
+        for idx in 0..60 {
+            let par = par("A paragraph of body text.");
+            doc.add_par(match idx {
+                0 => par.add_footnote("1", "A footnote at the bottom of the first page."),
+                _ => par,
+            });
+        }
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+
+        // The footnote sits below the body of the first page only.
+        let is_ftn = |lay: &&Lay| matches!(lay.cnt, LayCnt::Ftn(..));
+        let ftn = pags[0].iter().find(is_ftn).expect("Expected a footnote");
+        let bdy_btm = pags[0]
+            .iter()
+            .filter(|lay| matches!(lay.cnt, LayCnt::Par(_)))
+            .map(|lay| lay.y + lay.hgt)
+            .fold(0.0, f32::max);
+        assert!(ftn.y > bdy_btm);
+        assert!(pags[1].iter().all(|lay| !is_ftn(&lay)));
+
+        // The footnote takes space from the body of the first page.
+        let mut pln = doc.clone();
+        pln.elms[0] = Elm::Par(par("A paragraph of body text."));
+        let pln_pags = pln
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        let bdy_cnt = |lays: &[Lay]| lays.iter().filter(|lay| lay.idx > 0).count();
+        assert!(bdy_cnt(&pags[0]) < bdy_cnt(&pln_pags[0]));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_footnote");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+}
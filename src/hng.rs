@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::cap::*;
+use crate::doc::*;
+use crate::err::*;
+use crate::fnt::*;
+use crate::unit::*;
+use skia_safe::{
+    textlayout::{FontCollection, Paragraph},
+    FontMgr,
+};
+
+impl Doc {
+    /// Returns the hanging indentation of a paragraph in points,
+    /// or `None` when the paragraph has no hanging indentation.
+    ///
+    /// A paragraph with an indented first line or
+    /// tab stops has no hanging indentation.
+    pub fn hng_ind(&self, par: &Par) -> Option<f32> {
+        if par.has_ind.unwrap_or(self.has_ind) || !par.tabs.is_empty() {
+            return None;
+        }
+        par.hang_ind.map(|hng| hng.pt()).filter(|hng| *hng > 0.0)
+    }
+
+    /// Builds a paragraph with a hanging indentation
+    /// `hng` points wide laid out to a width in points.
+    ///
+    /// A placeholder the width of the indentation
+    /// begins each line after the first.
+    pub(crate) fn bld_hng(
+        &self,
+        par: &Par,
+        hng: f32,
+        wid: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Paragraph, DocError> {
+        // Begin each following line where the previous line ends.
+        let mut brks: Vec<usize> = vec![];
+        loop {
+            let mut paragraph = self.bld_txt(par, &brks, hng, fnts, font_mgr)?;
+            paragraph.layout(wid);
+            let lne = brks.len();
+            if lne + 1 >= paragraph.line_number() {
+                return Ok(paragraph);
+            }
+            let end = paragraph.get_actual_text_range(lne, true).end - PLC_LEN * lne;
+            if end <= brks.last().copied().unwrap_or_default() {
+                return Ok(paragraph);
+            }
+            brks.push(end);
+        }
+    }
+}
+
+/// Returns the _UTF-16_ ranges and URLs of linked runs
+/// of a paragraph built with a hanging indentation.
+pub(crate) fn hng_lnk_rngs<'a>(
+    paragraph: &Paragraph,
+    par: &'a Par,
+) -> Vec<(Range<usize>, &'a str)> {
+    let txt = par.all_txt();
+
+    // Placeholders begin the lines after the first.
+    let brks: Vec<usize> = (1..paragraph.line_number())
+        .map(|lne| {
+            let beg = paragraph.get_actual_text_range(lne, true).start - PLC_LEN * (lne - 1);
+            txt.get(..beg).map_or(0, |pre| pre.encode_utf16().count())
+        })
+        .collect();
+    let pos = |idx: usize, inc: bool| {
+        idx + brks
+            .iter()
+            .filter(|brk| **brk < idx || (inc && **brk == idx))
+            .count()
+    };
+
+    par.lnk_rngs(0)
+        .into_iter()
+        .map(|(rng, url)| (pos(rng.start, true)..pos(rng.end, false), url))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inch::*;
+
+    #[test]
+    fn test_hng_ind() {
+        let doc = new_ansi_letter();
+        let par = par("Entry").set_hang_ind(Some(In(0.5)));
+        assert_eq!(doc.hng_ind(&par), None);
+        let par = par.set_has_ind(Some(false));
+        assert_eq!(doc.hng_ind(&par), Some(In(0.5).pt()));
+        assert_eq!(doc.hng_ind(&par.set_hang_ind(None)), None);
+    }
+
+    #[test]
+    fn test_bibliography_save_pdf() {
+        let mut doc = new_ansi_letter();
+        let refs = [
+            "Knuth, D. E. (1984). Literate programming. The Computer Journal, 27(2), 97-111.",
+            "Lamport, L. (1994). LaTeX: A document preparation system (2nd ed.). Addison-Wesley, Reading, Massachusetts, which remains a standard reference for typesetting.",
+        ];
+        for txt in refs {
+            doc.add_par(
+                par(txt)
+                    .set_has_ind(Some(false))
+                    .set_hang_ind(Some(In(0.5)))
+                    .add_link(" Online.", "https://example.com"),
+            );
+        }
+
+        // Lines after the first begin at the hanging indentation.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let Elm::Par(entry) = &doc.elms[1] else {
+            panic!("Expected a paragraph");
+        };
+        let paragraph = doc
+            .bld_par_at(entry, In(3.0).pt(), &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph");
+        let plcs = paragraph.get_rects_for_placeholders();
+        assert!(paragraph.line_number() > 2);
+        assert_eq!(plcs.len(), paragraph.line_number() - 1);
+        for plc in plcs.iter() {
+            assert!(plc.rect.left.abs() < 1.0);
+            assert!((plc.rect.width() - In(0.5).pt()).abs() < 1.0);
+        }
+        assert!(plcs[0].rect.top > 0.0);
+        let lnks = hng_lnk_rngs(&paragraph, entry);
+        assert_eq!(lnks.len(), 1);
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_bibliography");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+}
@@ -0,0 +1,378 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+#[cfg(feature = "hyphenation")]
+use std::sync::OnceLock;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::doc::*;
+#[cfg(feature = "hyphenation")]
+use hyphenation::{Hyphenator as _, Language, Load, Standard};
+
+/// Soft hyphen marking where a word may break across lines.
+///
+/// Passed through to the line breaker unchanged, it is a break
+/// opportunity rather than visible text within a line.
+pub const SHY: char = '\u{00AD}';
+
+/// Non-breaking space joining two words on one line.
+///
+/// Passed through to the line breaker unchanged, it is drawn
+/// as a space but is never a break opportunity.
+pub const NBSP: char = '\u{00A0}';
+
+/// Hyphenates words with _Liang's_ pattern algorithm.
+///
+/// Built-in languages use the _TeX_ patterns of the `hyphenation` crate,
+/// and require the `hyphenation` feature.
+#[derive(Debug, Default, Clone)]
+pub struct Hyphenator {
+    /// Built-in dictionary used in place of `pats`.
+    #[cfg(feature = "hyphenation")]
+    std: Option<Standard>,
+    /// Break values between the letters of each pattern.
+    pats: HashMap<String, Vec<u8>>,
+    /// Length of the longest pattern in characters.
+    max_len: usize,
+    /// Minimum number of characters before a break.
+    lft_min: usize,
+    /// Minimum number of characters after a break.
+    rht_min: usize,
+}
+
+impl Hyphenator {
+    /// Returns a new [`Hyphenator`] from whitespace separated _TeX_ style patterns.
+    ///
+    /// A pattern interleaves letters with digits, where an odd digit allows
+    /// a break and an even digit inhibits one. A `.` matches a word edge.
+    pub fn new(pats: &str) -> Self {
+        let mut ret = Hyphenator {
+            lft_min: 2,
+            rht_min: 3,
+            ..Default::default()
+        };
+        for pat in pats.split_whitespace() {
+            let mut ltrs = String::new();
+            let mut vals = vec![0u8];
+            for c in pat.chars() {
+                match c.to_digit(10) {
+                    Some(d) => *vals.last_mut().unwrap() = d as u8,
+                    None => {
+                        ltrs.push(c);
+                        vals.push(0);
+                    }
+                }
+            }
+            ret.max_len = ret.max_len.max(ltrs.chars().count());
+            ret.pats.insert(ltrs, vals);
+        }
+        ret
+    }
+
+    /// Returns a new [`Hyphenator`] from a built-in dictionary.
+    #[cfg(feature = "hyphenation")]
+    fn from_std(std: Standard) -> Self {
+        Hyphenator {
+            std: Some(std),
+            lft_min: 2,
+            rht_min: 3,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the character indices of a word before which it may break.
+    pub fn brks(&self, word: &str) -> Vec<usize> {
+        let len = word.chars().count();
+        if len < self.lft_min + self.rht_min {
+            return vec![];
+        }
+
+        // Map the dictionary's byte offsets to character indices.
+        #[cfg(feature = "hyphenation")]
+        if let Some(std) = &self.std {
+            let low: String = word
+                .chars()
+                .map(|c| c.to_lowercase().next().unwrap_or(c))
+                .collect();
+            let brks = std.hyphenate(&low).breaks;
+            return low
+                .char_indices()
+                .enumerate()
+                .filter(|(idx, (ofs, _))| {
+                    brks.contains(ofs) && *idx >= self.lft_min && len - idx >= self.rht_min
+                })
+                .map(|(idx, _)| idx)
+                .collect();
+        }
+
+        // Accumulate the largest pattern value between each letter.
+        let edg = std::iter::once('.');
+        let ltrs: Vec<char> = edg
+            .clone()
+            .chain(word.chars().map(|c| c.to_lowercase().next().unwrap_or(c)))
+            .chain(edg)
+            .collect();
+        let mut vals = vec![0u8; ltrs.len() + 1];
+        for beg in 0..ltrs.len() {
+            for end in beg + 1..=ltrs.len().min(beg + self.max_len) {
+                let sub: String = ltrs[beg..end].iter().collect();
+                if let Some(pat) = self.pats.get(&sub) {
+                    for (ofs, val) in pat.iter().enumerate() {
+                        vals[beg + ofs] = vals[beg + ofs].max(*val);
+                    }
+                }
+            }
+        }
+
+        // A break before the word's character `idx` follows the leading edge.
+        (self.lft_min..=len - self.rht_min)
+            .filter(|idx| vals[idx + 1] % 2 == 1)
+            .collect()
+    }
+
+    /// Returns text with soft hyphens inserted where words may break.
+    pub fn hyphenate(&self, txt: &str) -> String {
+        let mut ret = String::with_capacity(txt.len());
+        let mut word = String::new();
+        for c in txt.chars().chain(std::iter::once(' ')) {
+            if c.is_alphabetic() {
+                word.push(c);
+                continue;
+            }
+            let brks = self.brks(&word);
+            for (idx, ltr) in word.chars().enumerate() {
+                if brks.contains(&idx) {
+                    ret.push(SHY);
+                }
+                ret.push(ltr);
+            }
+            word.clear();
+            ret.push(c);
+        }
+        ret.pop();
+        ret
+    }
+}
+
+/// Returns a hyphenator with the built-in patterns of a language,
+/// or `None` if the language has no built-in patterns.
+///
+/// The patterns of a language are loaded once and shared.
+/// Built-in patterns require the `hyphenation` feature.
+///
+/// ### Arguments
+///
+/// * `lang` - A language tag such as `en` or `en-US`.
+#[cfg(feature = "hyphenation")]
+pub fn hyphenator(lang: &str) -> Option<Arc<Hyphenator>> {
+    static EN_US: OnceLock<Option<Arc<Hyphenator>>> = OnceLock::new();
+    match lang.split(['-', '_']).next() {
+        Some("en") => EN_US
+            .get_or_init(|| {
+                Standard::from_embedded(Language::EnglishUS)
+                    .ok()
+                    .map(|std| Arc::new(Hyphenator::from_std(std)))
+            })
+            .clone(),
+        _ => None,
+    }
+}
+
+/// Returns a hyphenator with the built-in patterns of a language,
+/// or `None` as built-in patterns require the `hyphenation` feature.
+///
+/// ### Arguments
+///
+/// * `lang` - A language tag such as `en` or `en-US`.
+#[cfg(not(feature = "hyphenation"))]
+pub fn hyphenator(_lang: &str) -> Option<Arc<Hyphenator>> {
+    None
+}
+
+/// The hyphenator of a document's custom patterns,
+/// parsed once and kept with the document.
+#[derive(Debug, Default)]
+pub struct HypCache(Mutex<Option<(String, Arc<Hyphenator>)>>);
+
+impl Clone for HypCache {
+    fn clone(&self) -> Self {
+        HypCache(Mutex::new(self.lock().clone()))
+    }
+}
+
+impl HypCache {
+    /// Returns the hyphenator of patterns, parsing them
+    /// unless they match the patterns last parsed.
+    fn get(&self, pats: &str) -> Arc<Hyphenator> {
+        let mut lst = self.lock();
+        match &*lst {
+            Some((cur, hyp)) if cur == pats => hyp.clone(),
+            _ => {
+                let hyp = Arc::new(Hyphenator::new(pats));
+                *lst = Some((pats.into(), hyp.clone()));
+                hyp
+            }
+        }
+    }
+
+    /// Locks the cache, recovering it when poisoned.
+    fn lock(&self) -> MutexGuard<'_, Option<(String, Arc<Hyphenator>)>> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Doc {
+    /// Returns the hyphenator of the document,
+    /// or `None` if hyphenation is off or unavailable for `lang`.
+    ///
+    /// Patterns are parsed once rather than for each paragraph.
+    pub fn hyphenator(&self) -> Option<Arc<Hyphenator>> {
+        if !self.hyphenate {
+            return None;
+        }
+        match &self.hyp_pats {
+            Some(pats) => Some(self.hyp_cache.get(pats)),
+            None => hyphenator(&self.lang),
+        }
+    }
+
+    /// Returns a paragraph with soft hyphens inserted into its text
+    /// and runs when the document hyphenates.
+    pub fn hyp_par<'a>(&self, par: &'a Par) -> Cow<'a, Par> {
+        let Some(hyp) = self.hyphenator() else {
+            return Cow::Borrowed(par);
+        };
+        let mut ret = par.clone();
+        ret.txt = hyp.hyphenate(&par.txt);
+        for run in ret.runs.iter_mut() {
+            run.txt = hyp.hyphenate(&run.txt);
+        }
+        Cow::Owned(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fnt::*;
+    use skia_safe::{
+        textlayout::{FontCollection, Paragraph},
+        FontMgr,
+    };
+
+    #[cfg(feature = "hyphenation")]
+    #[test]
+    fn test_brks() {
+        let hyp = hyphenator("en-US").unwrap();
+        assert_eq!(hyp.brks("hyphenation"), vec![2, 6]);
+        assert_eq!(hyp.brks("Winter"), vec![3]);
+        assert_eq!(hyp.brks("happiness"), vec![3, 5]);
+        assert!(hyp.brks("cat").is_empty());
+
+        // Inflections with doubled consonants don't break.
+        for word in ["called", "passed", "filled", "dressed"] {
+            assert!(hyp.brks(word).is_empty(), "{word}");
+        }
+    }
+
+    #[test]
+    fn test_pats_brks() {
+        let hyp = Hyphenator::new("1tion n1n");
+        assert_eq!(hyp.brks("mention"), vec![3]);
+        assert_eq!(hyp.brks("tunnel"), vec![3]);
+    }
+
+    #[cfg(feature = "hyphenation")]
+    #[test]
+    fn test_hyphenate() {
+        let hyp = hyphenator("en-US").unwrap();
+        assert_eq!(
+            hyp.hyphenate("happiness, winter 42."),
+            "hap\u{ad}pi\u{ad}ness, win\u{ad}ter 42."
+        );
+        assert!(hyphenator("xx").is_none());
+    }
+
+    #[test]
+    fn test_hyp_par() {
+        let doc = new_ansi_letter();
+        let par = par("winter");
+        assert_eq!(doc.hyp_par(&par).txt, "winter");
+
+        let doc = doc.set_hyphenate(true);
+        #[cfg(feature = "hyphenation")]
+        assert_eq!(doc.hyp_par(&par).txt, "win\u{ad}ter");
+
+        let doc = doc.set_hyp_pats(Some("w1i".into()));
+        assert_eq!(doc.hyp_par(&par).txt, "winter");
+    }
+
+    #[test]
+    fn test_hyp_cache() {
+        // Documents with different patterns each keep their parsed patterns.
+        let one = new_ansi_letter()
+            .set_hyphenate(true)
+            .set_hyp_pats(Some("1tion".into()));
+        let two = one.clone().set_hyp_pats(Some("n1n".into()));
+        let fst = one.hyphenator().unwrap();
+        assert!(Arc::ptr_eq(
+            &two.hyphenator().unwrap(),
+            &two.hyphenator().unwrap()
+        ));
+        assert!(Arc::ptr_eq(&fst, &one.hyphenator().unwrap()));
+        assert_eq!(fst.brks("mention"), vec![3]);
+
+        // Changed patterns are parsed again.
+        let mut one = one;
+        one.hyp_pats = Some("n1n".into());
+        assert_eq!(one.hyphenator().unwrap().brks("tunnel"), vec![3]);
+    }
+
+    #[test]
+    fn test_shy_nbsp() {
+        let doc = new_ansi_letter().set_has_ind(false);
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let mut bld = |txt: &str, wid: f32| {
+            doc.bld_par_at(&par(txt), wid, &mut fnts, &font_mgr)
+                .expect("Failed to build paragraph")
+        };
+
+        // A soft hyphen within a line is not drawn.
+        let plain = bld("winter", 1000.0).longest_line();
+        let shy = bld(&format!("win{SHY}ter"), 1000.0).longest_line();
+        assert!((plain - shy).abs() < 0.5);
+
+        // A non-breaking space keeps two words on the same line.
+        let wid = bld("one two", 1000.0).longest_line() + 1.0;
+        let plain = bld("one two it", wid);
+        let nbsp = bld(&format!("one two{NBSP}it"), wid);
+        assert_eq!(plain.line_number(), 2);
+        assert_eq!(nbsp.line_number(), 2);
+        let fst_wid = |paragraph: &Paragraph| paragraph.get_line_metrics_at(0).unwrap().width;
+        assert!(fst_wid(&nbsp) < fst_wid(&plain));
+    }
+
+    #[cfg(feature = "hyphenation")]
+    #[test]
+    fn test_hyphenate_save_pdf() {
+        let mut doc = new_ansi_letter().set_hyphenate(true).set_lang("en-US");
+        let txt = "Quantum chromodynamics describes interactions between quarks and gluons, whose asymptotic freedom and confinement shape hadronic matter.";
+        let par = par(txt).add_link(" Lattice", "https://example.com");
+
+        // Soft hyphens are breaks rather than visible text.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let plain = new_ansi_letter()
+            .bld_par(&par, &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph");
+        let hyphenated = doc
+            .bld_par(&par, &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph");
+        assert!(hyphenated.height() <= plain.height());
+
+        doc.add_par(par);
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_hyphenate");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+}
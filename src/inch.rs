@@ -1,13 +1,17 @@
+use crate::pt::*;
 use crate::unit::*;
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::{Display, Formatter, Result},
-    ops::{Add, Deref, Div, Mul, Rem, Sub},
+    ops::Deref,
 };
 
 /// Points per inch.
 pub const PT_PER_IN: f32 = 72.0;
 
+/// Millimeters per inch.
+pub const MM_PER_IN: f32 = 25.4;
+
 /// A length in inches.
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub struct In(pub f32);
@@ -32,96 +36,14 @@ impl Unit for In {
     }
 }
 
-// Implement arithmetic operations with two operands of `In`
-
-impl Add for In {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self {
-        In(self.0 + rhs.0)
-    }
-}
-
-impl Sub for In {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self {
-        In(self.0 - rhs.0)
-    }
-}
-
-impl Mul for In {
-    type Output = Self;
-
-    fn mul(self, rhs: Self) -> Self {
-        In(self.0 * rhs.0)
-    }
-}
-
-impl Div for In {
-    type Output = Self;
-
-    fn div(self, rhs: Self) -> Self {
-        In(self.0 / rhs.0)
-    }
-}
-
-impl Rem for In {
-    type Output = Self;
-
-    fn rem(self, rhs: Self) -> Self {
-        In(self.0 % rhs.0)
-    }
-}
-
-// Implement arithmetic operations with all primitive numeric types
-macro_rules! impl_numeric_ops {
-    ($($t:ty)*) => {
-        $(
-            impl Add<$t> for In {
-                type Output = Self;
-
-                fn add(self, rhs: $t) -> Self {
-                    In(self.0 + rhs as f32)
-                }
-            }
-
-            impl Sub<$t> for In {
-                type Output = Self;
-
-                fn sub(self, rhs: $t) -> Self {
-                    In(self.0 - rhs as f32)
-                }
-            }
-
-            impl Mul<$t> for In {
-                type Output = Self;
-
-                fn mul(self, rhs: $t) -> Self {
-                    In(self.0 * rhs as f32)
-                }
-            }
-
-            impl Div<$t> for In {
-                type Output = Self;
-
-                fn div(self, rhs: $t) -> Self {
-                    In(self.0 / rhs as f32)
-                }
-            }
-
-            impl Rem<$t> for In {
-                type Output = Self;
-
-                fn rem(self, rhs: $t) -> Self {
-                    In(self.0 % rhs as f32)
-                }
-            }
-        )*
+impl In {
+    /// Returns a length in inches from a length in points.
+    pub fn from_pt(pt: Pt) -> Self {
+        In(pt.0 / PT_PER_IN)
     }
 }
 
-impl_numeric_ops!(i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64);
+impl_unit_ops!(In);
 
 #[cfg(test)]
 mod tests {
@@ -143,6 +65,11 @@ mod tests {
         assert_eq!(actual_points, expected_points);
     }
 
+    #[test]
+    fn test_from_pt() {
+        assert_eq!(In::from_pt(Pt(36.0)), In(0.5));
+    }
+
     #[test]
     fn test_round_trip_serialize_deserialize() {
         let original = In(12.34);
@@ -197,6 +124,26 @@ mod tests {
         assert_eq!(result.0, 1.0);
     }
 
+    #[test]
+    fn test_neg_in() {
+        assert_eq!(-In(1.0), In(-1.0));
+    }
+
+    #[test]
+    fn test_assign_in() {
+        let mut a = In(5.0);
+        a += In(3.0);
+        assert_eq!(a.0, 8.0);
+        a -= In(2.0);
+        assert_eq!(a.0, 6.0);
+        a *= In(2.0);
+        assert_eq!(a.0, 12.0);
+        a /= In(3.0);
+        assert_eq!(a.0, 4.0);
+        a %= In(3.0);
+        assert_eq!(a.0, 1.0);
+    }
+
     macro_rules! test_numeric_ops {
         ($($name:ident: $t:ty, $val:expr)*) => {
             $(
@@ -214,6 +161,15 @@ mod tests {
                     assert_eq!(result_mul.0, 5.0 * $val as f32);
                     assert_eq!(result_div.0, 5.0 / $val as f32);
                     assert_eq!(result_rem.0, 5.0 % $val as f32);
+
+                    let mut b = a;
+                    b += $val as $t;
+                    b -= $val as $t;
+                    b *= $val as $t;
+                    b /= $val as $t;
+                    assert_eq!(b, a);
+                    b %= $val as $t;
+                    assert_eq!(b.0, 5.0 % $val as f32);
                 }
             )*
         }
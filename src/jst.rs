@@ -0,0 +1,141 @@
+use crate::cap::*;
+use crate::doc::*;
+use skia_safe::textlayout::Paragraph;
+
+/// Width in points left unfilled by a justified last line,
+/// keeping its widened text from wrapping.
+const JST_TOL: f32 = 0.1;
+
+impl Par {
+    /// Returns the paragraph with each space from a byte offset
+    /// of its text widened by `wrd_spc` points.
+    fn wdn_from(&self, idx: usize, wrd_spc: f32) -> Par {
+        let runs = if self.runs.is_empty() {
+            vec![Run::new(&self.txt, None)]
+        } else {
+            self.runs.clone()
+        };
+
+        // Split the run containing the offset and widen the runs following it.
+        let mut pos = 0;
+        let mut ret = Vec::with_capacity(runs.len() + 1);
+        for mut run in runs {
+            let end = pos + run.txt.len();
+            if end <= idx {
+                ret.push(run);
+            } else if pos >= idx {
+                run.wrd_spc = Some(wrd_spc);
+                ret.push(run);
+            } else {
+                let tal = run.txt.split_off(idx - pos);
+                ret.push(run.clone());
+                ret.push(Run {
+                    txt: tal,
+                    wrd_spc: Some(wrd_spc),
+                    ..run
+                });
+            }
+            pos = end;
+        }
+
+        Par {
+            txt: String::new(),
+            runs: ret,
+            ..self.clone()
+        }
+    }
+}
+
+impl Doc {
+    /// Returns a fully justified paragraph with the spaces of its
+    /// last line widened so that the line spans a width in points,
+    /// or `None` when the last line is not widened.
+    ///
+    /// ### Arguments
+    ///
+    /// * `par` - The transformed and hyphenated paragraph.
+    /// * `paragraph` - The paragraph built and laid out to the width.
+    /// * `wid` - Width of the paragraph in points.
+    pub(crate) fn jst_lst(&self, par: &Par, paragraph: &Paragraph, wid: f32) -> Option<Par> {
+        if par.aln.unwrap_or(self.aln) != Align::JustifyAll || !par.tabs.is_empty() {
+            return None;
+        }
+        let lne = paragraph.line_number().checked_sub(1)?;
+        let lm = paragraph.get_line_metrics_at(lne)?;
+        let gap = wid - lm.width as f32 - JST_TOL;
+
+        // Placeholders on earlier lines precede the last line in the built text,
+        // and text built following a drop cap lacks its first character.
+        let top = (lm.baseline - lm.ascent) as f32;
+        let plcs = paragraph
+            .get_rects_for_placeholders()
+            .iter()
+            .filter(|plc| plc.rect.top < top)
+            .count();
+        let txt = par.all_txt();
+        let cap_len = match self.cap_par(par) {
+            Some(_) => txt.len() - par.cap_bdy().all_txt().len(),
+            None => 0,
+        };
+        let beg = paragraph.get_actual_text_range(lne, false).start;
+        let beg = (beg + cap_len).checked_sub(PLC_LEN * plcs)?;
+
+        let spcs = txt.get(beg..)?.trim_end().matches(' ').count();
+        if spcs == 0 || gap <= 0.0 {
+            return None;
+        }
+        Some(par.wdn_from(beg, gap / spcs as f32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fnt::*;
+    use crate::inch::*;
+    use crate::unit::*;
+    use skia_safe::{textlayout::FontCollection, FontMgr};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_wdn_from() {
+        let par = par("Poster headline").add_run(" set wide", Some(Style::Bold));
+        let wdn = par.wdn_from(10, 2.0);
+        assert_eq!(wdn.all_txt(), par.all_txt());
+        assert_eq!(wdn.runs.len(), 3);
+        assert_eq!(wdn.runs[0].txt, "Poster hea");
+        assert_eq!(wdn.runs[0].wrd_spc, None);
+        assert_eq!(wdn.runs[1].txt, "dline");
+        assert_eq!(wdn.runs[2].wrd_spc, Some(2.0));
+        assert_eq!(wdn.runs[2].fnt_sty, Some(Style::Bold));
+    }
+
+    #[test]
+    fn test_justify_all_save_pdf() {
+        let mut doc = new_ansi_letter();
+        let txt = "A poster paragraph set with full justification stretches every line across the column, including its short final line of words.";
+        doc.add_par(par(txt).set_aln(Some(Align::Justify)));
+        doc.add_par(par(txt).set_aln(Some(Align::JustifyAll)));
+
+        // Only the fully justified last line spans the width.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let wid = In(3.0).pt();
+        let lst_wid = |elm: &Elm, fnts: &mut HashMap<FontSource, FontCollection>| {
+            let Elm::Par(par) = elm else {
+                panic!("Expected a paragraph");
+            };
+            let paragraph = doc
+                .bld_par_at(par, wid, fnts, &font_mgr)
+                .expect("Failed to build paragraph");
+            let lne = paragraph.line_number() - 1;
+            assert!(lne > 0);
+            paragraph.get_line_metrics_at(lne).unwrap().width as f32
+        };
+        assert!(lst_wid(&doc.elms[0], &mut fnts) < wid - 1.0);
+        assert!((lst_wid(&doc.elms[1], &mut fnts) - wid).abs() < 1.0);
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_justify_all");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+}
@@ -0,0 +1,1837 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::doc::*;
+use crate::err::*;
+use crate::fnt::*;
+use crate::frm::*;
+use crate::inch::*;
+use crate::mrg::*;
+use crate::num::*;
+use crate::pag::*;
+use crate::pt::*;
+use crate::sze::*;
+use crate::tab::*;
+use crate::txf::*;
+use crate::unit::*;
+use skia_safe::{
+    textlayout::{
+        FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, PlaceholderAlignment,
+        PlaceholderStyle, TextBaseline, TextStyle, TypefaceFontProvider,
+    },
+    FontMgr, FontStyle, Image, Paint,
+};
+
+/// Height of the page body on which `elms` are measured.
+const MSR_HGT: In = In(1.0e4);
+
+/// An element laid out on a page.
+#[derive(Debug)]
+pub struct Lay {
+    /// Index of the element in `elms`.
+    pub idx: usize,
+    /// Distance from the left of the page to the element in points.
+    pub x: f32,
+    /// Distance from the top of the page to the element in points.
+    pub y: f32,
+    /// Width of the element in points.
+    pub wid: f32,
+    /// Height of the element in points.
+    pub hgt: f32,
+    /// Distance from the top of the content to the top of the element in points.
+    ///
+    /// Non-zero for the lines of a paragraph continuing from a previous frame.
+    pub ofs: f32,
+    /// Laid out content of the element.
+    pub cnt: LayCnt,
+}
+
+impl Lay {
+    /// Returns `true` if the element is a piece of a paragraph split across frames.
+    pub fn is_spl(&self) -> bool {
+        match &self.cnt {
+            LayCnt::Par(paragraph) | LayCnt::Itm(_, paragraph, _) => {
+                self.ofs > 0.0 || self.hgt < paragraph.height()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Metrics of a laid out line of a paragraph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineInfo {
+    /// Index of the page with the line.
+    pub pag: usize,
+    /// Index of the line's paragraph or list in `elms`.
+    pub par_idx: usize,
+    /// Distance from the top of the page to the line's baseline in points.
+    pub baseline: f32,
+    /// Distance from the baseline to the top of the line's glyphs in points.
+    pub ascent: f32,
+    /// Distance from the baseline to the bottom of the line's glyphs in points.
+    pub descent: f32,
+    /// Width of the line's text in points.
+    pub wid: f32,
+}
+
+/// Content of a page extending below its bottom margin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Overflow {
+    /// Index of the page.
+    pub pag: usize,
+    /// Length of the content below the bottom margin in points.
+    pub pts: f32,
+    /// Index in `elms` of the first element crossing the bottom margin.
+    pub par_idx: usize,
+}
+
+/// Laid out content of an element.
+#[derive(Debug)]
+pub enum LayCnt {
+    /// A paragraph laid out to the text width.
+    Par(Paragraph),
+    /// An _SVG_ graphic.
+    Svg(Svg),
+    /// A raster _image_ decoded once when laid out.
+    Img(Image),
+    /// A horizontal _rule_ drawn across the element's width.
+    Rule(Rule),
+    /// A _QR code_ encoding text drawn across the element's width.
+    Qr(String),
+    /// A list item's marker, paragraph, and the item it was built from.
+    ///
+    /// The paragraph is drawn right of the marker's layout width.
+    Itm(Paragraph, Paragraph, Par),
+    /// A footnote laid out to the text width at the bottom of the page,
+    /// and the footnote it was built from.
+    Ftn(Paragraph, Par),
+    /// A table row's cells with their column widths in points and the cell
+    /// each was built from, and the rule drawn around each cell.
+    Row(Vec<(f32, Paragraph, Par)>, Option<Rule>),
+}
+
+impl Doc {
+    /// Lays out `elms` into pages.
+    ///
+    /// Elements flow through `frms` in order, or through the margin
+    /// columns of each page when the document has no frames. An element
+    /// continues in the next frame when it overflows the current one,
+    /// and a page break continues in the first frame of a following page.
+    ///
+    /// A paragraph splits at a line boundary when it overflows,
+    /// unless it is kept together. A split leaves at least
+    /// `orphan_lines` before and `widow_lines` after the boundary.
+    ///
+    /// An element taller than an empty frame is placed rather than dropped.
+    ///
+    /// Margin frames exclude the space taken by the header and footer.
+    ///
+    /// Footnotes are drawn above the bottom margin, below a separator,
+    /// of the page on which the paragraph marking them begins.
+    /// They take space from the body of the page.
+    ///
+    /// Errors are wrapped in [`DocError::RenderError`] naming
+    /// the page index and the failing element's index.
+    pub fn lay_pags(
+        &self,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Vec<Vec<Lay>>, DocError> {
+        Ok(self.lay_secs(fnts, font_mgr)?.0)
+    }
+
+    /// Lays out `elms` into pages with the size and margins of each page.
+    ///
+    /// A section break continues on a following page with the
+    /// section's size and margins, or on the current page when it
+    /// has no elements. Pages use `sze` and `mrg` until the first
+    /// section break overrides them.
+    pub fn lay_secs(
+        &self,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(Vec<Vec<Lay>>, Vec<(Sze, Mrg)>), DocError> {
+        let (hdr_hgt, ftr_hgt) = self.hdr_ftr_hgt(fnts, font_mgr)?;
+        let mut flw = Flow::new(self, hdr_hgt, ftr_hgt);
+
+        for (elm_idx, elm) in self.elms.iter().enumerate() {
+            match elm {
+                Elm::Par(par) => {
+                    let nxt = match par.keep_with_next {
+                        Some(true) => self
+                            .fst_lne(elm_idx + 1, flw.frm.wid.pt(), fnts, font_mgr)
+                            .map_err(|err| DocError::render(flw.frm.pag, elm_idx + 1, err))?,
+                        _ => None,
+                    };
+                    self.lay_par(&mut flw, elm_idx, par, None, nxt, fnts, font_mgr)?
+                }
+                Elm::List(lst) => {
+                    for (itm_idx, itm) in lst.itms.iter().enumerate() {
+                        let mrk = (lst.kind.mrk(itm_idx), lst.mrk_ind.pt());
+                        self.lay_par(&mut flw, elm_idx, itm, Some(mrk), None, fnts, font_mgr)?;
+                    }
+                }
+                Elm::Svg(svg) => {
+                    let hgt = svg.hgt.pt();
+                    flw.blk(hgt);
+                    let x = flw.frm.x.pt();
+                    flw.push(Lay {
+                        idx: elm_idx,
+                        x,
+                        y: flw.y,
+                        wid: svg.wid.pt(),
+                        hgt,
+                        ofs: 0.0,
+                        cnt: LayCnt::Svg(svg.clone()),
+                    });
+                }
+                Elm::Img(img) => {
+                    let hgt = img.hgt.pt();
+                    flw.blk(hgt);
+                    let image = img
+                        .decode()
+                        .map_err(|err| DocError::render(flw.frm.pag, elm_idx, err))?;
+                    let x = img.x(flw.frm.x.pt(), flw.frm.wid.pt());
+                    flw.push(Lay {
+                        idx: elm_idx,
+                        x,
+                        y: flw.y,
+                        wid: img.wid.pt(),
+                        hgt,
+                        ofs: 0.0,
+                        cnt: LayCnt::Img(image),
+                    });
+                }
+                Elm::Rule(rule) => {
+                    let hgt = rule.hgt();
+                    flw.blk(hgt);
+                    let (x, wid) = (flw.frm.x.pt(), flw.frm.wid.pt());
+                    flw.push(Lay {
+                        idx: elm_idx,
+                        x,
+                        y: flw.y,
+                        wid,
+                        hgt,
+                        ofs: 0.0,
+                        cnt: LayCnt::Rule(*rule),
+                    });
+                }
+                Elm::QrCode { data, size } => {
+                    let hgt = size.pt();
+                    flw.blk(hgt);
+                    let x = flw.frm.x.pt();
+                    flw.push(Lay {
+                        idx: elm_idx,
+                        x,
+                        y: flw.y,
+                        wid: hgt,
+                        hgt,
+                        ofs: 0.0,
+                        cnt: LayCnt::Qr(data.clone()),
+                    });
+                }
+                Elm::Table {
+                    rows,
+                    col_widths,
+                    border,
+                    header_rows,
+                    col_fmts,
+                } => self.lay_tbl(
+                    &mut flw,
+                    elm_idx,
+                    rows,
+                    col_widths,
+                    col_fmts,
+                    *border,
+                    *header_rows,
+                    fnts,
+                    font_mgr,
+                )?,
+                Elm::PagBrk => {
+                    if !flw.is_emp {
+                        flw.nxt_pag();
+                    }
+                }
+                Elm::ColBrk => {
+                    if !flw.is_emp {
+                        flw.nxt_frm();
+                    }
+                }
+                Elm::SecBrk { sze, mrg } => flw.sec_brk(*sze, *mrg),
+            }
+        }
+        flw.end_pag(flw.frm.pag);
+
+        Ok((flw.pags, flw.geos))
+    }
+
+    /// Lays out a paragraph into the flow.
+    ///
+    /// A list item paragraph has a marker text and width in points.
+    /// The marker is laid out in a column left of the item's lines,
+    /// and the item's first line is not indented.
+    ///
+    /// A paragraph kept with the next element has the next element's
+    /// first line height and spacing before in points.
+    #[allow(clippy::too_many_arguments)]
+    fn lay_par(
+        &self,
+        flw: &mut Flow,
+        elm_idx: usize,
+        par: &Par,
+        mrk: Option<(String, f32)>,
+        nxt: Option<(f32, f32)>,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(), DocError> {
+        if !par.is_on_pag(flw.frm.pag) {
+            flw.pag_mut();
+            flw.is_emp = false;
+            return Ok(());
+        }
+
+        let ctx = |pag| move |err| DocError::render(pag, elm_idx, err);
+        let itm: Cow<Par> = match mrk {
+            Some(_) => Cow::Owned(Par {
+                has_ind: Some(false),
+                drop_cap: None,
+                ..par.clone()
+            }),
+            None => Cow::Borrowed(par),
+        };
+        let mrk_wid = mrk.as_ref().map_or(0.0, |(_, wid)| *wid);
+        let mut paragraph = self
+            .bld_par_at(
+                &itm,
+                par.txt_wid(flw.frm.wid.pt()) - mrk_wid,
+                fnts,
+                font_mgr,
+            )
+            .map_err(ctx(flw.frm.pag))?;
+
+        // Determine space between the previous paragraph and this one.
+        // Padding insets the lines from the top and bottom.
+        let pad = par.pad();
+        let lne_hgt = paragraph.get_line_metrics_at(0).unwrap().height as f32 + 2.0 * pad;
+        let bfr = self.spc_bfr(par);
+        let mut hgt = paragraph.height() + 2.0 * pad;
+
+        // Space before is skipped at the top of a frame.
+        let mut gap = if flw.is_emp {
+            0.0
+        } else {
+            spc_gap(flw.spc_aft, bfr, self.collapse_spacing)
+        };
+
+        // Determine space after paragraph.
+        let aft = self.spc_aft(par);
+
+        // Determine the space taken by footnotes.
+        let bdy_wid = (flw.sze.width - flw.mrg.width()).pt();
+        let mut ftns = self
+            .bld_ftns(par, bdy_wid, fnts, font_mgr)
+            .map_err(ctx(flw.frm.pag))?;
+        let ftn_hgt: f32 = ftns.iter().map(|(ftn, _)| ftn.height()).sum();
+
+        // Continue in the next frame when the paragraph overflows
+        // and is kept together, or its first line overflows.
+        // A paragraph kept with the next element also continues when
+        // it fits but the next element's first line overflows.
+        let keep = par.keep_together.unwrap_or(false);
+        let is_ovr = |flw: &Flow, hgt: f32| flw.y + gap + hgt + flw.ftn_ext(ftn_hgt) > flw.btm();
+        let is_brk = is_ovr(flw, if keep { hgt } else { lne_hgt });
+        let is_orp = nxt.is_some_and(|(nxt_hgt, nxt_bfr)| {
+            let nxt_gap = spc_gap(aft, nxt_bfr, self.collapse_spacing);
+            !is_ovr(flw, hgt) && is_ovr(flw, hgt + nxt_gap + nxt_hgt)
+        });
+        if !flw.is_emp && (is_brk || is_orp) {
+            flw.nxt_frm();
+            gap = 0.0;
+            if !par.is_on_pag(flw.frm.pag) {
+                flw.pag_mut();
+                flw.is_emp = false;
+                return Ok(());
+            }
+            paragraph = self
+                .bld_par_at(
+                    &itm,
+                    par.txt_wid(flw.frm.wid.pt()) - mrk_wid,
+                    fnts,
+                    font_mgr,
+                )
+                .map_err(ctx(flw.frm.pag))?;
+            hgt = paragraph.height() + 2.0 * pad;
+        }
+        flw.y += gap;
+
+        // Split the paragraph at line boundaries across frames.
+        // Lines continuing in a following frame keep their layout width.
+        let hgts = lne_hgts(&paragraph);
+        let lay_wid = par.txt_wid(flw.frm.wid.pt()) - mrk_wid;
+        let wid = par.txt_wid(flw.frm.wid.pt());
+        let ind_lft = par.ind_lft.unwrap_or_default().pt();
+        let mut cnt = self
+            .with_mrk(paragraph, &itm, &mrk, fnts, font_mgr)
+            .map_err(ctx(flw.frm.pag))?;
+        let (wdw, orp) = (self.widow_lines as usize, self.orphan_lines as usize);
+        let mut beg = 0;
+        loop {
+            // Place the lines fitting the frame, leaving the widow and orphan lines.
+            // Footnotes go on the page of the first lines.
+            let ext = if beg == 0 { flw.ftn_ext(ftn_hgt) } else { 0.0 };
+            let avl = flw.btm() - flw.y - ext - 2.0 * pad;
+            let rst = &hgts[beg..];
+            let mut fit = if keep {
+                rst.len()
+            } else {
+                fit_lnes(rst, avl, wdw, orp)
+            };
+            if fit == 0 {
+                // Continue in the next frame, or place at least one line in an empty frame.
+                if !flw.is_emp {
+                    flw.nxt_frm();
+                    continue;
+                }
+                fit = fit_lnes(rst, avl, 0, 0).max(1);
+            }
+            let end = beg + fit;
+            let ofs: f32 = hgts[..beg].iter().sum();
+            let cut = if end == hgts.len() {
+                hgt - 2.0 * pad
+            } else {
+                ofs + hgts[beg..end].iter().sum::<f32>()
+            };
+            flw.y += pad;
+            flw.push(Lay {
+                idx: elm_idx,
+                x: flw.frm.x.pt() + ind_lft + pad,
+                y: flw.y,
+                wid,
+                hgt: cut - ofs,
+                ofs,
+                cnt,
+            });
+            flw.y += pad;
+            if beg == 0 {
+                flw.add_ftns(elm_idx, std::mem::take(&mut ftns));
+            }
+            if end == hgts.len() {
+                break;
+            }
+
+            // Continue the remaining lines at the top of the next frame.
+            flw.nxt_frm();
+            beg = end;
+            let paragraph = self
+                .bld_par_at(&itm, lay_wid, fnts, font_mgr)
+                .map_err(ctx(flw.frm.pag))?;
+            cnt = self
+                .with_mrk(paragraph, &itm, &mrk, fnts, font_mgr)
+                .map_err(ctx(flw.frm.pag))?;
+        }
+
+        flw.spc_aft = aft;
+
+        Ok(())
+    }
+
+    /// Lays out the rows of a table into the flow.
+    ///
+    /// A row continues in the next frame when it overflows.
+    /// The first `hdr_cnt` rows are kept with the first body row
+    /// and repeated at the top of each following frame.
+    #[allow(clippy::too_many_arguments)]
+    fn lay_tbl(
+        &self,
+        flw: &mut Flow,
+        elm_idx: usize,
+        rows: &[Vec<Par>],
+        col_widths: &[In],
+        col_fmts: &[Option<NumberFormat>],
+        border: Option<Rule>,
+        hdr_cnt: usize,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(), DocError> {
+        let ctx = |pag| move |err| DocError::render(pag, elm_idx, err);
+        let wid: f32 = col_widths.iter().map(|wid| wid.pt()).sum();
+        let row_lay = |flw: &Flow, hgt: f32, cels| Lay {
+            idx: elm_idx,
+            x: flw.frm.x.pt(),
+            y: flw.y,
+            wid,
+            hgt,
+            ofs: 0.0,
+            cnt: LayCnt::Row(cels, border),
+        };
+
+        // Determine the height of the header rows and the first body row.
+        let hdrs = &rows[..hdr_cnt.min(rows.len())];
+        let mut hdr_hgt = 0.0;
+        for row in rows.iter().take(hdrs.len() + 1) {
+            hdr_hgt += self
+                .bld_row(row, col_widths, col_fmts, fnts, font_mgr)
+                .map_err(ctx(flw.frm.pag))?
+                .0;
+        }
+
+        for (row_idx, row) in rows.iter().enumerate() {
+            let (hgt, cels) = self
+                .bld_row(row, col_widths, col_fmts, fnts, font_mgr)
+                .map_err(ctx(flw.frm.pag))?;
+            if row_idx == 0 && !hdrs.is_empty() {
+                flw.blk(hdr_hgt);
+            } else if row_idx >= hdrs.len() {
+                let frm_idx = flw.frm_idx;
+                flw.blk(hgt);
+                if flw.frm_idx != frm_idx {
+                    // Repeat the header rows above the continuing rows.
+                    for hdr in hdrs {
+                        let (hgt, cels) = self
+                            .bld_row(hdr, col_widths, col_fmts, fnts, font_mgr)
+                            .map_err(ctx(flw.frm.pag))?;
+                        flw.push(row_lay(flw, hgt, cels));
+                    }
+                }
+            }
+            flw.push(row_lay(flw, hgt, cels));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the height of the first line of an element
+    /// and its spacing before in points.
+    ///
+    /// Returns `None` for a page break or past the last element.
+    fn fst_lne(
+        &self,
+        elm_idx: usize,
+        wid: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Option<(f32, f32)>, DocError> {
+        let par = match self.elms.get(elm_idx) {
+            Some(Elm::Par(par)) => par,
+            Some(Elm::List(lst)) => match lst.itms.first() {
+                Some(itm) => itm,
+                None => return Ok(None),
+            },
+            Some(Elm::Svg(svg)) => return Ok(Some((svg.hgt.pt(), 0.0))),
+            Some(Elm::Img(img)) => return Ok(Some((img.hgt.pt(), 0.0))),
+            Some(Elm::Rule(rule)) => return Ok(Some((rule.hgt(), 0.0))),
+            Some(Elm::QrCode { size, .. }) => return Ok(Some((size.pt(), 0.0))),
+            Some(Elm::Table {
+                rows,
+                col_widths,
+                header_rows,
+                col_fmts,
+                ..
+            }) => {
+                // Header rows are kept with the first body row.
+                let mut hgt = 0.0;
+                for row in rows.iter().take(header_rows + 1) {
+                    hgt += self.bld_row(row, col_widths, col_fmts, fnts, font_mgr)?.0;
+                }
+                return Ok((!rows.is_empty()).then_some((hgt, 0.0)));
+            }
+            Some(Elm::PagBrk) | Some(Elm::ColBrk) | Some(Elm::SecBrk { .. }) | None => {
+                return Ok(None)
+            }
+        };
+        let paragraph = self.bld_par_at(par, par.txt_wid(wid), fnts, font_mgr)?;
+        let lne_hgt = paragraph.get_line_metrics_at(0).unwrap().height as f32;
+        Ok(Some((lne_hgt, self.spc_bfr(par))))
+    }
+
+    /// Returns the laid out content of a paragraph,
+    /// or of a list item `par` with a marker text and width in points.
+    fn with_mrk(
+        &self,
+        paragraph: Paragraph,
+        par: &Par,
+        mrk: &Option<(String, f32)>,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<LayCnt, DocError> {
+        match mrk {
+            Some((txt, wid)) => {
+                // Lay out the marker in the item's font without indentation.
+                let mrk_par = Par {
+                    ind_lft: None,
+                    ind_rht: None,
+                    has_ind: Some(false),
+                    aln: Some(Align::Left),
+                    drop_cap: None,
+                    txt: txt.clone(),
+                    runs: vec![],
+                    ..par.clone()
+                };
+                let mut mrk = self.bld_par(&mrk_par, fnts, font_mgr)?;
+                mrk.layout(*wid);
+                Ok(LayCnt::Itm(mrk, paragraph, par.clone()))
+            }
+            None => Ok(LayCnt::Par(paragraph)),
+        }
+    }
+
+    /// Returns the space taken from the top and bottom
+    /// of the text area by the header and footer in points.
+    ///
+    /// The header takes its height and spacing after.
+    /// The footer takes its height and spacing before.
+    pub fn hdr_ftr_hgt(
+        &self,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(f32, f32), DocError> {
+        let mut top: f32 = 0.0;
+        if let Some(hdr) = &self.hdr {
+            let paragraph = self.bld_par(hdr, fnts, font_mgr)?;
+            top = paragraph.height() + self.spc_aft(hdr);
+        }
+
+        let mut btm: f32 = 0.0;
+        if let Some(ftr) = &self.ftr {
+            let paragraph = self.bld_par(ftr, fnts, font_mgr)?;
+            btm = paragraph.height() + self.spc_bfr(ftr);
+        }
+
+        Ok((top, btm))
+    }
+
+    /// Returns the nominal line height of a paragraph in points,
+    /// its font size scaled by its line spacing.
+    ///
+    /// Unlike a laid out line, the height ignores runs with larger fonts.
+    pub fn base_lne_hgt(&self, par: &Par) -> f32 {
+        let fnt_sze = par.fnt_sze.unwrap_or(self.fnt_sze);
+        let spc_lne = par.spc_lne.unwrap_or(self.spc_lne);
+        fnt_sze * self.spc_mdl.lne_hgt(spc_lne, fnt_sze)
+    }
+
+    /// Returns the space _before_ a paragraph in points.
+    ///
+    /// See [`SpacingModel::par_spc`] for the formula.
+    pub fn spc_bfr(&self, par: &Par) -> f32 {
+        let spc_bfr = par.spc_bfr.unwrap_or(self.spc_par_bfr);
+        self.spc_mdl.par_spc(spc_bfr, self.base_lne_hgt(par))
+    }
+
+    /// Returns the space _after_ a paragraph in points.
+    ///
+    /// See [`SpacingModel::par_spc`] for the formula.
+    pub fn spc_aft(&self, par: &Par) -> f32 {
+        let spc_aft = par.spc_aft.unwrap_or(self.spc_par_aft);
+        self.spc_mdl.par_spc(spc_aft, self.base_lne_hgt(par))
+    }
+
+    /// Builds a paragraph laid out to the text width.
+    pub fn bld_par(
+        &self,
+        par: &Par,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Paragraph, DocError> {
+        let par_wid = self.sze.width - self.mrg.width();
+        self.bld_par_at(par, par.txt_wid(par_wid.pt()), fnts, font_mgr)
+    }
+
+    /// Builds a paragraph laid out to a width in points.
+    ///
+    /// The first lines of a paragraph with a drop cap
+    /// flow to the right of the drop cap at the width,
+    /// and lines after the first of a paragraph with
+    /// a hanging indentation are indented.
+    ///
+    /// The last line of a fully justified paragraph spans the width.
+    pub fn bld_par_at(
+        &self,
+        par: &Par,
+        wid: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Paragraph, DocError> {
+        // Transform the case of the text and
+        // mark hyphenation points with soft hyphens.
+        let tf = par.tf_par();
+        let par = &*self.hyp_par(&tf);
+
+        let paragraph = self.bld_wrp(par, wid, fnts, font_mgr)?;
+        match self.jst_lst(par, &paragraph, wid) {
+            Some(jst) => self.bld_wrp(&jst, wid, fnts, font_mgr),
+            None => Ok(paragraph),
+        }
+    }
+
+    /// Builds a transformed and hyphenated paragraph
+    /// laid out to a width in points.
+    fn bld_wrp(
+        &self,
+        par: &Par,
+        wid: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Paragraph, DocError> {
+        match (self.cap_par(par), self.hng_ind(par)) {
+            (Some(cap), _) => self.bld_cap_bdy(par, &cap, wid, fnts, font_mgr),
+            (None, Some(hng)) => self.bld_hng(par, hng, wid, fnts, font_mgr),
+            (None, None) => {
+                let mut paragraph = self.bld_txt(par, &[], 0.0, fnts, font_mgr)?;
+                paragraph.layout(wid);
+                Ok(paragraph)
+            }
+        }
+    }
+
+    /// Builds the text of a paragraph with placeholders
+    /// `brk_wid` points wide before byte offsets `brks` of the text.
+    ///
+    /// Without placeholders, the first line is indented.
+    /// The paragraph is not laid out.
+    pub(crate) fn bld_txt(
+        &self,
+        par: &Par,
+        brks: &[usize],
+        brk_wid: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<Paragraph, DocError> {
+        // Determine paragraph font collection.
+        let fnt = par.fnt.as_ref().unwrap_or(&self.fnt);
+        let mut run_fnts: Vec<&FontSource> = vec![];
+        for run_fnt in par.runs.iter().filter_map(|run| run.fnt.as_ref()) {
+            if run_fnt != fnt && !run_fnts.contains(&run_fnt) {
+                run_fnts.push(run_fnt);
+            }
+        }
+        let cur_fnt_col = fnt_col(fnt, &run_fnts, self.fnt_timeout, fnts, font_mgr)?;
+
+        // Determine paragraph text style.
+        let fnt_sze = par.fnt_sze.unwrap_or(self.fnt_sze);
+        let mut cur_ts = TextStyle::new();
+        cur_ts.set_font_families(&[fnt.name()]);
+        cur_ts.set_font_size(fnt_sze);
+        cur_ts.set_locale(&self.lang);
+        let spc_lne = par.spc_lne.unwrap_or(self.spc_lne);
+        cur_ts.set_height(self.spc_mdl.lne_hgt(spc_lne, fnt_sze));
+        cur_ts.set_height_override(true);
+        match par.stroke {
+            Some(stroke) => cur_ts.set_foreground_paint(&stroke.paint()),
+            None => {
+                let mut paint = Paint::default();
+                paint.set_color(par.clr.unwrap_or(self.clr));
+                cur_ts.set_foreground_paint(&paint)
+            }
+        };
+        par.fnt_sty.unwrap_or(self.fnt_sty).set(&mut cur_ts);
+        if let (Some(bg_clr), None | Some(Highlight::Lines)) = (par.bg_clr, par.highlight) {
+            let mut paint = Paint::default();
+            paint.set_color(bg_clr);
+            cur_ts.set_background_paint(&paint);
+        }
+
+        // Set small capitals with the font's feature, or synthesize them.
+        let mut is_sc = false;
+        if par.text_transform == Some(TextTransform::SmallCaps) {
+            let typefaces = (*cur_fnt_col)
+                .clone()
+                .find_typefaces(&[fnt.name()], FontStyle::normal());
+            match typefaces.first() {
+                Some(typeface) if has_ftr(typeface, "smcp") => cur_ts.add_font_feature("smcp", 1),
+                _ => is_sc = true,
+            }
+        }
+
+        for (tag, val) in &par.features {
+            cur_ts.add_font_feature(tag, *val as i32);
+        }
+
+        // Determine paragraph style.
+        let mut cur_par_sty = ParagraphStyle::new();
+        par.aln.unwrap_or(self.aln).set(&mut cur_par_sty);
+        par.direction
+            .unwrap_or(self.direction)
+            .set(&mut cur_par_sty);
+
+        // Build paragraph.
+        let mut par_bld = ParagraphBuilder::new(&cur_par_sty, &*cur_fnt_col);
+        par_bld.push_style(&cur_ts);
+
+        // Determine paragraph first line indentation.
+        let mut ind_wid = 0.0;
+        if brks.is_empty() && par.has_ind.unwrap_or(self.has_ind) {
+            ind_wid = par.ind.unwrap_or(self.ind).pt(fnt_sze);
+            par_bld.add_placeholder(&plc(ind_wid));
+        }
+
+        // Add paragraph text.
+        let pces: Vec<(&str, TextStyle)> = if par.runs.is_empty() {
+            vec![(par.txt.as_str(), cur_ts.clone())]
+        } else {
+            par.runs
+                .iter()
+                .map(|run| (run.txt.as_str(), run.ts(&cur_ts)))
+                .collect()
+        };
+        if !par.tabs.is_empty() {
+            add_tabbed_txt(
+                &mut par_bld,
+                &cur_par_sty,
+                &cur_fnt_col,
+                &par.tabs,
+                &pces,
+                ind_wid,
+            );
+        } else {
+            let mut brks = brks.iter().peekable();
+            let mut pos = 0;
+            for (txt, ts) in &pces {
+                par_bld.push_style(ts);
+                let end = pos + txt.len();
+                let mut beg = 0;
+                let add_txt = |par_bld: &mut ParagraphBuilder, txt: &str| {
+                    if is_sc {
+                        add_sc_txt(par_bld, txt, ts);
+                    } else {
+                        par_bld.add_text(txt);
+                    }
+                };
+                while let Some(brk) = brks.next_if(|brk| **brk <= end) {
+                    add_txt(&mut par_bld, &txt[beg..brk - pos]);
+                    par_bld.add_placeholder(&plc(brk_wid));
+                    beg = brk - pos;
+                }
+                add_txt(&mut par_bld, &txt[beg..]);
+                par_bld.pop();
+                pos = end;
+            }
+        }
+
+        Ok(par_bld.build())
+    }
+
+    /// Returns the document prepared for layout as when rendering:
+    /// with its fields filled in, and a cache of its loaded fonts
+    /// falling back where a font fails to load.
+    pub(crate) fn prep_lay(&self) -> Result<(Cow<'_, Doc>, FontCache), DocError> {
+        let doc = self.fill_fields();
+        let mut cache = FontCache::new();
+        doc.load_fnts(&mut cache.fnts, &cache.font_mgr)?;
+        Ok((doc, cache))
+    }
+
+    /// Returns where an element lands as a page index
+    /// and a distance from the top of the page.
+    ///
+    /// ### Arguments
+    ///
+    /// * `par_index` - Index of the paragraph in `elms`.
+    pub fn locate_par(&self, par_index: usize) -> Result<(usize, In), DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+
+        let pags = doc.lay_pags(&mut cache.fnts, &cache.font_mgr)?;
+        for (pag_idx, lays) in pags.iter().enumerate() {
+            if let Some(lay) = lays.iter().find(|lay| lay.idx == par_index) {
+                return Ok((pag_idx, In(lay.y / PT_PER_IN)));
+            }
+        }
+
+        Err(DocError::ElementIndexError(par_index))
+    }
+
+    /// Returns the number of pages the document renders,
+    /// including pages added where elements overflow.
+    pub fn page_count(&self) -> Result<usize, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+
+        Ok(doc.lay_pags(&mut cache.fnts, &cache.font_mgr)?.len())
+    }
+
+    /// Returns the index of the rendered page on which
+    /// each page of [`Doc::pages`] begins.
+    ///
+    /// Pages added where elements overflow are counted.
+    pub fn page_indices(&self) -> Result<Vec<usize>, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+
+        // Find the first element index laid out on each rendered page.
+        let fsts: Vec<usize> = doc
+            .lay_pags(&mut cache.fnts, &cache.font_mgr)?
+            .iter()
+            .map(|lays| lays.iter().map(|lay| lay.idx).min().unwrap_or(usize::MAX))
+            .collect();
+
+        // A page begins on the last rendered page starting at or before it.
+        Ok(self
+            .pages()
+            .map(|pag| fsts.iter().rposition(|fst| *fst <= pag.idx).unwrap_or(0))
+            .collect())
+    }
+
+    /// Returns the metrics of each laid out line of body paragraphs
+    /// and list items, in page order.
+    ///
+    /// Lines are laid out as when rendering, without painting.
+    /// A paragraph split across frames reports each line once,
+    /// on the page where it is drawn.
+    pub fn line_metrics(&self) -> Result<Vec<LineInfo>, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+
+        let mut ret = vec![];
+        let pags = doc.lay_pags(&mut cache.fnts, &cache.font_mgr)?;
+        for (pag, lays) in pags.iter().enumerate() {
+            for lay in lays {
+                let (LayCnt::Par(paragraph) | LayCnt::Itm(_, paragraph, _)) = &lay.cnt else {
+                    continue;
+                };
+                // Keep the lines within the piece drawn in this frame.
+                let mut top = 0.0;
+                for (lm, hgt) in paragraph.get_line_metrics().iter().zip(lne_hgts(paragraph)) {
+                    if top >= lay.ofs - 0.5 && top < lay.ofs + lay.hgt - 0.5 {
+                        ret.push(LineInfo {
+                            pag,
+                            par_idx: lay.idx,
+                            baseline: lay.y - lay.ofs + lm.baseline as f32,
+                            ascent: lm.ascent as f32,
+                            descent: lm.descent as f32,
+                            wid: lm.width as f32,
+                        });
+                    }
+                    top += hgt;
+                }
+            }
+        }
+
+        Ok(ret)
+    }
+
+    /// Returns the pages whose content extends below the bottom margin,
+    /// such as an element taller than an empty page.
+    ///
+    /// The bottom of a page's body is above its footer and footnotes.
+    /// An empty report means every element fits on its page.
+    pub fn overflow_report(&self) -> Result<Vec<Overflow>, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+
+        let (_, ftr_hgt) = doc.hdr_ftr_hgt(&mut cache.fnts, &cache.font_mgr)?;
+        let (pags, geos) = doc.lay_secs(&mut cache.fnts, &cache.font_mgr)?;
+        let mut ret = vec![];
+        for (pag, (lays, (sze, mrg))) in pags.iter().zip(geos).enumerate() {
+            // Footnotes follow the body, after their separator.
+            let (bdy, btm) = match lays
+                .iter()
+                .position(|lay| matches!(lay.cnt, LayCnt::Ftn(..)))
+            {
+                Some(ftn_idx) => (&lays[..ftn_idx - 1], lays[ftn_idx - 1].y),
+                None => (&lays[..], (sze.height - mrg.btm).pt() - ftr_hgt),
+            };
+            let Some(fst) = bdy.iter().find(|lay| lay.y + lay.hgt > btm + 0.5) else {
+                continue;
+            };
+            let end = bdy.iter().map(|lay| lay.y + lay.hgt).fold(btm, f32::max);
+            ret.push(Overflow {
+                pag,
+                pts: end - btm,
+                par_idx: fst.idx,
+            });
+        }
+
+        Ok(ret)
+    }
+
+    /// Returns the height of a paragraph in points
+    /// laid out to the width of the first frame.
+    ///
+    /// Fields of the paragraph are filled in as when rendering.
+    ///
+    /// ### Arguments
+    ///
+    /// * `par` - The paragraph to measure.
+    pub fn measure_par(&self, par: &Par) -> Result<f32, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+        let mut par = par.clone();
+        par.render_template(&doc.fld_vals());
+
+        let paragraph = doc.bld_par_at(
+            &par,
+            par.txt_wid(doc.frm_at(0).wid.pt()),
+            &mut cache.fnts,
+            &cache.font_mgr,
+        )?;
+        Ok(paragraph.height())
+    }
+
+    /// Returns the height of `elms` in points laid out
+    /// in one unbroken column between the margins.
+    ///
+    /// Heights include spacing between elements and
+    /// exclude page breaks, the header, and the footer.
+    pub fn measure_height(&self) -> Result<f32, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+
+        // Lay out elements on a page tall enough to hold them.
+        let mut doc = Doc {
+            hdr: None,
+            ftr: None,
+            columns: 1,
+            frms: vec![],
+            ..doc.into_owned()
+        };
+        doc.sze.height = MSR_HGT + doc.mrg.height();
+        doc.elms
+            .retain(|elm| !matches!(elm, Elm::PagBrk | Elm::ColBrk | Elm::SecBrk { .. }));
+
+        // Sum the extent of elements on each page.
+        let mut ret = 0.0;
+        for lays in doc.lay_pags(&mut cache.fnts, &cache.font_mgr)? {
+            let top = lays.iter().map(|lay| lay.y).fold(f32::MAX, f32::min);
+            let btm = lays.iter().map(|lay| lay.y + lay.hgt).fold(0.0, f32::max);
+            ret += (btm - top).max(0.0);
+        }
+
+        Ok(ret)
+    }
+}
+
+/// Returns the height of each line of a paragraph in points.
+///
+/// The last line takes the remainder of the paragraph height.
+fn lne_hgts(paragraph: &Paragraph) -> Vec<f32> {
+    let mut ret: Vec<f32> = paragraph
+        .get_line_metrics()
+        .iter()
+        .map(|lm| lm.height as f32)
+        .collect();
+    let prv: f32 = ret.iter().rev().skip(1).sum();
+    match ret.last_mut() {
+        Some(lst) => *lst = paragraph.height() - prv,
+        None => ret.push(paragraph.height()),
+    }
+    ret
+}
+
+/// Returns the style of an empty placeholder `wid` points wide on the baseline.
+fn plc(wid: f32) -> PlaceholderStyle {
+    PlaceholderStyle {
+        width: wid,
+        height: 0.0,
+        alignment: PlaceholderAlignment::Baseline,
+        baseline_offset: 0.0,
+        baseline: TextBaseline::Alphabetic,
+    }
+}
+
+/// Returns a font collection with a paragraph font and the fonts of its runs.
+///
+/// A paragraph without run fonts borrows the cached collection of its font.
+/// Font sources are cloned only when first cached.
+fn fnt_col<'a>(
+    fnt: &FontSource,
+    run_fnts: &[&FontSource],
+    timeout: Option<Duration>,
+    fnts: &'a mut HashMap<FontSource, FontCollection>,
+    font_mgr: &FontMgr,
+) -> Result<Cow<'a, FontCollection>, DocError> {
+    for src in std::iter::once(fnt).chain(run_fnts.iter().copied()) {
+        if !fnts.contains_key(src) {
+            fnts.insert(src.clone(), create_fnt_col(src, timeout, font_mgr)?);
+        }
+    }
+    if run_fnts.is_empty() {
+        return Ok(Cow::Borrowed(&fnts[fnt]));
+    }
+
+    // Register the typefaces of every font in a single collection.
+    let mut tfp = TypefaceFontProvider::new();
+    for src in std::iter::once(fnt).chain(run_fnts.iter().copied()) {
+        let name = src.name();
+        let src_col = fnts.get_mut(src).unwrap();
+        for typeface in src_col.find_typefaces(&[&name], FontStyle::normal()) {
+            tfp.register_typeface(typeface, Some(name.as_str()));
+        }
+    }
+    let mut ret = FontCollection::new();
+    ret.set_default_font_manager(Some(tfp.into()), None);
+    Ok(Cow::Owned(ret))
+}
+
+/// Elements flowing through frames during a layout pass.
+struct Flow<'a> {
+    doc: &'a Doc,
+    /// Space taken by the header in points.
+    hdr_hgt: f32,
+    /// Space taken by the footer in points.
+    ftr_hgt: f32,
+    /// Position of the current frame in the flow.
+    frm_idx: usize,
+    /// The current frame.
+    frm: Frame,
+    /// Distance from the top of the page to the next element in points.
+    y: f32,
+    /// Spacing after the previous element in points.
+    spc_aft: f32,
+    /// Indicates whether the current frame has no elements.
+    is_emp: bool,
+    /// Size of the pages of the current section.
+    sze: Sze,
+    /// Margins of the pages of the current section.
+    mrg: Mrg,
+    /// Footnotes of the current page with the index of the element marking each.
+    ftns: Vec<(usize, Paragraph, Par)>,
+    /// Laid out elements of each page.
+    pags: Vec<Vec<Lay>>,
+    /// Size and margins of each page.
+    geos: Vec<(Sze, Mrg)>,
+}
+
+impl<'a> Flow<'a> {
+    fn new(doc: &'a Doc, hdr_hgt: f32, ftr_hgt: f32) -> Self {
+        let mut ret = Flow {
+            doc,
+            hdr_hgt,
+            ftr_hgt,
+            frm_idx: 0,
+            frm: Frame::default(),
+            y: 0.0,
+            spc_aft: 0.0,
+            is_emp: true,
+            sze: doc.sze,
+            mrg: doc.mrg,
+            ftns: vec![],
+            pags: vec![],
+            geos: vec![],
+        };
+        ret.frm = ret.frm_at(0);
+        ret.y = ret.frm.y.pt();
+        ret
+    }
+
+    /// Returns the frame at a position in the flow
+    /// with margin frames shrunk to fit the header and footer.
+    fn frm_at(&self, idx: usize) -> Frame {
+        let frm = self.doc.frm_at_in(idx, self.sze, self.mrg);
+        if idx < self.doc.frms.len() {
+            return frm;
+        }
+        Frame {
+            y: frm.y + In::from_pt(Pt(self.hdr_hgt)),
+            hgt: frm.hgt - In::from_pt(Pt(self.hdr_hgt + self.ftr_hgt)),
+            ..frm
+        }
+    }
+
+    /// Continues the flow at the top of the next frame.
+    fn nxt_frm(&mut self) {
+        let pag = self.frm.pag;
+        self.frm_idx += 1;
+        self.frm = self.frm_at(self.frm_idx);
+        if self.frm.pag != pag {
+            self.end_pag(pag);
+        }
+        self.y = self.frm.y.pt();
+        self.spc_aft = 0.0;
+        self.is_emp = true;
+    }
+
+    /// Continues the flow at the first frame of a following page.
+    fn nxt_pag(&mut self) {
+        let pag = self.frm.pag;
+        while self.frm.pag <= pag {
+            self.nxt_frm();
+        }
+    }
+
+    /// Continues the flow with the size and margins of a section.
+    fn sec_brk(&mut self, sze: Option<Sze>, mrg: Option<Mrg>) {
+        self.sze = sze.unwrap_or(self.sze);
+        self.mrg = mrg.unwrap_or(self.mrg);
+        let pag = self.frm.pag;
+        if self.pags.get(pag).is_some_and(|lays| !lays.is_empty()) {
+            self.nxt_pag();
+        } else {
+            // Lay out the empty current page with the section's geometry.
+            self.frm = self.frm_at(self.frm_idx);
+            self.y = self.frm.y.pt();
+            self.spc_aft = 0.0;
+        }
+    }
+
+    /// Returns the laid out elements of the current frame's page,
+    /// adding empty pages up to the page index as needed.
+    fn pag_mut(&mut self) -> &mut Vec<Lay> {
+        let pag = self.frm.pag;
+        if self.pags.len() <= pag {
+            self.pags.resize_with(pag + 1, Vec::new);
+            self.geos.resize(pag + 1, (self.sze, self.mrg));
+        }
+        &mut self.pags[pag]
+    }
+
+    /// Prepares the flow for a block `hgt` points tall
+    /// preceded by the previous element's spacing after.
+    fn blk(&mut self, hgt: f32) {
+        // Continue in the next frame when the block overflows.
+        if !self.is_emp && self.y + self.spc_aft + hgt > self.btm() {
+            self.nxt_frm();
+        }
+        self.y += self.spc_aft;
+        self.spc_aft = 0.0;
+    }
+
+    /// Adds a laid out element and advances past it.
+    fn push(&mut self, lay: Lay) {
+        self.y += lay.hgt;
+        self.is_emp = false;
+        self.pag_mut().push(lay);
+    }
+
+    /// Returns the distance from the top of the page to the bottom
+    /// of the current frame above the page's footnotes in points.
+    fn btm(&self) -> f32 {
+        self.frm.btm() - self.ftn_hgt()
+    }
+
+    /// Returns the space taken by the footnotes of the current page in points.
+    fn ftn_hgt(&self) -> f32 {
+        self.ftn_ext(self.ftns.iter().map(|(_, ftn, _)| ftn.height()).sum())
+    }
+
+    /// Returns the space taken by footnotes `hgt` points tall
+    /// added to the current page in points.
+    ///
+    /// The first footnotes of a page also take the separator.
+    fn ftn_ext(&self, hgt: f32) -> f32 {
+        match (hgt > 0.0, self.ftns.is_empty()) {
+            (false, _) => 0.0,
+            (true, true) => hgt + self.doc.ftn_sep().hgt(),
+            (true, false) => hgt,
+        }
+    }
+
+    /// Adds footnotes marked by an element to the current page.
+    fn add_ftns(&mut self, elm_idx: usize, ftns: Vec<(Paragraph, Par)>) {
+        self.ftns
+            .extend(ftns.into_iter().map(|(ftn, par)| (elm_idx, ftn, par)));
+    }
+
+    /// Finishes a page by aligning its content vertically
+    /// and laying out its footnotes.
+    fn end_pag(&mut self, pag: usize) {
+        self.aln_pag(pag);
+        self.end_ftns(pag);
+    }
+
+    /// Moves the content of a page down by the document's vertical
+    /// alignment of the space left above the footnotes and footer.
+    ///
+    /// Pages flowing through frames are not aligned.
+    fn aln_pag(&mut self, pag: usize) {
+        let frc = self.doc.vert_aln.frc();
+        let Some(&(sze, mrg)) = self.geos.get(pag) else {
+            return;
+        };
+        if frc == 0.0 || !self.doc.frms.is_empty() {
+            return;
+        }
+        let btm = (sze.height - mrg.btm).pt() - self.ftr_hgt - self.ftn_hgt();
+        let Some(lays) = self.pags.get_mut(pag) else {
+            return;
+        };
+        let Some(end) = lays.iter().map(|lay| lay.y + lay.hgt).reduce(f32::max) else {
+            return;
+        };
+        let dy = ((btm - end) * frc).max(0.0);
+        for lay in lays.iter_mut() {
+            lay.y += dy;
+        }
+    }
+
+    /// Lays out the footnotes of a page above its bottom margin
+    /// and the footer, below a separator.
+    fn end_ftns(&mut self, pag: usize) {
+        let ftns = std::mem::take(&mut self.ftns);
+        let (Some(fst), Some(&(sze, mrg))) = (ftns.first(), self.geos.get(pag)) else {
+            return;
+        };
+        let sep = self.doc.ftn_sep();
+        let hgt: f32 = ftns.iter().map(|(_, ftn, _)| ftn.height()).sum();
+        let x = mrg.lft.pt();
+        let wid = (sze.width - mrg.width()).pt();
+        let mut y = (sze.height - mrg.btm).pt() - self.ftr_hgt - hgt - sep.hgt();
+
+        let mut lays = vec![Lay {
+            idx: fst.0,
+            x,
+            y,
+            wid: wid * sep.wid_frc,
+            hgt: sep.hgt(),
+            ofs: 0.0,
+            cnt: LayCnt::Rule(sep.set_wid_frc(1.0)),
+        }];
+        y += sep.hgt();
+        for (idx, ftn, par) in ftns {
+            let hgt = ftn.height();
+            lays.push(Lay {
+                idx,
+                x,
+                y,
+                wid,
+                hgt,
+                ofs: 0.0,
+                cnt: LayCnt::Ftn(ftn, par),
+            });
+            y += hgt;
+        }
+        self.pags[pag].extend(lays);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_par() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("One"));
+        doc.add_par(par("Two"));
+        doc.add_pag_brk();
+        doc.add_par(par("Three"));
+        doc.add_pag_brk();
+        doc.add_par(par("Four"));
+        doc.add_par(par("Five"));
+
+        let (pag_idx, y) = doc.locate_par(6).expect("Failed to locate paragraph");
+        assert_eq!(pag_idx, 2);
+        assert!(y > doc.mrg.top);
+        assert!(y < doc.sze.height - doc.mrg.btm);
+    }
+
+    #[test]
+    fn test_line_metrics() {
+        let mut doc = new_ansi_letter();
+        assert!(doc
+            .line_metrics()
+            .expect("Failed to get line metrics")
+            .is_empty());
+
+        doc.add_par(par(&"A paragraph wrapping across lines. ".repeat(8)));
+        doc.add_pag_brk();
+        doc.add_par(par("Two"));
+        let lnes = doc.line_metrics().expect("Failed to get line metrics");
+        assert!(lnes.len() > 2);
+        let (fst, lst) = (lnes[0], lnes[lnes.len() - 1]);
+        assert_eq!((fst.pag, fst.par_idx), (0, 0));
+        assert_eq!((lst.pag, lst.par_idx), (1, 2));
+        assert!(fst.baseline > doc.mrg.top.pt());
+        assert!(fst.ascent > 0.0 && fst.descent > 0.0);
+        assert!(fst.wid > lst.wid);
+        assert!(lnes
+            .windows(2)
+            .all(|w| w[0].pag < w[1].pag || w[0].baseline < w[1].baseline));
+    }
+
+    #[test]
+    fn test_overflow_report() {
+        let mut doc = new_ansi_letter();
+        for _ in 0..100 {
+            doc.add_par(par("A paragraph repeated until the page overflows."));
+        }
+        assert!(doc
+            .overflow_report()
+            .expect("Failed to report overflow")
+            .is_empty());
+
+        doc.add_qr("https://example.com", In(12.0));
+        let ovrs = doc.overflow_report().expect("Failed to report overflow");
+        assert_eq!(ovrs.len(), 1);
+        assert_eq!(ovrs[0].par_idx, 100);
+        assert!((ovrs[0].pts - In(3.0).pt()).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_page_count() {
+        let mut doc = new_ansi_letter();
+        assert_eq!(doc.page_count().expect("Failed to count pages"), 0);
+
+        doc.add_par(par("One"));
+        doc.add_pag_brk();
+        doc.add_par(par("Two"));
+        assert_eq!(doc.page_count().expect("Failed to count pages"), 2);
+
+        // Overflowing elements continue on added pages.
+        for _ in 0..100 {
+            doc.add_par(par("A paragraph repeated until the page overflows."));
+        }
+        assert!(doc.page_count().expect("Failed to count pages") > 2);
+    }
+
+    #[test]
+    fn test_vert_aln() {
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Title"));
+        doc.add_par(par("Subtitle"));
+        doc.add_pag_brk();
+        doc.add_par(par("Body"));
+        let top = In(1.0).pt();
+        let btm = In(10.0).pt();
+
+        let lay = |doc: &Doc, fnts: &mut HashMap<FontSource, FontCollection>| {
+            doc.lay_pags(fnts, &font_mgr)
+                .expect("Failed to lay out pages")
+        };
+        let pags = lay(&doc, &mut fnts);
+        assert_eq!(pags[0][0].y, top);
+
+        // Content ends at the bottom margin.
+        let doc = doc.set_vert_aln(VertAlign::Bottom);
+        let pags = lay(&doc, &mut fnts);
+        assert!((pags[0][1].y + pags[0][1].hgt - btm).abs() < 0.01);
+        assert!((pags[1][0].y + pags[1][0].hgt - btm).abs() < 0.01);
+
+        // Content is centered between the margins.
+        let doc = doc.set_vert_aln(VertAlign::Center);
+        let pags = lay(&doc, &mut fnts);
+        let abv = pags[0][0].y - top;
+        let blw = btm - (pags[0][1].y + pags[0][1].hgt);
+        assert!((abv - blw).abs() < 0.01);
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_vert_aln");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_rtl() {
+        let mut doc = new_ansi_letter().set_direction(TextDirection::Rtl);
+        let rtl = par("שלום 123 world");
+        let ltr = rtl.clone().set_direction(Some(TextDirection::Ltr));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let rng = |paragraph: &Paragraph| {
+            let tbs = paragraph.get_rects_for_range(
+                1..15,
+                skia_safe::textlayout::RectHeightStyle::Max,
+                skia_safe::textlayout::RectWidthStyle::Tight,
+            );
+            let lft = tbs.iter().map(|tb| tb.rect.left).fold(f32::MAX, f32::min);
+            let rht = tbs.iter().map(|tb| tb.rect.right).fold(0.0, f32::max);
+            (lft, rht)
+        };
+
+        // The indentation and text are flush right.
+        let paragraph = doc
+            .bld_par(&rtl, &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph");
+        let ind = paragraph.get_rects_for_placeholders()[0].rect;
+        assert!((ind.right - In(6.5).pt()).abs() < 1.0);
+        assert!((rng(&paragraph).1 - ind.left).abs() < 1.0);
+
+        // A left to right paragraph is flush left.
+        let paragraph = doc
+            .bld_par(&ltr, &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph");
+        let ind = paragraph.get_rects_for_placeholders()[0].rect;
+        assert!(ind.left.abs() < 1.0);
+        assert!((rng(&paragraph).0 - ind.right).abs() < 1.0);
+
+        doc.add_par(rtl);
+        doc.add_par(ltr);
+        let pth = std::env::temp_dir().join("pdf_doc_test_rtl");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_measure_par() {
+        let doc = new_ansi_letter();
+        let one = doc
+            .measure_par(&par("One line."))
+            .expect("Failed to measure");
+        assert!(one > 0.0);
+
+        let txt = "A paragraph long enough to wrap across several lines of text. ".repeat(10);
+        let many = doc.measure_par(&par(&txt)).expect("Failed to measure");
+        assert!(many > one * 2.0);
+
+        // Narrower columns wrap to more lines.
+        let cols = new_ansi_letter().set_columns(2);
+        assert!(cols.measure_par(&par(&txt)).expect("Failed to measure") > many);
+    }
+
+    #[test]
+    fn test_measure_height() {
+        let mut doc = new_ansi_letter();
+        assert_eq!(doc.measure_height().expect("Failed to measure"), 0.0);
+
+        let rep = par("A paragraph repeated until the page overflows.");
+        for _ in 0..100 {
+            doc.add_par(rep.clone());
+        }
+        let hgt = doc.measure_height().expect("Failed to measure");
+        let par_hgt = doc.measure_par(&rep).expect("Failed to measure");
+        assert!(hgt >= par_hgt * 100.0);
+        assert!(hgt > (doc.sze.height - doc.mrg.height()).pt());
+
+        // Page breaks don't add height.
+        doc.add_pag_brk();
+        assert_eq!(doc.measure_height().expect("Failed to measure"), hgt);
+    }
+
+    #[test]
+    fn test_lay_parity_ovr() {
+        // Nearly fill the first page's 9in text area.
+        let mut doc = new_ansi_letter();
+        doc.add_svg(svg(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#,
+            In(1.0),
+            In(8.75),
+        ));
+        let odd = par("Recto. ".repeat(40))
+            .set_page_parity(Some(Parity::Odd))
+            .set_keep_together(Some(true));
+        doc.add_par(odd);
+        let idx = doc.elms.len() - 1;
+
+        // The kept paragraph breaking onto an even page is skipped.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert!(pags.iter().flatten().all(|lay| lay.idx != idx));
+
+        // A paragraph beginning on an even page is skipped.
+        let mut doc = new_ansi_letter();
+        doc.add_pag_brk();
+        doc.add_par(par("Recto.").set_page_parity(Some(Parity::Odd)));
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert!(pags.iter().flatten().all(|lay| lay.idx != 1));
+    }
+
+    #[test]
+    fn test_page_indices() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("First"));
+        for _ in 0..80 {
+            doc.add_par(par("Line"));
+        }
+        doc.add_pag_brk();
+        doc.add_par(par("After"));
+
+        // Pages added by overflow are counted.
+        let cnt = doc.page_count().expect("Failed to count pages");
+        assert!(cnt > 2);
+        let idxs = doc.page_indices().expect("Failed to lay out");
+        assert_eq!(idxs, vec![0, cnt - 1]);
+        assert_eq!(idxs.len(), doc.pages().count());
+    }
+
+    #[test]
+    fn test_measure_prep_lay() {
+        // Measurement falls back from a missing font as rendering does.
+        let missing = FontSource::File(std::path::PathBuf::from("missing.ttf"));
+        let mut doc = new_ansi_letter()
+            .set_fnt(missing)
+            .set_fallback_fnt(Some(crate::tst::tst_fnt()));
+        let drf = par("Draft of {{date}}.");
+        doc.add_par(drf.clone());
+        assert!(doc.measure_par(&drf).expect("Failed to measure") > 0.0);
+        assert!(doc.measure_height().expect("Failed to measure") > 0.0);
+        assert_eq!(doc.locate_par(0).expect("Failed to locate").0, 0);
+        assert_eq!(doc.page_count().expect("Failed to count pages"), 1);
+    }
+
+    #[test]
+    fn test_locate_par_missing() {
+        let mut doc = new_ansi_letter();
+        doc.add_pag_brk();
+        assert!(matches!(
+            doc.locate_par(0),
+            Err(DocError::ElementIndexError(0))
+        ));
+    }
+
+    #[test]
+    fn test_lay_pags_auto_break() {
+        let mut doc = new_ansi_letter();
+        for _ in 0..100 {
+            doc.add_par(par("A paragraph repeated until the page overflows."));
+        }
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert!(pags.len() > 1);
+        assert_eq!(pags.iter().map(|lays| lays.len()).sum::<usize>(), 100);
+
+        let btm = (doc.sze.height - doc.mrg.btm).pt();
+        for lay in pags.iter().flatten() {
+            assert!(lay.y + lay.hgt <= btm);
+        }
+    }
+
+    #[test]
+    fn test_lay_pags_tall_par() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Short."));
+        doc.add_par(
+            par(&"A paragraph taller than a page. ".repeat(500)).set_keep_together(Some(true)),
+        );
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert_eq!(pags.len(), 2);
+        assert_eq!(pags[1].len(), 1);
+        assert_eq!(pags[1][0].idx, 1);
+    }
+
+    #[test]
+    fn test_lay_pags_hdr_ftr() {
+        let mut doc = new_ansi_letter()
+            .set_hdr(Some(par("Header")))
+            .set_ftr(Some(par("Footer")));
+        for _ in 0..100 {
+            doc.add_par(par("A paragraph repeated until the page overflows."));
+        }
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let (hdr_hgt, ftr_hgt) = doc
+            .hdr_ftr_hgt(&mut fnts, &font_mgr)
+            .expect("Failed to measure header and footer");
+        assert!(hdr_hgt > 0.0);
+        assert!(ftr_hgt > 0.0);
+
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        let top = doc.mrg.top.pt() + hdr_hgt;
+        let btm = (doc.sze.height - doc.mrg.btm).pt() - ftr_hgt;
+        for lay in pags.iter().flatten() {
+            assert!(lay.y >= top);
+            assert!(lay.y + lay.hgt <= btm);
+        }
+    }
+
+    #[test]
+    fn test_lay_pags_ind_lft_rht() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Body"));
+        doc.add_par(
+            par("Quote")
+                .set_ind_lft(Some(In(0.5)))
+                .set_ind_rht(Some(In(0.5))),
+        );
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert_eq!(pags[0][0].x, In(1.0).pt());
+        assert_eq!(pags[0][0].wid, In(6.5).pt());
+        assert_eq!(pags[0][1].x, In(1.5).pt());
+        assert_eq!(pags[0][1].wid, In(5.5).pt());
+    }
+
+    #[test]
+    fn test_lay_pags_spc_bfr() {
+        let mut doc = new_ansi_letter()
+            .set_spc_par_bfr(LineSpace::Custom(1.0))
+            .set_spc_par_aft(LineSpace::Custom(0.0));
+        doc.add_par(par("First"));
+        doc.add_par(par("Second"));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+
+        // Space before is skipped for the first paragraph on a page.
+        assert_eq!(pags[0][0].y, doc.mrg.top.pt());
+        assert!(pags[0][1].y > pags[0][0].y + pags[0][0].hgt);
+    }
+
+    #[test]
+    fn test_lay_pags_spc_aft() {
+        let mut doc = new_ansi_letter().set_spc_par_aft(LineSpace::Custom(0.6));
+        let txt = "A paragraph long enough to wrap across several lines of text. ".repeat(4);
+        doc.add_par(par(&txt));
+        doc.add_par(par(&txt));
+        doc.add_par(par(&txt).add_styled_run(Run::new("Big", None).set_fnt_sze(Some(30.0))));
+        doc.add_par(par(&txt));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+
+        // The gap is the nominal line height times the spacing after,
+        // added once regardless of line count or run sizes.
+        let gap = doc.fnt_sze * 1.35 * 0.6;
+        for pair in pags[0].windows(2) {
+            assert!((pair[1].y - (pair[0].y + pair[0].hgt) - gap).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_bld_par_runs() {
+        let doc = new_ansi_letter();
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pln = doc
+            .bld_par(&par("Plain text"), &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph");
+        let big = doc
+            .bld_par(
+                &par("Plain ").add_styled_run(
+                    Run::new("text", None)
+                        .set_fnt_sze(Some(36.0))
+                        .set_fnt_sty(Some(Style::Bold)),
+                ),
+                &mut fnts,
+                &font_mgr,
+            )
+            .expect("Failed to build paragraph");
+
+        // A larger run raises the line height.
+        assert!(big.height() > pln.height());
+    }
+
+    #[test]
+    fn test_lay_pags_split_par() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Short."));
+        doc.add_par(par(&"A paragraph split across pages. ".repeat(100)));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+
+        // The paragraph starts below the first and continues on the next page.
+        assert_eq!(pags[0].len(), 2);
+        assert_eq!(pags[0][1].ofs, 0.0);
+        assert!(pags[0][1].is_spl());
+        assert_eq!(pags[1][0].idx, 1);
+        assert_eq!(pags[1][0].y, doc.mrg.top.pt());
+        assert_eq!(pags[1][0].ofs, pags[0][1].hgt);
+
+        let btm = (doc.sze.height - doc.mrg.btm).pt();
+        for lay in pags.iter().flatten() {
+            assert!(lay.y + lay.hgt <= btm);
+        }
+    }
+
+    #[test]
+    fn test_lay_pags_keep_together() {
+        let mut doc = new_ansi_letter();
+        for _ in 0..40 {
+            doc.add_par(par("A paragraph repeated until the page is nearly full."));
+        }
+        let txt = "A callout kept together on one page. ".repeat(20);
+        doc.add_par(par(&txt).set_keep_together(Some(true)));
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+
+        // The callout is placed whole on one page.
+        let pcs: Vec<&Lay> = pags.iter().flatten().filter(|lay| lay.idx == 40).collect();
+        assert_eq!(pcs.len(), 1);
+        assert!(!pcs[0].is_spl());
+    }
+
+    #[test]
+    fn test_lay_pags_keep_with_next() {
+        let hdg = par("Heading").set_keep_with_next(Some(true));
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+
+        // Measure the heading alone.
+        let mut doc = new_ansi_letter();
+        doc.add_par(hdg.clone());
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        let hgt = pags[0][0].hgt;
+
+        // A frame fitting the heading but not the body's first line.
+        let mut doc = new_ansi_letter();
+        doc.add_frm(Frame::new(
+            0,
+            In(1.0),
+            In(1.0),
+            In(6.5),
+            In::from_pt(Pt(hgt + 1.0)),
+        ));
+        doc.add_par(hdg);
+        doc.add_par(par("Body"));
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert!(pags[0].is_empty());
+        assert_eq!(pags[1].len(), 2);
+        assert_eq!(pags[1][0].idx, 0);
+    }
+
+    #[test]
+    fn test_lay_pags_widow_orphan() {
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+
+        // Measure a three line paragraph below another.
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("First"));
+        doc.add_par(par("One\nTwo\nThree"));
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        let top = pags[0][1].y - doc.mrg.top.pt();
+        let lne = pags[0][1].hgt / 3.0;
+
+        // Lays out the document in a first frame fitting a number of lines.
+        let mut lay_frm = |doc: &Doc, lns: f32| {
+            let mut doc = doc.clone();
+            let hgt = In::from_pt(Pt(top + lne * lns));
+            doc.add_frm(Frame::new(0, In(1.0), In(1.0), In(6.5), hgt));
+            doc.lay_pags(&mut fnts, &font_mgr)
+                .expect("Failed to lay out pages")
+        };
+
+        // A single orphan line continues in the next frame.
+        let pags = lay_frm(&doc, 1.5);
+        assert_eq!(pags[0].len(), 1);
+        assert_eq!(pags[1][0].idx, 1);
+        assert!(!pags[1][0].is_spl());
+
+        // Without a minimum a single line is left.
+        let doc = doc.set_orphan_lines(0).set_widow_lines(0);
+        let pags = lay_frm(&doc, 1.5);
+        assert_eq!(pags[0].len(), 2);
+        assert!(pags[0][1].is_spl());
+
+        // A single widow line pulls a line into the next frame.
+        let doc = doc.set_widow_lines(2);
+        let pags = lay_frm(&doc, 2.5);
+        assert_eq!(pags[0].len(), 2);
+        assert!(pags[1][0].hgt > pags[0][1].hgt);
+    }
+}
@@ -1,12 +1,82 @@
+#[cfg(feature = "bin")]
+pub mod bin;
+pub mod cap;
+pub mod clr;
+pub mod cm;
+pub mod dgn;
 pub mod doc;
 pub mod err;
+pub mod fit;
+pub mod fld;
+pub mod fnd;
+pub mod fnt;
+pub mod frm;
+pub mod ftn;
+pub mod hng;
+pub mod hyp;
 pub mod inch;
+pub mod jst;
+pub mod lay;
+pub mod meta;
+#[cfg(feature = "markdown")]
+pub mod mkd;
+pub mod mm;
 pub mod mrg;
+#[cfg(feature = "net")]
+pub mod net;
+pub mod num;
+pub mod otl;
+pub mod pag;
+pub mod pt;
+pub mod qr;
 pub mod sze;
+pub mod tab;
+pub mod tbl;
+#[cfg(test)]
+mod tst;
+pub mod txf;
 pub mod unit;
+pub mod vld;
+pub mod wtr;
+#[cfg(feature = "yaml")]
+pub mod yml;
+#[cfg(feature = "bin")]
+pub use bin::*;
+pub use cap::*;
+pub use clr::*;
+pub use cm::*;
+pub use dgn::*;
 pub use doc::*;
 pub use err::*;
+pub use fit::*;
+pub use fld::*;
+pub use fnd::*;
+pub use fnt::*;
+pub use frm::*;
+pub use ftn::*;
+pub use hng::*;
+pub use hyp::*;
 pub use inch::*;
+pub use jst::*;
+pub use lay::*;
+pub use meta::*;
+#[cfg(feature = "markdown")]
+pub use mkd::*;
+pub use mm::*;
 pub use mrg::*;
+#[cfg(feature = "net")]
+pub use net::*;
+pub use num::*;
+pub use otl::*;
+pub use pag::*;
+pub use pt::*;
+pub use qr::*;
 pub use sze::*;
+pub use tab::*;
+pub use tbl::*;
+pub use txf::*;
 pub use unit::*;
+pub use vld::*;
+pub use wtr::*;
+#[cfg(feature = "yaml")]
+pub use yml::*;
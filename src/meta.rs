@@ -0,0 +1,153 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::doc::*;
+use serde::{Deserialize, Serialize};
+use skia_safe::pdf;
+
+/// Number of seconds in a day.
+const SEC_PER_DAY: u64 = 86_400;
+
+/// Metadata of a document shown by PDF viewers.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct DocMeta {
+    /// The document's _title_.
+    pub title: String,
+    /// The name of the person who created the document.
+    pub author: String,
+    /// The _subject_ of the document.
+    pub subject: String,
+    /// Comma separated _keywords_ of the document.
+    pub keywords: String,
+    /// The product that created the original document.
+    pub creator: String,
+    /// Time the document was _created_ in seconds since the _Unix_ epoch,
+    /// or `None` for the time the PDF is written.
+    ///
+    /// Set a fixed time for reproducible output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<u64>,
+    /// Time the document was _modified_ in seconds since the _Unix_ epoch,
+    /// or `None` for the time the PDF is written.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<u64>,
+}
+
+impl DocMeta {
+    /// Returns PDF metadata for the document.
+    ///
+    /// The producer names this crate and its version.
+    pub fn pdf(&self) -> pdf::Metadata {
+        let now = now_secs();
+        pdf::Metadata {
+            title: self.title.clone(),
+            author: self.author.clone(),
+            subject: self.subject.clone(),
+            keywords: self.keywords.clone(),
+            creator: self.creator.clone(),
+            producer: format!("pdf-doc {}", env!("CARGO_PKG_VERSION")),
+            creation: Some(pdf_dte(self.created.unwrap_or(now))),
+            modified: Some(pdf_dte(self.modified.unwrap_or(now))),
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns the current time in seconds since the _Unix_ epoch.
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |dur| dur.as_secs())
+}
+
+/// Returns the _UTC_ date and time of seconds since the _Unix_ epoch.
+pub fn pdf_dte(secs: u64) -> pdf::DateTime {
+    let days = (secs / SEC_PER_DAY) as i64;
+    let sec = secs % SEC_PER_DAY;
+
+    // Convert days to a civil date counted from March of a 400 year era.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    pdf::DateTime {
+        time_zone_minutes: 0,
+        year: year as u16,
+        month: month as u8,
+        // The epoch fell on a Thursday.
+        day_of_week: ((days + 4) % 7) as u8,
+        day: (doy - (153 * mp + 2) / 5 + 1) as u8,
+        hour: (sec / 3_600) as u8,
+        minute: (sec / 60 % 60) as u8,
+        second: (sec % 60) as u8,
+    }
+}
+
+impl Doc {
+    /// Returns PDF metadata for the document
+    /// with its image quality and compression.
+    pub fn pdf_meta(&self) -> pdf::Metadata {
+        let mut ret = self.meta.pdf();
+        if self.pdf_quality < 1.0 {
+            ret.encoding_quality = Some((self.pdf_quality.max(0.0) * 100.0).round() as i32);
+        }
+        if !self.compress {
+            ret.compression_level = pdf::CompressionLevel::None;
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdf() {
+        let meta = DocMeta {
+            title: "Title".into(),
+            author: "Author".into(),
+            ..Default::default()
+        };
+        let md = meta.pdf();
+        assert_eq!(md.title, "Title");
+        assert_eq!(md.author, "Author");
+        assert!(md.subject.is_empty());
+        assert!(md.producer.starts_with("pdf-doc "));
+        assert!(md.creation.is_some_and(|dte| dte.year >= 2024));
+    }
+
+    #[test]
+    fn test_pdf_dte() {
+        let dte = pdf_dte(0);
+        assert_eq!(
+            (dte.year, dte.month, dte.day, dte.day_of_week),
+            (1970, 1, 1, 4)
+        );
+        let dte = pdf_dte(1_700_000_000);
+        assert_eq!(
+            (dte.year, dte.month, dte.day, dte.day_of_week),
+            (2023, 11, 14, 2)
+        );
+        assert_eq!((dte.hour, dte.minute, dte.second), (22, 13, 20));
+        let dte = pdf_dte(951_782_400);
+        assert_eq!((dte.year, dte.month, dte.day), (2000, 2, 29));
+    }
+
+    #[test]
+    fn test_pinned_dates() {
+        let meta = DocMeta {
+            created: Some(0),
+            modified: Some(1_700_000_000),
+            ..Default::default()
+        };
+        let md = meta.pdf();
+        assert_eq!(md.creation.unwrap().year, 1970);
+        assert_eq!(md.modified.unwrap().year, 2023);
+    }
+}
@@ -0,0 +1,239 @@
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+use crate::doc::*;
+
+/// Builds document elements from _Markdown_ events.
+#[derive(Debug, Default)]
+struct MkdBld {
+    /// Elements built so far.
+    elms: Vec<Elm>,
+    /// Paragraph being built.
+    par: Option<Par>,
+    /// Text runs of the paragraph being built.
+    runs: Vec<Run>,
+    /// Depth of nested strong emphasis.
+    bld: usize,
+    /// Depth of nested emphasis.
+    itl: usize,
+    /// URL of the enclosing link.
+    lnk: Option<String>,
+    /// Lists being built, innermost last.
+    lsts: Vec<List>,
+}
+
+impl MkdBld {
+    /// Returns the font style of text at the current emphasis.
+    fn sty(&self) -> Option<Style> {
+        match (self.bld > 0, self.itl > 0) {
+            (true, true) => Some(Style::BoldItalic),
+            (true, false) => Some(Style::Bold),
+            (false, true) => Some(Style::Italic),
+            (false, false) => None,
+        }
+    }
+
+    /// Starts a paragraph unless one is being built.
+    fn bgn(&mut self, par: Par) {
+        if self.par.is_none() {
+            self.par = Some(par);
+        }
+    }
+
+    /// Appends text to the paragraph being built.
+    fn txt(&mut self, txt: &str) {
+        self.bgn(Par::default());
+        let run = Run::new(txt, self.lnk.as_deref()).set_fnt_sty(self.sty());
+        match self.runs.last_mut() {
+            Some(lst) if lst.link == run.link && lst.fnt_sty == run.fnt_sty => {
+                lst.txt.push_str(txt)
+            }
+            _ => self.runs.push(run),
+        }
+    }
+
+    /// Finishes the paragraph being built into the innermost
+    /// list, or the elements when outside a list.
+    fn end(&mut self) {
+        let Some(mut par) = self.par.take() else {
+            return;
+        };
+        let mut runs = std::mem::take(&mut self.runs);
+        if let Some(lst) = runs.last_mut() {
+            lst.txt.truncate(lst.txt.trim_end().len());
+        }
+        runs.retain(|run| !run.txt.is_empty());
+        if runs.is_empty() {
+            return;
+        }
+
+        // Plain text needs no runs.
+        if runs.len() == 1 && runs[0].link.is_none() && runs[0].fnt_sty.is_none() {
+            par.txt = runs.remove(0).txt;
+        } else {
+            par.runs = runs;
+        }
+
+        match self.lsts.last_mut() {
+            Some(lst) => lst.itms.push(par),
+            None => self.elms.push(Elm::Par(par)),
+        }
+    }
+
+    /// Applies a _Markdown_ event.
+    fn evt(&mut self, evt: Event) {
+        match evt {
+            Event::Start(Tag::Heading { level, .. }) => {
+                self.end();
+                self.bgn(
+                    par("")
+                        .set_heading(Some(level as u8))
+                        .set_fnt_sty(Some(Style::Bold))
+                        .set_has_ind(Some(false))
+                        .set_keep_with_next(Some(true)),
+                );
+            }
+            Event::Start(Tag::Paragraph) => {
+                // Paragraphs of a loose list item join the item.
+                if self.par.is_some() && !self.lsts.is_empty() {
+                    self.txt("\n");
+                }
+                self.bgn(Par::default());
+            }
+            Event::Start(Tag::List(fst)) => {
+                self.end();
+                let kind = match fst {
+                    Some(_) => ListKind::Numbered,
+                    None => ListKind::default(),
+                };
+                self.lsts.push(list(kind, vec![]));
+            }
+            Event::Start(Tag::Item) => {
+                self.end();
+                self.bgn(Par::default());
+            }
+            Event::Start(Tag::Strong) => self.bld += 1,
+            Event::Start(Tag::Emphasis) => self.itl += 1,
+            Event::Start(Tag::Link { dest_url, .. }) => self.lnk = Some(dest_url.to_string()),
+            Event::End(TagEnd::Strong) => self.bld = self.bld.saturating_sub(1),
+            Event::End(TagEnd::Emphasis) => self.itl = self.itl.saturating_sub(1),
+            Event::End(TagEnd::Link) => self.lnk = None,
+            // An image's alt text stays inline in its paragraph.
+            Event::End(TagEnd::Image) => {}
+            Event::End(TagEnd::Paragraph) if !self.lsts.is_empty() => {}
+            Event::End(TagEnd::List(_)) => {
+                self.end();
+                let Some(lst) = self.lsts.pop() else {
+                    return;
+                };
+                // Nested lists flatten into the enclosing list.
+                match self.lsts.last_mut() {
+                    Some(prn) => prn.itms.extend(lst.itms),
+                    None if lst.itms.is_empty() => {}
+                    None => self.elms.push(Elm::List(lst)),
+                }
+            }
+            Event::End(_) => self.end(),
+            Event::Text(txt) | Event::Code(txt) | Event::Html(txt) | Event::InlineHtml(txt) => {
+                self.txt(&txt)
+            }
+            Event::SoftBreak => self.txt(" "),
+            Event::HardBreak => self.txt("\n"),
+            Event::Rule => {
+                self.end();
+                self.elms.push(Elm::Rule(rule()));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Doc {
+    /// Creates an _8.5in x 11in_ document from _Markdown_.
+    ///
+    /// See [`Doc::add_markdown`] for how _Markdown_ maps to elements.
+    pub fn from_markdown(md: &str) -> Doc {
+        let mut doc = new_ansi_letter();
+        doc.add_markdown(md);
+        doc
+    }
+
+    /// Adds elements from _Markdown_ to the end of the document.
+    ///
+    /// Headings become heading paragraphs, `**bold**` and `*italic*`
+    /// become styled runs, links become linked runs, lists become
+    /// lists, and `---` becomes a rule. Other constructs degrade to
+    /// plain paragraphs. Elements use the document's styling.
+    ///
+    /// ### Arguments
+    ///
+    /// * `md` - The _Markdown_ text.
+    pub fn add_markdown(&mut self, md: &str) {
+        let mut bld = MkdBld::default();
+        for evt in Parser::new(md) {
+            bld.evt(evt);
+        }
+        bld.end();
+        self.elms.extend(bld.elms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_markdown() {
+        let md = "# Title\n\nSome **bold** and *italic*\ntext.\n\n- One\n- Two\n\n---\n\n1. First\n\n> Quoted";
+        let doc = Doc::from_markdown(md);
+        assert_eq!(doc.elms.len(), 6);
+
+        let Elm::Par(hdg) = &doc.elms[0] else {
+            panic!("Expected a heading");
+        };
+        assert_eq!(hdg.heading_level, Some(1));
+        assert_eq!(hdg.txt, "Title");
+
+        let Elm::Par(bdy) = &doc.elms[1] else {
+            panic!("Expected a paragraph");
+        };
+        assert_eq!(bdy.all_txt(), "Some bold and italic text.");
+        assert_eq!(bdy.runs[1].fnt_sty, Some(Style::Bold));
+        assert_eq!(bdy.runs[3].fnt_sty, Some(Style::Italic));
+
+        let Elm::List(lst) = &doc.elms[2] else {
+            panic!("Expected a list");
+        };
+        assert_eq!(lst.kind, ListKind::default());
+        assert_eq!(lst.itms.len(), 2);
+        assert_eq!(lst.itms[1].txt, "Two");
+
+        assert!(matches!(doc.elms[3], Elm::Rule(_)));
+        assert!(matches!(&doc.elms[4], Elm::List(lst) if lst.kind == ListKind::Numbered));
+        assert!(matches!(&doc.elms[5], Elm::Par(par) if par.txt == "Quoted"));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_markdown");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_add_markdown_styling() {
+        let mut doc = new_a4().set_fnt_sze(14.0);
+        doc.add_markdown("[Link](https://example.com) ```code```");
+        assert_eq!(doc.fnt_sze, 14.0);
+        let Elm::Par(par) = &doc.elms[0] else {
+            panic!("Expected a paragraph");
+        };
+        assert_eq!(par.runs[0].link.as_deref(), Some("https://example.com"));
+        assert_eq!(par.all_txt(), "Link code");
+    }
+
+    #[test]
+    fn test_from_markdown_image_alt() {
+        let doc = Doc::from_markdown("Text ![logo](x.png) more");
+        assert_eq!(doc.elms.len(), 1);
+        let Elm::Par(par) = &doc.elms[0] else {
+            panic!("Expected a paragraph");
+        };
+        assert_eq!(par.all_txt(), "Text logo more");
+    }
+}
@@ -0,0 +1,92 @@
+use crate::inch::*;
+use crate::unit::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter, Result},
+    ops::Deref,
+};
+
+/// A length in millimeters.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Mm(pub f32);
+
+impl Display for Mm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}mm", self.0)
+    }
+}
+
+impl Deref for Mm {
+    type Target = f32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Unit for Mm {
+    fn pt(&self) -> f32 {
+        self.0 * PT_PER_IN / MM_PER_IN
+    }
+}
+
+impl From<Mm> for In {
+    fn from(len: Mm) -> In {
+        In(len.0 / MM_PER_IN)
+    }
+}
+
+impl_unit_ops!(Mm);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Mm(25.4)), "25.4mm");
+    }
+
+    #[test]
+    fn test_to_pt() {
+        assert!((Mm(25.4).pt() - 72.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_into_in() {
+        let len: In = Mm(50.8).into();
+        assert_eq!(len, In(2.0));
+        assert!((len.pt() - Mm(50.8).pt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_round_trip_serialize_deserialize() {
+        let original = Mm(12.34);
+
+        // Serialize the `Mm` instance to a JSON string
+        let serialized = serde_json::to_string(&original).expect("Failed to serialize");
+
+        // Deserialize the JSON string back to a `Mm` instance
+        let deserialized: Mm = serde_json::from_str(&serialized).expect("Failed to deserialize");
+
+        // Check that the original and deserialized instances are the same
+        assert_eq!(original, deserialized);
+    }
+
+    #[test]
+    fn test_arithmetic_mm() {
+        let a = Mm(10.0);
+        let b = Mm(4.0);
+        assert_eq!((a + b).0, 14.0);
+        assert_eq!((a - b).0, 6.0);
+        assert_eq!((a * 2).0, 20.0);
+        assert_eq!((a / 4.0).0, 2.5);
+        assert_eq!(-a, Mm(-10.0));
+
+        let mut c = a;
+        c += b;
+        c -= 2;
+        c *= 0.5;
+        assert_eq!(c, Mm(6.0));
+    }
+}
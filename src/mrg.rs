@@ -1,13 +1,15 @@
-use crate::inch::In;
+use crate::{
+    inch::{In, MM_PER_IN},
+    mm::Mm,
+    pt::Pt,
+};
 use serde::{Deserialize, Serialize};
 
 /// A margin with _1in_ for the left, right, bottom, and top.
-pub const MRG_IN_1: Mrg = Mrg {
-    lft: In(1.0),
-    rht: In(1.0),
-    btm: In(1.0),
-    top: In(1.0),
-};
+pub const MRG_IN_1: Mrg = Mrg::uniform(In(1.0));
+
+/// A margin with _25mm_ for the left, right, bottom, and top.
+pub const MRG_MM_25: Mrg = Mrg::uniform(In(25.0 / MM_PER_IN));
 
 /// A margin with a _left_, _right_, _bottom_, _top_.
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd)]
@@ -28,6 +30,74 @@ impl Mrg {
         Self { lft, rht, btm, top }
     }
 
+    /// Returns a new [`Mrg`] from lengths in _millimeters_.
+    pub fn from_mm(lft: f32, rht: f32, btm: f32, top: f32) -> Self {
+        Self::from_units(Mm(lft), Mm(rht), Mm(btm), Mm(top))
+    }
+
+    /// Returns a new [`Mrg`] from lengths in _points_.
+    pub fn from_pt(lft: f32, rht: f32, btm: f32, top: f32) -> Self {
+        Self::from_units(Pt(lft), Pt(rht), Pt(btm), Pt(top))
+    }
+
+    /// Returns a new [`Mrg`] from lengths in any unit convertible to inches.
+    ///
+    /// Units may be mixed, such as `Mrg::from_units(Mm(20.0), Mm(20.0), In(1.0), Pt(54.0))`.
+    pub fn from_units(
+        lft: impl Into<In>,
+        rht: impl Into<In>,
+        btm: impl Into<In>,
+        top: impl Into<In>,
+    ) -> Self {
+        Self::new(lft.into(), rht.into(), btm.into(), top.into())
+    }
+
+    /// Returns a new [`Mrg`] with the same length on every side.
+    pub const fn uniform(len: In) -> Self {
+        Self::symmetric(len, len)
+    }
+
+    /// Returns a new [`Mrg`] with one length for the _left_ and _right_,
+    /// and another for the _bottom_ and _top_.
+    pub const fn symmetric(horizontal: In, vertical: In) -> Self {
+        Self {
+            lft: horizontal,
+            rht: horizontal,
+            btm: vertical,
+            top: vertical,
+        }
+    }
+
+    /// Sets the _left_ and _right_ margins.
+    ///
+    /// ### Arguments
+    ///
+    /// * `horizontal` - The new left and right margin.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated left and right margins.
+    pub fn horizontal(mut self, horizontal: In) -> Self {
+        self.lft = horizontal;
+        self.rht = horizontal;
+        self
+    }
+
+    /// Sets the _bottom_ and _top_ margins.
+    ///
+    /// ### Arguments
+    ///
+    /// * `vertical` - The new bottom and top margin.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated bottom and top margins.
+    pub fn vertical(mut self, vertical: In) -> Self {
+        self.btm = vertical;
+        self.top = vertical;
+        self
+    }
+
     pub fn width(&self) -> In {
         self.lft + self.rht
     }
@@ -36,3 +106,38 @@ impl Mrg {
         self.btm + self.top
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_mm() {
+        let mrg = Mrg::from_mm(25.4, 50.8, 12.7, 0.0);
+        assert_eq!(mrg, Mrg::new(In(1.0), In(2.0), In(0.5), In(0.0)));
+    }
+
+    #[test]
+    fn test_from_pt_units() {
+        let mrg = Mrg::from_pt(72.0, 144.0, 36.0, 0.0);
+        assert_eq!(mrg, Mrg::new(In(1.0), In(2.0), In(0.5), In(0.0)));
+        let mrg = Mrg::from_units(Mm(25.4), In(2.0), Pt(36.0), In(0.0));
+        assert_eq!(mrg, Mrg::new(In(1.0), In(2.0), In(0.5), In(0.0)));
+    }
+
+    #[test]
+    fn test_uniform_symmetric() {
+        assert_eq!(
+            Mrg::uniform(In(1.0)),
+            Mrg::new(In(1.0), In(1.0), In(1.0), In(1.0))
+        );
+        assert_eq!(
+            Mrg::symmetric(In(1.0), In(0.5)),
+            Mrg::new(In(1.0), In(1.0), In(0.5), In(0.5))
+        );
+        assert_eq!(
+            MRG_IN_1.horizontal(In(2.0)).vertical(In(0.5)),
+            Mrg::symmetric(In(2.0), In(0.5))
+        );
+    }
+}
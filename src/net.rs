@@ -0,0 +1,158 @@
+use std::collections::hash_map::Entry::Vacant;
+use std::collections::HashMap;
+
+use crate::doc::*;
+use crate::err::*;
+use crate::inch::*;
+use skia_safe::{Data, Image};
+
+/// Images downloaded once and shared across documents,
+/// such as a logo added to many letters.
+#[derive(Debug, Default)]
+pub struct ImgCache {
+    /// Encoded image bytes by URL.
+    pub imgs: HashMap<String, Vec<u8>>,
+}
+
+impl ImgCache {
+    /// Returns a new empty [`ImgCache`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of downloaded images.
+    pub fn len(&self) -> usize {
+        self.imgs.len()
+    }
+
+    /// Returns `true` if no images are downloaded.
+    pub fn is_empty(&self) -> bool {
+        self.imgs.is_empty()
+    }
+}
+
+impl Doc {
+    /// Downloads a _PNG_ or _JPEG_ image and adds it to the end of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `url` - URL of the encoded image.
+    /// * `wid` - Width of the image.
+    /// * `hgt` - Height of the image.
+    pub fn add_img_url(&mut self, url: &str, wid: In, hgt: In) -> Result<(), DocError> {
+        self.add_img_url_with_cache(url, wid, hgt, &mut ImgCache::new())
+    }
+
+    /// Adds an image downloaded once from a URL to the end of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `url` - URL of the encoded image.
+    /// * `wid` - Width of the image.
+    /// * `hgt` - Height of the image.
+    /// * `cache` - Images downloaded by earlier calls, extended with the image.
+    pub fn add_img_url_with_cache(
+        &mut self,
+        url: &str,
+        wid: In,
+        hgt: In,
+        cache: &mut ImgCache,
+    ) -> Result<(), DocError> {
+        if let Vacant(e) = cache.imgs.entry(url.into()) {
+            e.insert(fetch_img(url)?);
+        }
+        self.add_img(cache.imgs[url].clone(), wid, hgt);
+        Ok(())
+    }
+}
+
+/// Downloads an encoded image, checking that it decodes.
+pub fn fetch_img(url: &str) -> Result<Vec<u8>, DocError> {
+    let data = reqwest::blocking::get(url)
+        .and_then(|rsp| rsp.error_for_status())
+        .and_then(|rsp| rsp.bytes())?
+        .to_vec();
+    if Image::from_encoded(Data::new_copy(&data)).is_none() {
+        return Err(DocError::ImgDecodeError(StringError::new(&format!(
+            "Unable to decode image from `{}`.",
+            url
+        ))));
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use skia_safe::{surfaces, EncodedImageFormat};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Returns a _PNG_ image filled with blue.
+    fn png() -> Vec<u8> {
+        let mut surface = surfaces::raster_n32_premul((16, 16)).unwrap();
+        surface.canvas().clear(skia_safe::Color::BLUE);
+        surface
+            .image_snapshot()
+            .encode(None, EncodedImageFormat::PNG, None)
+            .unwrap()
+            .as_bytes()
+            .to_vec()
+    }
+
+    /// Serves a body to one request on the loopback interface,
+    /// and returns the URL to request.
+    fn serve(body: Vec<u8>) -> String {
+        let lsn = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/logo.png", lsn.local_addr().unwrap());
+        thread::spawn(move || {
+            let (mut stm, _) = lsn.accept().unwrap();
+            let mut req = [0; 1024];
+            let _ = stm.read(&mut req);
+            let hdr = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stm.write_all(hdr.as_bytes()).unwrap();
+            stm.write_all(&body).unwrap();
+        });
+        url
+    }
+
+    #[test]
+    fn test_fetch_img() {
+        assert_eq!(
+            fetch_img(&serve(png())).expect("Failed to fetch image"),
+            png()
+        );
+        assert!(matches!(
+            fetch_img(&serve(b"not an image".to_vec())),
+            Err(DocError::ImgDecodeError(_))
+        ));
+        assert!(matches!(
+            fetch_img("not a url"),
+            Err(DocError::NetworkError(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_img_url() {
+        let mut doc = new_ansi_letter();
+        assert!(doc.add_img_url("not a url", In(1.0), In(1.0)).is_err());
+        assert!(doc.elms.is_empty());
+
+        // A cached image is not downloaded again.
+        let url = serve(png());
+        let mut cache = ImgCache::new();
+        for _ in 0..2 {
+            doc.add_img_url_with_cache(&url, In(1.0), In(1.0), &mut cache)
+                .expect("Failed to add image");
+        }
+        assert_eq!(cache.len(), 1);
+        assert_eq!(doc.elms.len(), 2);
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_img_url");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+}
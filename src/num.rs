@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+/// A _US_ number format, e.g. `1,234.56`.
+pub const NUM_FMT_US: NumberFormat = NumberFormat {
+    grp: Some(','),
+    dec: '.',
+    prc: 2,
+};
+
+/// A _European_ number format, e.g. `1.234,56`.
+pub const NUM_FMT_EU: NumberFormat = NumberFormat {
+    grp: Some('.'),
+    dec: ',',
+    prc: 2,
+};
+
+/// A locale format for numeric values with
+/// a _grouping_ separator, _decimal_ separator, and _precision_.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Thousands _grouping_ separator, or `None` for no grouping.
+    pub grp: Option<char>,
+    /// _Decimal_ separator.
+    pub dec: char,
+    /// Number of digits after the decimal separator.
+    pub prc: usize,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NUM_FMT_US
+    }
+}
+
+impl NumberFormat {
+    /// Returns a new [`NumberFormat`].
+    pub fn new(grp: Option<char>, dec: char, prc: usize) -> Self {
+        Self { grp, dec, prc }
+    }
+
+    /// Formats a numeric value.
+    pub fn fmt(&self, val: f64) -> String {
+        let txt = format!("{:.*}", self.prc, val.abs());
+        let (int, frc) = match txt.split_once('.') {
+            Some((int, frc)) => (int, Some(frc)),
+            None => (txt.as_str(), None),
+        };
+
+        let mut ret = String::new();
+        if val < 0.0 && txt.chars().any(|c| c != '0' && c != '.') {
+            ret.push('-');
+        }
+
+        // Group integer digits.
+        for (idx, c) in int.chars().enumerate() {
+            if idx > 0 && (int.len() - idx) % 3 == 0 {
+                if let Some(grp) = self.grp {
+                    ret.push(grp);
+                }
+            }
+            ret.push(c);
+        }
+
+        if let Some(frc) = frc {
+            ret.push(self.dec);
+            ret.push_str(frc);
+        }
+
+        ret
+    }
+
+    /// Formats text when it is a plain decimal number such as `-1234.5`,
+    /// otherwise returns the text unchanged.
+    ///
+    /// Text such as `inf`, `NaN`, or `1e3` is left unchanged.
+    pub fn fmt_txt(&self, txt: &str) -> String {
+        match is_dec(txt.trim()).then(|| txt.trim().parse::<f64>()) {
+            Some(Ok(val)) => self.fmt(val),
+            _ => txt.to_string(),
+        }
+    }
+}
+
+/// Returns `true` if text is a decimal literal with an optional sign,
+/// integer digits, and optional fraction digits.
+fn is_dec(txt: &str) -> bool {
+    let txt = txt.strip_prefix(['-', '+']).unwrap_or(txt);
+    let (int, frc) = txt.split_once('.').unwrap_or((txt, "0"));
+    !int.is_empty() && !frc.is_empty() && int.chars().chain(frc.chars()).all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt_us() {
+        assert_eq!(NUM_FMT_US.fmt(1234.56), "1,234.56");
+        assert_eq!(NUM_FMT_US.fmt(1234567.0), "1,234,567.00");
+        assert_eq!(NUM_FMT_US.fmt(-12.5), "-12.50");
+    }
+
+    #[test]
+    fn test_fmt_eu() {
+        assert_eq!(NUM_FMT_EU.fmt(1234.56), "1.234,56");
+        assert_eq!(NUM_FMT_EU.fmt(123.0), "123,00");
+    }
+
+    #[test]
+    fn test_fmt_txt() {
+        assert_eq!(NUM_FMT_EU.fmt_txt("1234.56"), "1.234,56");
+        assert_eq!(NUM_FMT_EU.fmt_txt("n/a"), "n/a");
+        assert_eq!(NUM_FMT_US.fmt_txt(" -5 "), "-5.00");
+        for txt in ["inf", "NaN", "1e3", ".5", "5.", "1.2.3"] {
+            assert_eq!(NUM_FMT_US.fmt_txt(txt), txt);
+        }
+    }
+
+    #[test]
+    fn test_fmt_no_grp() {
+        let num_fmt = NumberFormat::new(None, '.', 0);
+        assert_eq!(num_fmt.fmt(1234.4), "1234");
+    }
+}
@@ -0,0 +1,386 @@
+use std::io::{self, Write};
+
+use crate::doc::*;
+use crate::err::*;
+use crate::inch::*;
+use crate::lay::*;
+use crate::pt::*;
+use serde::{Deserialize, Serialize};
+
+/// A _bookmark_ to a heading paragraph with nested sub-headings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Bookmark {
+    /// Heading level of the paragraph.
+    pub lvl: u8,
+    /// Text of the heading.
+    pub txt: String,
+    /// Index of the heading paragraph in `elms`.
+    pub idx: usize,
+    /// Index of the page.
+    pub pag: usize,
+    /// Distance from the top of the page to the heading.
+    pub y: In,
+    /// Bookmarks of sub-headings.
+    pub chd: Vec<Bookmark>,
+}
+
+/// Returns the name of the _PDF_ destination of a heading paragraph.
+///
+/// ### Arguments
+///
+/// * `idx` - Index of the paragraph in `elms`.
+pub fn dst_name(idx: usize) -> String {
+    format!("heading-{}", idx)
+}
+
+/// Nests bookmarks in document order under the nearest
+/// preceding bookmark with a lower heading level.
+pub fn nest(bmks: Vec<Bookmark>) -> Vec<Bookmark> {
+    fn add(lst: &mut Vec<Bookmark>, bmk: Bookmark) {
+        match lst.last_mut() {
+            Some(prv) if prv.lvl < bmk.lvl => add(&mut prv.chd, bmk),
+            _ => lst.push(bmk),
+        }
+    }
+
+    let mut ret = vec![];
+    for bmk in bmks {
+        add(&mut ret, bmk);
+    }
+    ret
+}
+
+impl Doc {
+    /// Returns bookmarks to heading paragraphs nested by heading level.
+    ///
+    /// Bookmarks are those of the outline written to the _PDF_,
+    /// with fields filled in and fonts falling back as when rendering.
+    pub fn outline(&self) -> Result<Vec<Bookmark>, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+        let pags = doc.lay_pags(&mut cache.fnts, &cache.font_mgr)?;
+        Ok(nest(doc.bmks(&pags)))
+    }
+
+    /// Returns bookmarks in document order to the heading
+    /// paragraphs of laid out pages.
+    ///
+    /// A heading split across frames is bookmarked where it begins.
+    pub(crate) fn bmks(&self, pags: &[Vec<Lay>]) -> Vec<Bookmark> {
+        let mut ret = vec![];
+        for (pag, lays) in pags.iter().enumerate() {
+            for lay in lays.iter().filter(|lay| lay.ofs == 0.0) {
+                if let (LayCnt::Par(_), Some(Elm::Par(par))) = (&lay.cnt, self.elms.get(lay.idx)) {
+                    if let Some(lvl) = par.heading_level {
+                        ret.push(Bookmark {
+                            lvl,
+                            txt: par.all_txt(),
+                            idx: lay.idx,
+                            pag,
+                            y: In::from_pt(Pt(lay.y)),
+                            chd: vec![],
+                        });
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+/// Marks the number of an indirect object beginning in a _PDF_.
+const OBJ: &[u8] = b" 0 obj";
+
+/// A writer counting the bytes of a _PDF_ written through it.
+///
+/// The last object written and what follows it are kept, so an outline
+/// can be appended once skia closes the document: skia writes the
+/// catalog last, followed by the cross-reference section and trailer.
+pub(crate) struct PdfWtr<W> {
+    /// The writer receiving the PDF.
+    w: W,
+    /// Number of bytes written.
+    len: usize,
+    /// Bytes from the start of the last object.
+    tail: Vec<u8>,
+}
+
+impl<W: Write> PdfWtr<W> {
+    /// Returns a new [`PdfWtr`] writing to `w`.
+    pub(crate) fn new(w: W) -> Self {
+        Self {
+            w,
+            len: 0,
+            tail: vec![],
+        }
+    }
+}
+
+impl<W: Write> Write for PdfWtr<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let cnt = self.w.write(buf)?;
+        self.len += cnt;
+
+        // Drop bytes before the line of the last object begun.
+        let ovl = self.tail.len().saturating_sub(OBJ.len() - 1);
+        self.tail.extend_from_slice(&buf[..cnt]);
+        if let Some(pos) = rfind(&self.tail[ovl..], OBJ) {
+            let beg = self.tail[..ovl + pos]
+                .iter()
+                .rposition(|b| *b == b'\n')
+                .map_or(0, |pos| pos + 1);
+            self.tail.drain(..beg);
+        }
+
+        Ok(cnt)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.w.flush()
+    }
+}
+
+/// Appends an _outline_ of nested bookmarks to a _PDF_ written by skia.
+///
+/// Skia writes no outline, so the outline is added as an incremental
+/// update: the outline items, a catalog referencing them, and a
+/// cross-reference section following the original file. Items go to
+/// the named destinations drawn at each heading. Only the catalog and
+/// trailer kept by the writer are read; the file isn't buffered.
+pub(crate) fn add_otl<W: Write>(w: &mut PdfWtr<W>, bmks: &[Bookmark]) -> Result<(), DocError> {
+    if bmks.is_empty() {
+        return Ok(());
+    }
+    let err = || DocError::from_encode("Unable to add the PDF outline.");
+
+    // Read the catalog, the last object, and the trailer.
+    let tail = std::str::from_utf8(&w.tail).map_err(|_| err())?;
+    let (num, rst) = tail.split_once(" 0 obj").ok_or_else(err)?;
+    let root: usize = num.trim().parse().map_err(|_| err())?;
+    let (cat, rst) = rst.split_once("endobj").ok_or_else(err)?;
+    let cat = cat.trim().strip_suffix(">>").ok_or_else(err)?;
+    let (_, rst) = rst.split_once("trailer").ok_or_else(err)?;
+    let (trl, prv) = rst.split_once("startxref").ok_or_else(err)?;
+    let trl = trl.trim();
+    let prv = prv.trim().trim_end_matches("%%EOF").trim();
+    let size = int_after(trl, "/Size").ok_or_else(err)?;
+    if int_after(trl, "/Root") != Some(root) {
+        return Err(err());
+    }
+
+    // Number the outline and its items after the original objects.
+    let otl = size;
+    let mut nxt = size + 1;
+    let mut objs = vec![];
+    let (fst, lst, cnt) = add_itms(bmks, otl, &mut nxt, &mut objs).ok_or_else(err)?;
+    objs.push((
+        otl,
+        format!("<</Type /Outlines\n/First {fst} 0 R\n/Last {lst} 0 R\n/Count {cnt}>>"),
+    ));
+    objs.sort_by_key(|(num, _)| *num);
+    let cat = (
+        root,
+        format!("{cat}\n/Outlines {otl} 0 R\n/PageMode /UseOutlines>>"),
+    );
+
+    // Write the objects, then their cross-reference section and trailer.
+    let mut txt = String::new();
+    if !tail.ends_with('\n') {
+        txt.push('\n');
+    }
+    let mut ofss = vec![];
+    for (num, obj) in std::iter::once(&cat).chain(&objs) {
+        ofss.push(w.len + txt.len());
+        txt.push_str(&format!("{num} 0 obj\n{obj}\nendobj\n"));
+    }
+    let xref = w.len + txt.len();
+    let ent = |ofs: &usize| format!("{:010} 00000 n \n", ofs);
+    txt.push_str(&format!(
+        "xref\n{root} 1\n{}{size} {}\n",
+        ent(&ofss[0]),
+        objs.len()
+    ));
+    ofss[1..].iter().for_each(|ofs| txt.push_str(&ent(ofs)));
+    let trl = trl.replacen(&format!("/Size {size}"), &format!("/Size {nxt}"), 1);
+    let trl = trl.strip_suffix(">>").ok_or_else(err)?;
+    txt.push_str(&format!(
+        "trailer\n{trl}\n/Prev {prv}>>\nstartxref\n{xref}\n%%EOF"
+    ));
+    w.w.write_all(txt.as_bytes())?;
+
+    Ok(())
+}
+
+/// Adds outline items of sibling bookmarks and their sub-headings,
+/// numbered from `nxt`, under a parent object.
+///
+/// ### Returns
+///
+/// The numbers of the first and last siblings, and the number of
+/// items including sub-headings, or `None` with no bookmarks.
+fn add_itms(
+    bmks: &[Bookmark],
+    par: usize,
+    nxt: &mut usize,
+    objs: &mut Vec<(usize, String)>,
+) -> Option<(usize, usize, usize)> {
+    let nums: Vec<usize> = (*nxt..*nxt + bmks.len()).collect();
+    *nxt += bmks.len();
+    let mut cnt = bmks.len();
+    for (pos, bmk) in bmks.iter().enumerate() {
+        let mut obj = format!(
+            "<</Title {}\n/Parent {par} 0 R\n/Dest /{}",
+            pdf_txt(&bmk.txt),
+            dst_name(bmk.idx)
+        );
+        if pos > 0 {
+            obj.push_str(&format!("\n/Prev {} 0 R", nums[pos - 1]));
+        }
+        if let Some(num) = nums.get(pos + 1) {
+            obj.push_str(&format!("\n/Next {num} 0 R"));
+        }
+        if let Some((fst, lst, chd)) = add_itms(&bmk.chd, nums[pos], nxt, objs) {
+            obj.push_str(&format!(
+                "\n/First {fst} 0 R\n/Last {lst} 0 R\n/Count {chd}"
+            ));
+            cnt += chd;
+        }
+        obj.push_str(">>");
+        objs.push((nums[pos], obj));
+    }
+    Some((*nums.first()?, *nums.last()?, cnt))
+}
+
+/// Returns text as a _PDF_ hexadecimal string in UTF-16BE with a byte order mark.
+fn pdf_txt(txt: &str) -> String {
+    let hex: String = txt
+        .encode_utf16()
+        .map(|unt| format!("{:04X}", unt))
+        .collect();
+    format!("<FEFF{hex}>")
+}
+
+/// Returns the integer following a key in a _PDF_ dictionary.
+fn int_after(dct: &str, key: &str) -> Option<usize> {
+    let (_, rst) = dct.split_once(key)?;
+    rst.split_whitespace()
+        .next()?
+        .trim_end_matches(">>")
+        .parse()
+        .ok()
+}
+
+/// Returns the position of the last occurrence of bytes.
+fn rfind(hay: &[u8], pat: &[u8]) -> Option<usize> {
+    hay.windows(pat.len()).rposition(|win| win == pat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{Dictionary, Document, Object};
+
+    fn bmk(lvl: u8, txt: &str) -> Bookmark {
+        Bookmark {
+            lvl,
+            txt: txt.into(),
+            idx: 0,
+            pag: 0,
+            y: In(1.0),
+            chd: vec![],
+        }
+    }
+
+    #[test]
+    fn test_nest() {
+        let ret = nest(vec![
+            bmk(1, "A"),
+            bmk(2, "A.1"),
+            bmk(3, "A.1.a"),
+            bmk(2, "A.2"),
+            bmk(1, "B"),
+            bmk(1, "C"),
+        ]);
+        assert_eq!(ret.len(), 3);
+        assert_eq!(ret[0].chd.len(), 2);
+        assert_eq!(ret[0].chd[0].chd[0].txt, "A.1.a");
+        assert_eq!(ret[0].chd[1].txt, "A.2");
+        assert!(ret[1].chd.is_empty());
+    }
+
+    #[test]
+    fn test_pdf_txt() {
+        assert_eq!(pdf_txt("Hi"), "<FEFF00480069>");
+        assert_eq!(pdf_txt("é"), "<FEFF00E9>");
+    }
+
+    #[test]
+    fn test_outline() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Intro").set_heading(Some(1)));
+        doc.add_par(par("Body"));
+        doc.add_pag_brk();
+        doc.add_par(par("Details").set_heading(Some(2)));
+
+        let ret = doc.outline().expect("Failed to build outline");
+        assert_eq!(ret.len(), 1);
+        assert_eq!(ret[0].txt, "Intro");
+        assert_eq!((ret[0].idx, ret[0].pag), (0, 0));
+        assert_eq!(ret[0].chd[0].txt, "Details");
+        assert_eq!((ret[0].chd[0].idx, ret[0].chd[0].pag), (3, 1));
+
+        // The PDF has an outline of the headings' destinations.
+        let data = doc.to_pdf_bytes().expect("Failed to write PDF");
+        let pdf = Document::load_mem(&data).expect("Failed to parse PDF");
+        assert_eq!(pdf.get_pages().len(), 2);
+        let cat = pdf.catalog().expect("Failed to read catalog");
+        assert_eq!(
+            cat.get(b"PageMode").and_then(Object::as_name).unwrap(),
+            b"UseOutlines"
+        );
+        let otl = ref_dct(&pdf, cat, b"Outlines");
+        assert_eq!(otl.get(b"Count").and_then(Object::as_i64).unwrap(), 2);
+        let itm = ref_dct(&pdf, otl, b"First");
+        assert_eq!(
+            itm.get(b"Title").and_then(Object::as_str).unwrap(),
+            utf16("Intro")
+        );
+        assert_eq!(
+            itm.get(b"Dest").and_then(Object::as_name).unwrap(),
+            b"heading-0"
+        );
+        let chd = ref_dct(&pdf, itm, b"First");
+        assert_eq!(
+            chd.get(b"Title").and_then(Object::as_str).unwrap(),
+            utf16("Details")
+        );
+        assert_eq!(
+            chd.get(b"Dest").and_then(Object::as_name).unwrap(),
+            b"heading-3"
+        );
+        assert!(chd.get(b"Next").is_err());
+    }
+
+    /// Returns the dictionary referenced by a key of a dictionary.
+    fn ref_dct<'a>(pdf: &'a Document, dct: &Dictionary, key: &[u8]) -> &'a Dictionary {
+        let id = dct.get(key).and_then(Object::as_reference).unwrap();
+        pdf.get_dictionary(id).expect("Failed to read dictionary")
+    }
+
+    /// Returns text as UTF-16BE bytes with a byte order mark.
+    fn utf16(txt: &str) -> Vec<u8> {
+        let mut ret = vec![0xFE, 0xFF];
+        txt.encode_utf16()
+            .for_each(|unt| ret.extend(unt.to_be_bytes()));
+        ret
+    }
+
+    #[test]
+    fn test_outline_none() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Body"));
+        let data = doc.to_pdf_bytes().expect("Failed to write PDF");
+        let pdf = Document::load_mem(&data).expect("Failed to parse PDF");
+        let cat = pdf.catalog().expect("Failed to read catalog");
+        assert!(cat.get(b"Outlines").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::doc::*;
+use crate::mrg::*;
+use crate::sze::*;
+
+/// Returns the number of leading lines of a paragraph placed on the current page.
+///
+/// Lines are placed while their cumulative height fits within `avl` points.
+/// A split leaves at least `orphans` lines at the bottom of the current page
+/// and at least `widows` lines at the top of the next page.
+///
+/// Paragraphs shorter than `widows + orphans` lines are exempt from splitting
+/// and move to the next page as a whole.
+///
+/// ### Arguments
+///
+/// * `lne_hgts` - Height of each paragraph line in points.
+/// * `avl` - Available vertical space on the current page in points.
+/// * `widows` - Minimum lines at the top of the next page.
+/// * `orphans` - Minimum lines at the bottom of the current page.
+///
+/// ### Returns
+///
+/// `lne_hgts.len()` when the whole paragraph fits, `0` when the paragraph
+/// moves to the next page, or the number of lines before the split.
+pub fn fit_lnes(lne_hgts: &[f32], avl: f32, widows: usize, orphans: usize) -> usize {
+    let cnt = lne_hgts.len();
+
+    // Count lines fitting within the available space.
+    let mut fit = 0;
+    let mut hgt: f32 = 0.0;
+    for lne_hgt in lne_hgts {
+        if hgt + lne_hgt > avl {
+            break;
+        }
+        hgt += lne_hgt;
+        fit += 1;
+    }
+    if fit == cnt {
+        return cnt;
+    }
+
+    // Short paragraphs move as a whole.
+    if cnt < widows + orphans {
+        return 0;
+    }
+
+    // Leave enough lines for the next page.
+    if cnt - fit < widows {
+        fit = cnt - widows;
+    }
+
+    // Leave enough lines on the current page.
+    if fit < orphans.max(1) {
+        return 0;
+    }
+
+    fit
+}
+
+/// Returns the vertical gap between two adjacent paragraphs in points.
+///
+/// ### Arguments
+///
+/// * `aft` - Spacing after the previous paragraph in points.
+/// * `bfr` - Spacing before the next paragraph in points.
+/// * `collapse` - `true` takes the larger of the two, `false` adds them.
+pub fn spc_gap(aft: f32, bfr: f32, collapse: bool) -> f32 {
+    if collapse {
+        aft.max(bfr)
+    } else {
+        aft + bfr
+    }
+}
+
+/// Token replaced with the one-based page number.
+pub const PAG_TKN: &str = "{{page}}";
+
+/// Token replaced with the number of pages.
+pub const PAGS_TKN: &str = "{{pages}}";
+
+/// A page _number_ and the document's page count,
+/// substituted for [`PAG_TKN`] and [`PAGS_TKN`] in header and footer text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageNumber {
+    /// One-based number of the page.
+    pub pag: usize,
+    /// Number of pages in the document.
+    pub pags: usize,
+}
+
+impl PageNumber {
+    /// Returns a new [`PageNumber`].
+    pub fn new(pag: usize, pags: usize) -> Self {
+        Self { pag, pags }
+    }
+
+    /// Returns text with page number tokens replaced.
+    pub fn fmt(&self, txt: &str) -> String {
+        txt.replace(PAG_TKN, &self.pag.to_string())
+            .replace(PAGS_TKN, &self.pags.to_string())
+    }
+}
+
+/// A page being drawn with its index, number, size, and margins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pag {
+    /// Index of the laid out page.
+    pub idx: usize,
+    /// Number of the page substituted in header and footer text.
+    pub num: PageNumber,
+    /// Size of the page.
+    pub sze: Sze,
+    /// Margins of the page.
+    pub mrg: Mrg,
+}
+
+impl Pag {
+    /// Returns a new [`Pag`].
+    pub fn new(idx: usize, num: PageNumber, sze: Sze, mrg: Mrg) -> Self {
+        Self { idx, num, sze, mrg }
+    }
+}
+
+/// A borrowed view of the elements between page breaks.
+#[derive(Debug, Clone, Copy)]
+pub struct Page<'a> {
+    /// Index in `elms` of the page's first element.
+    pub idx: usize,
+    /// Elements of the page.
+    pub elms: &'a [Elm],
+}
+
+impl<'a> Page<'a> {
+    /// Returns each element of the page paired with its index in `elms`.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &'a Elm)> {
+        let idx = self.idx;
+        self.elms
+            .iter()
+            .enumerate()
+            .map(move |(ofs, elm)| (idx + ofs, elm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_lnes_all() {
+        let lne_hgts = [10.0, 10.0, 10.0];
+        assert_eq!(fit_lnes(&lne_hgts, 30.0, 2, 2), 3);
+    }
+
+    #[test]
+    fn test_fit_lnes_short_par_moves_intact() {
+        // A two-line paragraph at a page boundary with room for one line.
+        let lne_hgts = [10.0, 10.0];
+        assert_eq!(fit_lnes(&lne_hgts, 15.0, 2, 2), 0);
+    }
+
+    #[test]
+    fn test_fit_lnes_widow() {
+        // Splitting after four lines would leave a single widow.
+        let lne_hgts = [10.0; 5];
+        assert_eq!(fit_lnes(&lne_hgts, 45.0, 2, 2), 3);
+    }
+
+    #[test]
+    fn test_fit_lnes_orphan() {
+        // Splitting after one line would leave a single orphan.
+        let lne_hgts = [10.0; 5];
+        assert_eq!(fit_lnes(&lne_hgts, 15.0, 2, 2), 0);
+    }
+
+    #[test]
+    fn test_spc_gap() {
+        assert_eq!(spc_gap(12.0, 18.0, false), 30.0);
+        assert_eq!(spc_gap(12.0, 18.0, true), 18.0);
+        assert_eq!(spc_gap(18.0, 12.0, true), 18.0);
+    }
+
+    #[test]
+    fn test_pag_num_fmt() {
+        let txt = "{{page}} of {{pages}}";
+        let ret: Vec<String> = (1..=3)
+            .map(|pag| PageNumber::new(pag, 3).fmt(txt))
+            .collect();
+        assert_eq!(ret, vec!["1 of 3", "2 of 3", "3 of 3"]);
+        assert_eq!(PageNumber::new(1, 3).fmt("Draft"), "Draft");
+    }
+}
@@ -0,0 +1,128 @@
+use crate::inch::*;
+use crate::unit::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter, Result},
+    ops::Deref,
+};
+
+/// A length in points.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Pt(pub f32);
+
+impl Display for Pt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}pt", self.0)
+    }
+}
+
+impl Deref for Pt {
+    type Target = f32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Unit for Pt {
+    fn pt(&self) -> f32 {
+        self.0
+    }
+}
+
+impl Pt {
+    /// Returns a length in points from a length in inches.
+    pub fn from_in(len: In) -> Self {
+        Pt(len.pt())
+    }
+}
+
+impl From<Pt> for In {
+    fn from(len: Pt) -> In {
+        In::from_pt(len)
+    }
+}
+
+impl_unit_ops!(Pt);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let length = Pt(12.5);
+        let expected_output = "12.5pt";
+        let actual_output = format!("{}", length);
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_to_pt() {
+        let length = Pt(12.0);
+        assert_eq!(length.pt(), 12.0);
+    }
+
+    #[test]
+    fn test_from_in() {
+        assert_eq!(Pt::from_in(In(1.5)), Pt(108.0));
+        assert_eq!(In::from_pt(Pt::from_in(In(2.25))), In(2.25));
+    }
+
+    #[test]
+    fn test_round_trip_serialize_deserialize() {
+        let original = Pt(12.34);
+
+        // Serialize the `Pt` instance to a JSON string
+        let serialized = serde_json::to_string(&original).expect("Failed to serialize");
+
+        // Deserialize the JSON string back to a `Pt` instance
+        let deserialized: Pt = serde_json::from_str(&serialized).expect("Failed to deserialize");
+
+        // Check that the original and deserialized instances are the same
+        assert_eq!(original, deserialized);
+    }
+
+    #[test]
+    fn test_arithmetic_pt() {
+        let a = Pt(10.0);
+        let b = Pt(4.0);
+        assert_eq!((a + b).0, 14.0);
+        assert_eq!((a - b).0, 6.0);
+        assert_eq!((a * b).0, 40.0);
+        assert_eq!((a / b).0, 2.5);
+        assert_eq!((a % b).0, 2.0);
+        assert_eq!(-a, Pt(-10.0));
+
+        let mut c = a;
+        c += b;
+        c -= 2;
+        c *= 0.5;
+        c /= Pt(2.0);
+        c %= 2u8;
+        assert_eq!(c, Pt(1.0));
+    }
+
+    macro_rules! test_numeric_ops {
+        ($($name:ident: $t:ty, $val:expr)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let a = Pt(5.0);
+                    assert_eq!((a + $val as $t).0, 5.0 + $val as f32);
+                    assert_eq!((a - $val as $t).0, 5.0 - $val as f32);
+                    assert_eq!((a * $val as $t).0, 5.0 * $val as f32);
+                    assert_eq!((a / $val as $t).0, 5.0 / $val as f32);
+                    assert_eq!((a % $val as $t).0, 5.0 % $val as f32);
+                }
+            )*
+        }
+    }
+
+    test_numeric_ops! {
+        test_ops_i32: i32, 2
+        test_ops_u32: u32, 2
+        test_ops_f32: f32, 2.0
+        test_ops_f64: f64, 2.0
+    }
+}
@@ -0,0 +1,110 @@
+use crate::clr::*;
+use crate::doc::*;
+use crate::err::*;
+use crate::inch::*;
+use skia_safe::{Canvas, Paint, Rect};
+
+/// Width of the light border around a QR code in modules.
+const QR_QZ: usize = 4;
+
+impl Doc {
+    /// Adds a _QR code_ encoding text to the end of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `data` - Text encoded by the QR code, such as a URL.
+    /// * `size` - Width and height of the QR code, including its quiet zone.
+    pub fn add_qr(&mut self, data: &str, size: In) {
+        self.elms.push(Elm::QrCode {
+            data: data.into(),
+            size,
+        });
+    }
+}
+
+/// Returns the rows of modules of a QR code encoding text,
+/// with `true` for each dark module.
+#[cfg(feature = "qr")]
+pub fn qr_mods(data: &str) -> Result<Vec<Vec<bool>>, DocError> {
+    let code = qrcode::QrCode::new(data.as_bytes())
+        .map_err(|err| DocError::from_encode(&err.to_string()))?;
+    Ok(code
+        .to_colors()
+        .chunks(code.width())
+        .map(|row| row.iter().map(|clr| *clr == qrcode::Color::Dark).collect())
+        .collect())
+}
+
+/// Returns an error, as encoding a QR code requires the `qr` feature.
+#[cfg(not(feature = "qr"))]
+pub fn qr_mods(_data: &str) -> Result<Vec<Vec<bool>>, DocError> {
+    Err(DocError::from_encode("QR codes require the `qr` feature"))
+}
+
+/// Draw a QR code encoding text with its quiet zone
+/// as a square `size` points wide at `x` and `y` points.
+pub fn drw_qr(canvas: &Canvas, data: &str, x: f32, y: f32, size: f32) -> Result<(), DocError> {
+    let mods = qr_mods(data)?;
+    let mod_sze = size / (mods.len() + 2 * QR_QZ) as f32;
+
+    let mut paint = Paint::default();
+    paint.set_color(WHITE);
+    canvas.draw_rect(Rect::from_xywh(x, y, size, size), &paint);
+    paint.set_color(BLACK);
+    for (row_idx, row) in mods.iter().enumerate() {
+        for (col_idx, _) in row.iter().enumerate().filter(|(_, drk)| **drk) {
+            let rect = Rect::from_xywh(
+                x + (col_idx + QR_QZ) as f32 * mod_sze,
+                y + (row_idx + QR_QZ) as f32 * mod_sze,
+                mod_sze,
+                mod_sze,
+            );
+            canvas.draw_rect(rect, &paint);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fnt::*;
+    use crate::lay::*;
+    use crate::unit::*;
+    use skia_safe::{textlayout::FontCollection, FontMgr};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_add_qr() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Scan for tickets"));
+        doc.add_qr("https://example.com/tickets/42", In(1.5));
+        assert!(doc.validate().is_ok());
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        let qr = &pags[0][1];
+        assert!(matches!(&qr.cnt, LayCnt::Qr(data) if data == "https://example.com/tickets/42"));
+        assert_eq!((qr.wid, qr.hgt), (In(1.5).pt(), In(1.5).pt()));
+
+        doc.add_qr("Empty", In(0.0));
+        assert!(doc.validate().is_err());
+    }
+
+    #[cfg(feature = "qr")]
+    #[test]
+    fn test_qr_save_pdf() {
+        let mods = qr_mods("https://example.com").expect("Failed to encode QR code");
+        assert!(mods.len() >= 21);
+        assert!(mods.iter().all(|row| row.len() == mods.len()));
+        assert!(mods[0][0]);
+
+        let mut doc = new_ansi_letter();
+        doc.add_qr("https://example.com", In(2.0));
+        let pth = std::env::temp_dir().join("pdf_doc_test_qr");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{inch::In, unit::Unit};
+use crate::{
+    inch::{In, MM_PER_IN},
+    mm::Mm,
+    pt::Pt,
+    unit::Unit,
+};
 use serde::{Deserialize, Serialize};
 
 /// An _8.5in x 11in_ letter size.
@@ -10,6 +15,32 @@ pub const ANSI_LETTER: Sze = Sze {
     height: In(11.0),
 };
 
+/// An _8.5in x 14in_ legal size.
+pub const LEGAL: Sze = Sze {
+    width: In(8.5),
+    height: In(14.0),
+};
+
+/// A _210mm x 297mm_ A4 size.
+///
+/// ISO 216 A4 is the standard paper size outside North America.
+pub const A4: Sze = Sze {
+    width: In(210.0 / MM_PER_IN),
+    height: In(297.0 / MM_PER_IN),
+};
+
+/// A _297mm x 420mm_ A3 size.
+pub const A3: Sze = Sze {
+    width: In(297.0 / MM_PER_IN),
+    height: In(420.0 / MM_PER_IN),
+};
+
+/// A _148mm x 210mm_ A5 size.
+pub const A5: Sze = Sze {
+    width: In(148.0 / MM_PER_IN),
+    height: In(210.0 / MM_PER_IN),
+};
+
 /// A size with a _width_ and _height_.
 #[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
 pub struct Sze {
@@ -23,10 +54,71 @@ impl Sze {
         Self { width, height }
     }
 
+    /// Returns a new [`Sze`] from lengths in _points_.
+    pub fn from_pt(width: f32, height: f32) -> Self {
+        Self::from_units(Pt(width), Pt(height))
+    }
+
+    /// Returns a new [`Sze`] from lengths in _millimeters_.
+    ///
+    /// Such as `Sze::from_mm(210.0, 297.0)` for A4.
+    pub fn from_mm(width: f32, height: f32) -> Self {
+        Self::from_units(Mm(width), Mm(height))
+    }
+
+    /// Returns a new [`Sze`] from lengths in any unit convertible to inches.
+    pub fn from_units(width: impl Into<In>, height: impl Into<In>) -> Self {
+        Self::new(width.into(), height.into())
+    }
+
     /// Returns a tuple in units of _points_.
     pub fn pt(&self) -> (f32, f32) {
         (self.width.pt(), self.height.pt())
     }
+
+    /// Returns `true` if the width is greater than the height.
+    pub fn is_landscape(&self) -> bool {
+        self.width > self.height
+    }
+
+    /// Returns the size with the longer side as the _width_.
+    pub fn landscape(self) -> Sze {
+        if self.width < self.height {
+            Sze::new(self.height, self.width)
+        } else {
+            self
+        }
+    }
+
+    /// Returns the size with the longer side as the _height_.
+    pub fn portrait(self) -> Sze {
+        if self.width > self.height {
+            Sze::new(self.height, self.width)
+        } else {
+            self
+        }
+    }
+
+    /// Returns the size in an orientation.
+    pub fn orient(self, ori: Orientation) -> Sze {
+        match ori {
+            Orientation::Portrait => self.portrait(),
+            Orientation::Landscape => self.landscape(),
+        }
+    }
+}
+
+/// Determines the orientation of a page.
+///
+/// - `Portrait`: The height is the longer side.
+/// - `Landscape`: The width is the longer side.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// The height is the longer side.
+    #[default]
+    Portrait,
+    /// The width is the longer side.
+    Landscape,
 }
 
 #[cfg(test)]
@@ -52,4 +144,30 @@ mod tests {
         // Check that the original and deserialized instances are the same
         assert_eq!(original, deserialized);
     }
+
+    #[test]
+    fn test_a_series_pt() {
+        let round = |sze: Sze| (sze.pt().0.round(), sze.pt().1.round());
+        assert_eq!(round(A3), (842.0, 1191.0));
+        assert_eq!(round(A4), (595.0, 842.0));
+        assert_eq!(round(A5), (420.0, 595.0));
+    }
+
+    #[test]
+    fn test_from_pt_mm() {
+        assert_eq!(Sze::from_pt(612.0, 792.0), ANSI_LETTER);
+        assert_eq!(Sze::from_mm(210.0, 297.0), A4);
+        assert_eq!(Sze::from_units(In(8.5), Pt(1008.0)), LEGAL);
+    }
+
+    #[test]
+    fn test_orientation() {
+        let lnd = ANSI_LETTER.landscape();
+        assert_eq!(lnd, Sze::new(In(11.0), In(8.5)));
+        assert!(lnd.is_landscape());
+        assert_eq!(lnd.landscape(), lnd);
+        assert_eq!(lnd.portrait(), ANSI_LETTER);
+        assert!(!ANSI_LETTER.is_landscape());
+        assert_eq!(ANSI_LETTER.orient(Orientation::Landscape), lnd);
+    }
 }
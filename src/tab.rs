@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use crate::doc::*;
+use crate::err::*;
+use crate::fnt::*;
+use crate::inch::*;
+use crate::unit::*;
+use serde::{Deserialize, Serialize};
+use skia_safe::{
+    textlayout::{
+        FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, PlaceholderAlignment,
+        PlaceholderStyle, TextBaseline, TextStyle,
+    },
+    Canvas, FontMgr, Point,
+};
+
+/// Width in points at which tabbed text segments are measured.
+const MSR_WID: f32 = 1.0e6;
+
+/// Narrowing of right tab gaps in points so that text ending
+/// at the right of the text area does not wrap to a new line.
+const RHT_TOL: f32 = 0.01;
+
+/// Alignment of the text following a tab stop.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TabAln {
+    /// Text starts at the tab stop.
+    #[default]
+    Left,
+    /// Text ends at the tab stop.
+    Right,
+}
+
+/// A _tab stop_ of a paragraph with an optional leader character.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct TabStop {
+    /// Distance from the left of the paragraph text to the tab stop.
+    pub pos: In,
+    /// Alignment of the text following the tab stop.
+    pub aln: TabAln,
+    /// Character repeated to fill the gap before the tab stop,
+    /// such as `'.'` for a dot leader.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leader: Option<char>,
+}
+
+/// Creates a tab stop with a position, an alignment, and an optional leader.
+pub fn tab_stop(pos: In, aln: TabAln, leader: Option<char>) -> TabStop {
+    TabStop { pos, aln, leader }
+}
+
+/// Adds text pieces to a paragraph builder with each
+/// tab character advancing to a tab stop.
+///
+/// The nth tab character advances to the nth tab stop.
+/// Tab characters past the last tab stop, or behind the text,
+/// advance no further.
+///
+/// ### Arguments
+///
+/// * `par_bld` - The paragraph builder.
+/// * `par_sty` - Style of the paragraph measuring the text.
+/// * `fnt_col` - Font collection of the paragraph.
+/// * `tabs` - Tab stops of the paragraph.
+/// * `pces` - Text pieces with their text styles.
+/// * `ind` - Width of the first line indentation in points.
+pub fn add_tabbed_txt(
+    par_bld: &mut ParagraphBuilder,
+    par_sty: &ParagraphStyle,
+    fnt_col: &FontCollection,
+    tabs: &[TabStop],
+    pces: &[(&str, TextStyle)],
+    ind: f32,
+) {
+    // Split text pieces into segments at tab characters.
+    let mut segs: Vec<Vec<(&str, &TextStyle)>> = vec![vec![]];
+    for (txt, ts) in pces {
+        for (idx, prt) in txt.split('\t').enumerate() {
+            if idx > 0 {
+                segs.push(vec![]);
+            }
+            segs.last_mut().unwrap().push((prt, ts));
+        }
+    }
+
+    // Measure the width of each segment.
+    let wids: Vec<f32> = segs
+        .iter()
+        .map(|seg| {
+            let mut bld = ParagraphBuilder::new(par_sty, fnt_col);
+            for (txt, ts) in seg {
+                bld.push_style(ts);
+                bld.add_text(txt);
+                bld.pop();
+            }
+            let mut paragraph = bld.build();
+            paragraph.layout(MSR_WID);
+            paragraph.max_intrinsic_width()
+        })
+        .collect();
+
+    // Add segments separated by gaps reaching each tab stop.
+    let mut x = ind;
+    for (idx, seg) in segs.iter().enumerate() {
+        for (txt, ts) in seg {
+            par_bld.push_style(ts);
+            par_bld.add_text(txt);
+            par_bld.pop();
+        }
+        x += wids[idx];
+
+        if let Some(nxt_wid) = wids.get(idx + 1) {
+            let gap = match tabs.get(idx) {
+                Some(tab) if tab.aln == TabAln::Left => tab.pos.pt() - x,
+                Some(tab) => tab.pos.pt() - x - nxt_wid - RHT_TOL,
+                None => 0.0,
+            }
+            .max(0.0);
+            par_bld.add_placeholder(&PlaceholderStyle {
+                width: gap,
+                height: 0.0,
+                alignment: PlaceholderAlignment::Baseline,
+                baseline_offset: 0.0,
+                baseline: TextBaseline::Alphabetic,
+            });
+            x += gap;
+        }
+    }
+}
+
+impl Doc {
+    /// Draw the leaders of a paragraph's tab stops
+    /// painted at `x` and `y` points.
+    ///
+    /// Leader characters fill the gap before each tab stop
+    /// and end at the text following the tab stop.
+    #[allow(clippy::too_many_arguments)]
+    pub fn drw_ldrs(
+        &self,
+        canvas: &Canvas,
+        paragraph: &Paragraph,
+        par: &Par,
+        x: f32,
+        y: f32,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(), DocError> {
+        if par.tabs.iter().all(|tab| tab.leader.is_none()) {
+            return Ok(());
+        }
+
+        // The first line indentation placeholder precedes the tab gaps.
+        let ofs = par.has_ind.unwrap_or(self.has_ind) as usize;
+        let gaps = paragraph.get_rects_for_placeholders();
+        for (gap, tab) in gaps.iter().skip(ofs).zip(&par.tabs) {
+            let Some(leader) = tab.leader else {
+                continue;
+            };
+
+            // Measure a single leader character in the paragraph's style.
+            let mut ldr_par = Par {
+                fnt: par.fnt.clone(),
+                fnt_sze: par.fnt_sze,
+                fnt_sty: par.fnt_sty,
+                clr: par.clr,
+                aln: Some(Align::Left),
+                spc_lne: par.spc_lne,
+                has_ind: Some(false),
+                stroke: par.stroke,
+                txt: leader.to_string(),
+                ..Default::default()
+            };
+            let ldr_wid = self.bld_par(&ldr_par, fnts, font_mgr)?.longest_line();
+            let cnt = if ldr_wid > 0.0 {
+                (gap.rect.width() / ldr_wid).floor() as usize
+            } else {
+                0
+            };
+            if cnt == 0 {
+                continue;
+            }
+
+            // Paint leaders on the baseline ending at the tab gap's right.
+            ldr_par.txt = leader.to_string().repeat(cnt);
+            let ldrs = self.bld_par(&ldr_par, fnts, font_mgr)?;
+            let pnt = Point {
+                x: x + gap.rect.right - ldrs.longest_line(),
+                y: y + gap.rect.bottom - ldrs.alphabetic_baseline(),
+            };
+            ldrs.paint(canvas, pnt);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use skia_safe::textlayout::{RectHeightStyle, RectWidthStyle};
+
+    /// Returns the left and right of a range of a paragraph's text in points.
+    fn lft_rht(paragraph: &Paragraph, rng: std::ops::Range<usize>) -> (f32, f32) {
+        let tbs = paragraph.get_rects_for_range(rng, RectHeightStyle::Max, RectWidthStyle::Tight);
+        (tbs[0].rect.left, tbs.last().unwrap().rect.right)
+    }
+
+    #[test]
+    fn test_rht_tab() {
+        let doc = new_ansi_letter();
+        let par = par("Introduction\t3")
+            .set_has_ind(Some(false))
+            .set_tabs(vec![tab_stop(In(6.5), TabAln::Right, Some('.'))]);
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let paragraph = doc
+            .bld_par(&par, &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph");
+
+        // The page number ends at the tab stop on the first line.
+        assert_eq!(paragraph.line_number(), 1);
+        let (_, rht) = lft_rht(&paragraph, 13..14);
+        assert!((rht - In(6.5).pt()).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_lft_tab() {
+        let doc = new_ansi_letter();
+        let par = par("A\tB\tC")
+            .set_has_ind(Some(false))
+            .set_tabs(vec![tab_stop(In(2.0), TabAln::Left, None)]);
+
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let paragraph = doc
+            .bld_par(&par, &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph");
+
+        // The first tab advances to its stop and the second advances no further.
+        let (b_lft, b_rht) = lft_rht(&paragraph, 2..3);
+        assert!((b_lft - In(2.0).pt()).abs() < 1.0);
+        let (c_lft, _) = lft_rht(&paragraph, 4..5);
+        assert!((c_lft - b_rht).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_tab_runs_save_pdf() {
+        let mut doc = new_ansi_letter();
+        let tabs = vec![tab_stop(In(6.5), TabAln::Right, Some('.'))];
+        doc.add_par(
+            par("Introduction\t")
+                .add_run("3", Some(Style::Bold))
+                .set_tabs(tabs.clone()),
+        );
+        doc.add_par(par("Conclusion\t12").set_tabs(tabs));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_tab");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use crate::doc::*;
+use crate::err::*;
+use crate::fnt::*;
+use crate::inch::*;
+use crate::num::*;
+use crate::unit::*;
+use skia_safe::{
+    textlayout::{FontCollection, Paragraph},
+    Canvas, FontMgr, Paint, PaintStyle, Point, Rect,
+};
+
+/// Space between a table cell's border and its text in points.
+pub(crate) const CEL_PAD: f32 = 4.0;
+
+impl Doc {
+    /// Adds a _table_ without borders to the end of the document.
+    ///
+    /// ### Arguments
+    ///
+    /// * `rows` - Cell paragraphs of each row.
+    /// * `widths` - Width of each column.
+    pub fn add_table(&mut self, rows: Vec<Vec<Par>>, widths: Vec<In>) {
+        self.elms.push(Elm::Table {
+            rows,
+            col_widths: widths,
+            border: None,
+            header_rows: 0,
+            col_fmts: vec![],
+        });
+    }
+
+    /// Builds the cells of a table row laid out to their column widths.
+    ///
+    /// Cells are not first-line indented unless set,
+    /// numeric cells are formatted with their column's number format,
+    /// and cells past the last column are dropped.
+    ///
+    /// ### Returns
+    ///
+    /// The height of the row in points and
+    /// each cell's column width in points and paragraph.
+    pub(crate) fn bld_row(
+        &self,
+        row: &[Par],
+        col_widths: &[In],
+        col_fmts: &[Option<NumberFormat>],
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(f32, Vec<(f32, Paragraph, Par)>), DocError> {
+        let mut hgt: f32 = 0.0;
+        let mut cels = Vec::with_capacity(row.len());
+        for (col_idx, (cel, wid)) in row.iter().zip(col_widths).enumerate() {
+            let mut cel = Par {
+                has_ind: Some(cel.has_ind.unwrap_or(false)),
+                ..cel.clone()
+            };
+            if let Some(Some(num_fmt)) = col_fmts.get(col_idx) {
+                cel.txt = num_fmt.fmt_txt(&cel.txt);
+            }
+            let wid = wid.pt();
+            let paragraph =
+                self.bld_par_at(&cel, cel.txt_wid(wid - 2.0 * CEL_PAD), fnts, font_mgr)?;
+            hgt = hgt.max(paragraph.height());
+            cels.push((wid, paragraph, cel));
+        }
+        Ok((hgt + 2.0 * CEL_PAD, cels))
+    }
+}
+
+/// Draw the cells of a table row from left to right.
+///
+/// ### Arguments
+///
+/// * `canvas` - The canvas.
+/// * `cels` - Each cell's column width in points, paragraph, and cell.
+/// * `border` - Rule drawn around each cell, or `None` for no borders.
+/// * `x` - Distance from the left of the page to the row in points.
+/// * `y` - Distance from the top of the page to the row in points.
+/// * `hgt` - Height of the row in points.
+pub fn drw_row(
+    canvas: &Canvas,
+    cels: &[(f32, Paragraph, Par)],
+    border: Option<&Rule>,
+    x: f32,
+    y: f32,
+    hgt: f32,
+) {
+    let mut x = x;
+    for (wid, paragraph, _) in cels {
+        paragraph.paint(
+            canvas,
+            Point {
+                x: x + CEL_PAD,
+                y: y + CEL_PAD,
+            },
+        );
+        if let Some(border) = border {
+            let mut paint = Paint::default();
+            paint.set_color(border.clr);
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_stroke_width(border.thk.pt());
+            canvas.draw_rect(Rect::from_xywh(x, y, *wid, hgt), &paint);
+        }
+        x += wid;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lay::*;
+
+    #[test]
+    fn test_bld_row() {
+        let doc = new_ansi_letter();
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let row = vec![
+            par("Short"),
+            par("A longer cell wrapping onto several lines within its narrow column"),
+            par("Dropped"),
+        ];
+        let (hgt, cels) = doc
+            .bld_row(&row, &[In(1.0), In(1.0)], &[], &mut fnts, &font_mgr)
+            .expect("Failed to build row");
+        assert_eq!(cels.len(), 2);
+        assert_eq!(cels[0].0, In(1.0).pt());
+        assert!(cels[1].1.line_number() > 1);
+        assert_eq!(hgt, cels[1].1.height() + 2.0 * CEL_PAD);
+    }
+
+    #[test]
+    fn test_table_save_pdf() {
+        let mut doc = new_ansi_letter();
+        let rows: Vec<Vec<Par>> = (0..80)
+            .map(|idx| vec![par(&format!("Row {}", idx)), par("Value")])
+            .collect();
+        doc.add_par(par("Above"));
+        doc.elms.push(Elm::Table {
+            rows,
+            col_widths: vec![In(2.0), In(3.0)],
+            border: Some(rule()),
+            header_rows: 0,
+            col_fmts: vec![],
+        });
+        doc.add_table(vec![vec![par("A"), par("B")]], vec![In(1.0), In(1.0)]);
+        assert!(doc
+            .to_text()
+            .starts_with("Above\n\nRow 0\tValue\nRow 1\tValue"));
+
+        // Rows continue on the next page without splitting.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert!(pags.len() > 1);
+        let btm = (doc.sze.height - doc.mrg.btm).pt();
+        for lay in pags.iter().flatten() {
+            assert!(lay.y + lay.hgt <= btm + 0.01);
+        }
+        let row = &pags[1][0];
+        assert!(matches!(&row.cnt, LayCnt::Row(cels, _) if cels.len() == 2));
+        assert_eq!(row.wid, In(5.0).pt());
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_table");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_header_rows() {
+        let mut doc = new_ansi_letter();
+        let mut rows = vec![vec![par("Name"), par("Value")]];
+        rows.extend((0..80).map(|idx| vec![par(&format!("Row {}", idx)), par("Value")]));
+        doc.elms.push(Elm::Table {
+            rows,
+            col_widths: vec![In(2.0), In(3.0)],
+            border: Some(rule()),
+            header_rows: 1,
+            col_fmts: vec![],
+        });
+
+        // The header row begins each continuation page.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        assert!(pags.len() > 1);
+        let row_cnt: usize = pags.iter().map(Vec::len).sum();
+        assert_eq!(row_cnt, 81 + pags.len() - 1);
+        let btm = (doc.sze.height - doc.mrg.btm).pt();
+        for lay in pags.iter().flatten() {
+            assert!(lay.y + lay.hgt <= btm + 0.01);
+        }
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_header_rows");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+
+    #[test]
+    fn test_table_col_fmts() {
+        let mut doc = new_ansi_letter();
+        doc.elms.push(Elm::Table {
+            rows: vec![
+                vec![par("1234.56"), par("1234.56"), par("1234.56")],
+                vec![par("Total"), par("1e3"), par("n/a")],
+            ],
+            col_widths: vec![In(1.0), In(1.0), In(1.0)],
+            border: None,
+            header_rows: 0,
+            col_fmts: vec![None, Some(NUM_FMT_EU), Some(NUM_FMT_US)],
+        });
+
+        // Numeric cells are formatted by column when laid out.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let pags = doc
+            .lay_pags(&mut fnts, &font_mgr)
+            .expect("Failed to lay out pages");
+        let txts: Vec<Vec<&str>> = pags[0]
+            .iter()
+            .map(|lay| match &lay.cnt {
+                LayCnt::Row(cels, _) => cels.iter().map(|(_, _, cel)| cel.txt.as_str()).collect(),
+                _ => vec![],
+            })
+            .collect();
+        assert_eq!(txts[0], vec!["1234.56", "1.234,56", "1,234.56"]);
+        assert_eq!(txts[1], vec!["Total", "1e3", "n/a"]);
+    }
+}
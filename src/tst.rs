@@ -0,0 +1,13 @@
+use crate::fnt::*;
+use std::path::PathBuf;
+
+/// Returns a font bundled with the crate sources.
+///
+/// Tests lay out with this font rather than fetching a Google font
+/// so that they run without a network.
+pub(crate) fn tst_fnt() -> FontSource {
+    FontSource::File(PathBuf::from(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/fonts/DejaVuSerif.ttf"
+    )))
+}
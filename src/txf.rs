@@ -0,0 +1,178 @@
+use std::borrow::Cow;
+
+use crate::doc::*;
+use serde::{Deserialize, Serialize};
+use skia_safe::{
+    textlayout::{ParagraphBuilder, TextStyle},
+    Typeface,
+};
+
+/// Size of synthesized small capitals as a fraction of the font size.
+const SC_RTO: f32 = 0.75;
+
+/// Typographic case _transform_ of paragraph text.
+///
+/// - `Upper`: All letters in uppercase.
+/// - `Lower`: All letters in lowercase.
+/// - `SmallCaps`: Lowercase letters as small capitals.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TextTransform {
+    /// All letters in uppercase.
+    Upper,
+    /// All letters in lowercase.
+    Lower,
+    /// Lowercase letters as small capitals.
+    ///
+    /// Uses the font's `smcp` feature when present, or otherwise
+    /// uppercase letters at a reduced size.
+    SmallCaps,
+}
+
+impl TextTransform {
+    /// Returns text with its case transformed.
+    ///
+    /// Small capitals leave the text unchanged.
+    pub fn apply(self, txt: &str) -> String {
+        match self {
+            TextTransform::Upper => txt.to_uppercase(),
+            TextTransform::Lower => txt.to_lowercase(),
+            TextTransform::SmallCaps => txt.to_string(),
+        }
+    }
+}
+
+impl Par {
+    /// Returns a paragraph with the case of its text and runs transformed.
+    pub fn tf_par(&self) -> Cow<Par> {
+        match self.text_transform {
+            Some(tf) if tf != TextTransform::SmallCaps => {
+                let mut ret = self.clone();
+                ret.txt = tf.apply(&self.txt);
+                for run in ret.runs.iter_mut() {
+                    run.txt = tf.apply(&run.txt);
+                }
+                Cow::Owned(ret)
+            }
+            _ => Cow::Borrowed(self),
+        }
+    }
+}
+
+/// Returns `true` if a typeface lists an _OpenType_ substitution feature.
+///
+/// ### Arguments
+///
+/// * `typeface` - The typeface.
+/// * `ftr` - The four letter feature tag, such as `smcp`.
+pub fn has_ftr(typeface: &Typeface, ftr: &str) -> bool {
+    typeface
+        .copy_table_data(u32::from_be_bytes(*b"GSUB"))
+        .is_some_and(|gsub| gsub.as_bytes().windows(4).any(|tag| tag == ftr.as_bytes()))
+}
+
+/// Adds text to a paragraph builder with lowercase letters
+/// synthesized as uppercase letters at a reduced size.
+///
+/// Letters keep their length in bytes.
+///
+/// ### Arguments
+///
+/// * `par_bld` - The paragraph builder.
+/// * `txt` - The text.
+/// * `ts` - Text style of the text.
+pub fn add_sc_txt(par_bld: &mut ParagraphBuilder, txt: &str, ts: &TextStyle) {
+    let mut sc_ts = ts.clone();
+    sc_ts.set_font_size(ts.font_size() * SC_RTO);
+
+    // Segment text into small capitals and other characters.
+    let is_sc = |c: char| c.is_lowercase() && sc_chr(c).is_some();
+    let mut beg = 0;
+    while beg < txt.len() {
+        let fst = txt[beg..].chars().next().unwrap_or_default();
+        let end = txt[beg..]
+            .char_indices()
+            .find(|(_, c)| is_sc(*c) != is_sc(fst))
+            .map_or(txt.len(), |(idx, _)| beg + idx);
+        let seg = &txt[beg..end];
+        if is_sc(fst) {
+            par_bld.push_style(&sc_ts);
+            par_bld.add_text(seg.chars().filter_map(sc_chr).collect::<String>());
+            par_bld.pop();
+        } else {
+            par_bld.add_text(seg);
+        }
+        beg = end;
+    }
+}
+
+/// Returns the uppercase letter of a small capital
+/// when it is one character of the same length in bytes.
+fn sc_chr(c: char) -> Option<char> {
+    let mut upr = c.to_uppercase();
+    match (upr.next(), upr.next()) {
+        (Some(u), None) if u.len_utf8() == c.len_utf8() => Some(u),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fnt::*;
+    use skia_safe::{textlayout::FontCollection, FontMgr};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_tf_par() {
+        let par = par("Title").add_run(" case", Some(Style::Bold));
+        assert_eq!(par.tf_par().all_txt(), "Title case");
+
+        let upr = par.clone().set_text_transform(Some(TextTransform::Upper));
+        assert_eq!(upr.tf_par().all_txt(), "TITLE CASE");
+        assert_eq!(upr.all_txt(), "Title case");
+
+        let lwr = par.clone().set_text_transform(Some(TextTransform::Lower));
+        assert_eq!(lwr.tf_par().all_txt(), "title case");
+
+        let sc = par.set_text_transform(Some(TextTransform::SmallCaps));
+        assert_eq!(sc.tf_par().all_txt(), "Title case");
+    }
+
+    #[test]
+    fn test_sc_chr() {
+        assert_eq!(sc_chr('a'), Some('A'));
+        assert_eq!(sc_chr('é'), Some('É'));
+        assert_eq!(sc_chr('ß'), None);
+    }
+
+    #[test]
+    fn test_small_caps_save_pdf() {
+        let mut doc = new_ansi_letter();
+        let txt = "Small Caps Heading";
+        doc.add_par(par(txt).set_text_transform(Some(TextTransform::SmallCaps)));
+        doc.add_par(par(txt).set_text_transform(Some(TextTransform::Upper)));
+        assert_eq!(doc.to_text(), format!("{}\n\n{}", txt, txt));
+
+        // Small capitals are narrower than uppercase letters.
+        let mut fnts: HashMap<FontSource, FontCollection> = HashMap::new();
+        let font_mgr = FontMgr::new();
+        let Elm::Par(sc) = &doc.elms[0] else {
+            panic!("Expected a paragraph");
+        };
+        let Elm::Par(upr) = &doc.elms[1] else {
+            panic!("Expected a paragraph");
+        };
+        let sc_wid = doc
+            .bld_par(sc, &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph")
+            .longest_line();
+        let upr_wid = doc
+            .bld_par(upr, &mut fnts, &font_mgr)
+            .expect("Failed to build paragraph")
+            .longest_line();
+        assert!(sc_wid < upr_wid);
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_small_caps");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+    }
+}
@@ -24,3 +24,105 @@ pub trait Unit:
     /// Returns units of _points_.
     fn pt(&self) -> f32;
 }
+
+/// Implements arithmetic and assignment operators for a unit of measure
+/// wrapping an `f32`, with operands of the unit and of every primitive
+/// numeric type, and negation.
+macro_rules! impl_unit_ops {
+    ($u:ident) => {
+        $crate::unit::impl_unit_ops!(@ops $u, $u, rhs, rhs.0);
+        $crate::unit::impl_unit_ops!(@ops $u, i8, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, i16, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, i32, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, i64, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, i128, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, u8, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, u16, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, u32, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, u64, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, u128, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, f32, rhs, rhs as f32);
+        $crate::unit::impl_unit_ops!(@ops $u, f64, rhs, rhs as f32);
+
+        impl std::ops::Neg for $u {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $u(-self.0)
+            }
+        }
+    };
+    (@ops $u:ident, $t:ty, $rhs:ident, $val:expr) => {
+        impl std::ops::Add<$t> for $u {
+            type Output = Self;
+
+            fn add(self, $rhs: $t) -> Self {
+                $u(self.0 + $val)
+            }
+        }
+
+        impl std::ops::Sub<$t> for $u {
+            type Output = Self;
+
+            fn sub(self, $rhs: $t) -> Self {
+                $u(self.0 - $val)
+            }
+        }
+
+        impl std::ops::Mul<$t> for $u {
+            type Output = Self;
+
+            fn mul(self, $rhs: $t) -> Self {
+                $u(self.0 * $val)
+            }
+        }
+
+        impl std::ops::Div<$t> for $u {
+            type Output = Self;
+
+            fn div(self, $rhs: $t) -> Self {
+                $u(self.0 / $val)
+            }
+        }
+
+        impl std::ops::Rem<$t> for $u {
+            type Output = Self;
+
+            fn rem(self, $rhs: $t) -> Self {
+                $u(self.0 % $val)
+            }
+        }
+
+        impl std::ops::AddAssign<$t> for $u {
+            fn add_assign(&mut self, $rhs: $t) {
+                self.0 += $val;
+            }
+        }
+
+        impl std::ops::SubAssign<$t> for $u {
+            fn sub_assign(&mut self, $rhs: $t) {
+                self.0 -= $val;
+            }
+        }
+
+        impl std::ops::MulAssign<$t> for $u {
+            fn mul_assign(&mut self, $rhs: $t) {
+                self.0 *= $val;
+            }
+        }
+
+        impl std::ops::DivAssign<$t> for $u {
+            fn div_assign(&mut self, $rhs: $t) {
+                self.0 /= $val;
+            }
+        }
+
+        impl std::ops::RemAssign<$t> for $u {
+            fn rem_assign(&mut self, $rhs: $t) {
+                self.0 %= $val;
+            }
+        }
+    };
+}
+
+pub(crate) use impl_unit_ops;
@@ -0,0 +1,267 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::doc::*;
+use crate::err::*;
+use crate::inch::*;
+use crate::lay::*;
+use crate::mrg::*;
+use crate::sze::*;
+use crate::unit::*;
+
+/// A warning for an element laid out within the unsafe zone near a page edge.
+///
+/// Borderless and physical printers commonly clip content in this zone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CropWarning {
+    /// Index of the page.
+    pub pag: usize,
+    /// Index of the element in `elms`.
+    pub idx: usize,
+    /// Smallest distance from the element to a page edge.
+    pub dst: In,
+}
+
+impl Display for CropWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Element {} on page {} is {} from the page edge",
+            self.idx, self.pag, self.dst
+        )
+    }
+}
+
+impl Doc {
+    /// Checks that the document settings can be laid out.
+    ///
+    /// Margins of every section must leave a positive text area,
+    /// gaps between columns must leave a positive column width, frames must have a positive size, table columns must have
+    /// a positive width, QR codes must have a positive size,
+    /// font sizes must be positive,
+    /// line spacings must be finite, and the PDF quality
+    /// must be between `0.0` and `1.0`.
+    pub fn validate(&self) -> Result<(), DocError> {
+        // Check the text area of each section.
+        let (mut sze, mut mrg) = (self.sze, self.mrg);
+        let mut secs = vec![(sze, mrg)];
+        for elm in &self.elms {
+            if let Elm::SecBrk { sze: s, mrg: m } = elm {
+                sze = s.unwrap_or(sze);
+                mrg = m.unwrap_or(mrg);
+                secs.push((sze, mrg));
+            }
+        }
+        for (sze, mrg) in secs {
+            if sze.width - mrg.width() <= In(0.0) || sze.height - mrg.height() <= In(0.0) {
+                return Err(DocError::from_layout(&format!(
+                    "Margins {} x {} leave no text area on a {} x {} page",
+                    mrg.width(),
+                    mrg.height(),
+                    sze.width,
+                    sze.height
+                )));
+            }
+            let cols = self.columns.max(1);
+            if sze.width - mrg.width() - self.column_gap * (cols - 1) <= In(0.0) {
+                return Err(DocError::from_layout(&format!(
+                    "Column gaps {} x {} leave no column width on a {} text area",
+                    self.column_gap,
+                    cols - 1,
+                    sze.width - mrg.width()
+                )));
+            }
+        }
+        for frm in &self.frms {
+            if frm.wid <= In(0.0) || frm.hgt <= In(0.0) {
+                return Err(DocError::from_layout(&format!(
+                    "Frame on page {} has no area",
+                    frm.pag
+                )));
+            }
+        }
+
+        // Check the font sizes and spacings of the document and paragraphs.
+        let chk_fnt_sze = |fnt_sze: f32| {
+            if fnt_sze.is_finite() && fnt_sze > 0.0 {
+                Ok(())
+            } else {
+                Err(DocError::from_layout(&format!(
+                    "Font size {} is not positive",
+                    fnt_sze
+                )))
+            }
+        };
+        let chk_spc = |spc: LineSpace| {
+            if spc.val().is_finite() {
+                Ok(())
+            } else {
+                Err(DocError::from_layout(&format!(
+                    "Line spacing {} is not finite",
+                    spc.val()
+                )))
+            }
+        };
+        if !(0.0..=1.0).contains(&self.pdf_quality) {
+            return Err(DocError::from_layout(&format!(
+                "PDF quality {} is not between 0 and 1",
+                self.pdf_quality
+            )));
+        }
+        chk_fnt_sze(self.fnt_sze)?;
+        chk_spc(self.spc_lne)?;
+        chk_spc(self.spc_par_bfr)?;
+        chk_spc(self.spc_par_aft)?;
+        for elm in &self.elms {
+            if let Elm::Table { col_widths, .. } = elm {
+                if col_widths.iter().any(|wid| *wid <= In(0.0)) {
+                    return Err(DocError::from_layout("Table column width is not positive"));
+                }
+            }
+            if let Elm::QrCode { size, .. } = elm {
+                if *size <= In(0.0) {
+                    return Err(DocError::from_layout("QR code size is not positive"));
+                }
+            }
+            for par in elm.pars() {
+                par.fnt_sze.map_or(Ok(()), chk_fnt_sze)?;
+                for run in &par.runs {
+                    run.fnt_sze.map_or(Ok(()), chk_fnt_sze)?;
+                }
+                for spc in [par.spc_lne, par.spc_bfr, par.spc_aft]
+                    .into_iter()
+                    .flatten()
+                {
+                    chk_spc(spc)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks laid out content against an unsafe zone near the page edges.
+    ///
+    /// ### Arguments
+    ///
+    /// * `zne` - Width of the unsafe zone along each page edge.
+    ///
+    /// ### Returns
+    ///
+    /// A warning for each element falling within the unsafe zone.
+    pub fn validate_crop(&self, zne: In) -> Result<Vec<CropWarning>, DocError> {
+        let (doc, mut cache) = self.prep_lay()?;
+
+        let (pags, geos) = doc.lay_secs(&mut cache.fnts, &cache.font_mgr)?;
+        Ok(crop_wrns(&pags, &geos, zne))
+    }
+
+    /// Sets the width of the unsafe _crop zone_ along each page edge.
+    ///
+    /// ### Arguments
+    ///
+    /// * `crop_zone` - The new zone width, or `None` to check no zone.
+    ///
+    /// ### Returns
+    ///
+    /// Self with updated crop zone.
+    pub fn set_crop_zone(mut self, crop_zone: Option<In>) -> Self {
+        self.crop_zone = crop_zone;
+        self
+    }
+}
+
+/// Returns a warning for each laid out element within
+/// an unsafe zone along the edges of its page.
+///
+/// ### Arguments
+///
+/// * `pags` - Laid out elements of each page.
+/// * `geos` - Size and margins of each page.
+/// * `zne` - Width of the unsafe zone along each page edge.
+pub(crate) fn crop_wrns(pags: &[Vec<Lay>], geos: &[(Sze, Mrg)], zne: In) -> Vec<CropWarning> {
+    let mut ret = vec![];
+    for (pag, (lays, (sze, _))) in pags.iter().zip(geos).enumerate() {
+        let (pag_wid, pag_hgt) = sze.pt();
+        for lay in lays {
+            let dst = lay
+                .x
+                .min(lay.y)
+                .min(pag_wid - (lay.x + lay.wid))
+                .min(pag_hgt - (lay.y + lay.hgt));
+            if dst < zne.pt() {
+                ret.push(CropWarning {
+                    pag,
+                    idx: lay.idx,
+                    dst: In(dst / PT_PER_IN),
+                });
+            }
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let mut doc = new_ansi_letter();
+        doc.add_par(par("Hello").set_spc_lne(Some(LineSpace::Double)));
+        assert!(doc.validate().is_ok());
+
+        let nrw = doc.clone().set_sze(Sze::new(In(1.0), In(11.0)));
+        assert!(matches!(nrw.validate(), Err(DocError::InvalidLayout(_))));
+        let pth = std::env::temp_dir().join("pdf_doc_test_validate");
+        assert!(matches!(
+            nrw.save_pdf(&pth),
+            Err(DocError::InvalidLayout(_))
+        ));
+
+        let mut sec = doc.clone();
+        sec.add_sec_brk(None, Some(Mrg::uniform(In(6.0))));
+        assert!(sec.validate().is_err());
+
+        assert!(doc.clone().set_fnt_sze(0.0).validate().is_err());
+
+        let mut spc = doc.clone();
+        spc.add_par(par("Inf").set_spc_aft(Some(LineSpace::Custom(f32::INFINITY))));
+        assert!(spc.validate().is_err());
+
+        let mut run = doc;
+        run.add_par(par("").add_styled_run(Run::new("Neg", None).set_fnt_sze(Some(-1.0))));
+        assert!(run.validate().is_err());
+
+        // Gaps wider than the text area leave no column width.
+        let gap = new_ansi_letter().set_columns(3).set_column_gap(In(3.25));
+        assert!(matches!(gap.validate(), Err(DocError::InvalidLayout(_))));
+        assert!(gap.set_column_gap(In(3.0)).validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_crop() {
+        let mut doc = new_ansi_letter().set_mrg(Mrg::new(In(0.1), In(0.1), In(0.1), In(0.1)));
+        doc.add_svg(svg(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#,
+            In(1.0),
+            In(1.0),
+        ));
+
+        let wrns = doc.validate_crop(In(0.25)).expect("Failed to validate");
+        assert_eq!(wrns.len(), 1);
+        assert_eq!(wrns[0].pag, 0);
+        assert_eq!(wrns[0].idx, 0);
+
+        let wrns = doc.validate_crop(In(0.05)).expect("Failed to validate");
+        assert!(wrns.is_empty());
+
+        // Rendering reports elements within the document's crop zone.
+        let pth = std::env::temp_dir().join("pdf_doc_test_crop_zone");
+        let dgn = doc.save_pdf(&pth).expect("Failed to save pdf");
+        assert!(dgn.is_empty());
+        let doc = doc.set_crop_zone(Some(In(0.25)));
+        let dgn = doc.save_pdf(&pth).expect("Failed to save pdf");
+        assert_eq!(dgn.wrns.len(), 1);
+        assert!(dgn.wrns[0].starts_with("Element 0 on page 0"));
+    }
+}
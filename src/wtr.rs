@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use crate::clr::*;
+use crate::doc::*;
+use crate::err::*;
+use crate::fnt::*;
+use crate::sze::*;
+use serde::{Deserialize, Serialize};
+use skia_safe::{textlayout::FontCollection, Canvas, FontMgr, Paint, Point, Rect};
+
+/// Fraction of the page diagonal spanned by watermark text.
+const WTR_SPAN: f32 = 0.6;
+
+/// Large rotated semi-transparent text drawn beneath the body of every page.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Watermark {
+    /// Text _content_ of the watermark.
+    pub txt: String,
+    /// Opacity of the watermark from `0.0` to `1.0`.
+    pub opacity: f32,
+    /// Counter-clockwise rotation of the watermark in degrees.
+    pub angle: f32,
+}
+
+/// Creates a watermark with text, an opacity, and an angle in degrees.
+pub fn watermark(txt: &str, opacity: f32, angle: f32) -> Watermark {
+    Watermark {
+        txt: txt.into(),
+        opacity,
+        angle,
+    }
+}
+
+impl Watermark {
+    /// Returns the gray text color of the watermark at its opacity.
+    pub fn clr(&self) -> Color {
+        let a = (self.opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color::new(128, 128, 128, a)
+    }
+}
+
+impl Doc {
+    /// Draws the background color and watermark of a page
+    /// of a size beneath the body.
+    pub fn drw_bg(
+        &self,
+        canvas: &Canvas,
+        sze: Sze,
+        fnts: &mut HashMap<FontSource, FontCollection>,
+        font_mgr: &FontMgr,
+    ) -> Result<(), DocError> {
+        let (wid, hgt) = sze.pt();
+
+        // Fill the page with the background color.
+        if let Some(bg_clr) = self.bg_clr {
+            let mut paint = Paint::default();
+            paint.set_color(bg_clr);
+            canvas.draw_rect(Rect::from_wh(wid, hgt), &paint);
+        }
+
+        // Draw the watermark centered on the page.
+        if let Some(wtr) = &self.watermark {
+            let par = Par {
+                fnt_sze: Some(72.0),
+                clr: Some(wtr.clr()),
+                aln: Some(Align::Left),
+                has_ind: Some(false),
+                txt: wtr.txt.clone(),
+                ..Default::default()
+            };
+            let mut paragraph = self.bld_par(&par, fnts, font_mgr)?;
+            let diag = wid.hypot(hgt);
+            paragraph.layout(diag);
+            let txt_wid = paragraph.longest_line();
+            if txt_wid > 0.0 {
+                let scl = WTR_SPAN * diag / txt_wid;
+                canvas.save();
+                canvas.translate((wid / 2.0, hgt / 2.0));
+                canvas.rotate(-wtr.angle, None);
+                canvas.scale((scl, scl));
+                paragraph.paint(
+                    canvas,
+                    Point::new(-txt_wid / 2.0, -paragraph.height() / 2.0),
+                );
+                canvas.restore();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wtr_clr() {
+        assert_eq!(watermark("DRAFT", 0.5, 45.0).clr().a, 128);
+        assert_eq!(watermark("DRAFT", 2.0, 45.0).clr().a, 255);
+    }
+
+    #[test]
+    fn test_bg_wtr_save_pdf() {
+        let mut doc = new_ansi_letter()
+            .set_bg_clr(Some(Color::rgb(250, 245, 230)))
+            .set_watermark(Some(watermark("DRAFT", 0.2, 45.0)));
+        doc.add_par(par("First"));
+        doc.add_pag_brk();
+        doc.add_pag_brk();
+        doc.add_par(par("Last"));
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_bg_wtr");
+        doc.save_pdf(&pth).expect("Failed to save pdf");
+
+        // The background fills a rendered page.
+        let png = doc
+            .render_page_png_dpi(0, 10.0)
+            .expect("Failed to render png");
+        assert!(!png.is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::Path;
+
+use crate::doc::*;
+use crate::err::*;
+
+impl Doc {
+    /// Serialize the document to a _YAML_ string.
+    pub fn to_yaml(&self) -> Result<String, DocError> {
+        serde_yaml::to_string(self).map_err(DocError::from)
+    }
+
+    /// Deserialize a document from a _YAML_ string.
+    pub fn from_yaml(yaml_str: &str) -> Result<Doc, DocError> {
+        serde_yaml::from_str(yaml_str).map_err(DocError::from)
+    }
+
+    /// Save the document as a _YAML_ file.
+    ///
+    /// `.yaml` file suffix is automatically appended.
+    pub fn save_yaml<P>(&self, pth: P) -> Result<(), DocError>
+    where
+        P: AsRef<Path>,
+    {
+        let yaml_str = self.to_yaml()?;
+        let file_path = pth.as_ref().with_extension("yaml");
+        fs::write(file_path, yaml_str).map_err(DocError::FileError)
+    }
+
+    /// Read a _YAML_ file from disk.
+    ///
+    /// `.yaml` file suffix is automatically appended.
+    pub fn read_yaml<P>(pth: P) -> Result<Doc, DocError>
+    where
+        P: AsRef<Path>,
+    {
+        let file_path = pth.as_ref().with_extension("yaml");
+        let yaml_str = fs::read_to_string(file_path).map_err(DocError::from)?;
+        Doc::from_yaml(&yaml_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let mut org = new_a4().set_title("Report");
+        org.add_par(par("Hello").set_spc_aft(Some(LineSpace::Custom(0.6))));
+        org.add_pag_brk();
+        org.add_rule();
+
+        let pth = std::env::temp_dir().join("pdf_doc_test_yaml");
+        org.save_yaml(&pth).expect("Failed to save yaml");
+        let de = Doc::read_yaml(&pth).expect("Failed to read yaml");
+        assert_eq!(de.meta.title, "Report");
+        assert_eq!(de.sze, org.sze);
+        assert_eq!(de.elms.len(), 3);
+        assert_eq!(de.to_yaml().unwrap(), org.to_yaml().unwrap());
+
+        assert!(matches!(
+            Doc::from_yaml("elms: 3"),
+            Err(DocError::YamlError(_))
+        ));
+    }
+}